@@ -1,5 +1,5 @@
 //! Contains [EnemyBundle] which is the main [LdtkEntity] for this example.
-use crate::{equipment::EquipmentDrops, health::Health, mother::UnresolvedMotherRef};
+use crate::{equipment::EquipmentDrops, health::Health};
 use bevy::prelude::*;
 use bevy_ecs_ldtk::prelude::*;
 
@@ -12,8 +12,6 @@ pub struct EnemyBundle {
     health: Health,
     #[with(EquipmentDrops::from_field)]
     equipment_drops: EquipmentDrops,
-    #[with(UnresolvedMotherRef::from_mother_field)]
-    unresolved_mother: UnresolvedMotherRef,
     #[sprite_sheet]
     sprite_sheet: Sprite,
 }
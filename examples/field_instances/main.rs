@@ -10,8 +10,8 @@
 //! - mother, a nullable entity reference.
 //!
 //! This example accesses all of these and stores them on the enemy entity via components.
-//! With the mother field - it also demonstrates one possible pattern for resolving an LDtk entity
-//! reference to an actual bevy "relational" component.
+//! With the mother field - it also demonstrates how the entity references resolved by the plugin
+//! can be turned into an actual bevy "relational" component.
 //!
 //! Note that there are similar APIs for accessing and coercing any possible field type in LDtk.
 //! Check out the
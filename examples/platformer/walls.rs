@@ -1,195 +1,73 @@
-use std::collections::HashSet;
-
-use bevy::{platform::collections::HashMap, prelude::*};
-use bevy_ecs_ldtk::prelude::*;
+use bevy::prelude::*;
+use bevy_ecs_ldtk::{prelude::*, utils::merge_int_grid_cells};
 
 use avian2d::prelude::*;
 
-#[derive(Copy, Clone, Eq, PartialEq, Debug, Default, Component)]
-pub struct Wall;
+/// IntGrid value of dirt walls
+const DIRT: i32 = 1;
 
-#[derive(Clone, Debug, Default, Bundle, LdtkIntCell)]
-pub struct WallBundle {
-    wall: Wall,
-}
+/// IntGrid value of stone walls
+const STONE: i32 = 3;
 
-/// Spawns heron collisions for the walls of a level
+/// Spawns avian collisions for the walls of a level
 ///
-/// You could just insert a ColliderBundle into the WallBundle,
+/// You could just insert a collider into an LdtkIntCell bundle,
 /// but this spawns a different collider for EVERY wall tile.
 /// This approach leads to bad performance.
 ///
-/// Instead, by flagging the wall tiles and spawning the collisions later,
-/// we can minimize the amount of colliding entities.
-///
-/// The algorithm used here is a nice compromise between simplicity, speed,
-/// and a small number of rectangle colliders.
-/// In basic terms, it will:
-/// 1. consider where the walls are
-/// 2. combine wall tiles into flat "plates" in each individual row
-/// 3. combine the plates into rectangles across multiple rows wherever possible
-/// 4. spawn colliders for each rectangle
+/// Instead, we merge the wall cells of each IntGrid layer into rectangles with
+/// `merge_int_grid_cells`, minimizing the amount of colliding entities.
+/// Dirt and stone are merged together, so there are no seams where they meet.
+/// If every IntGrid value should be merged separately, the plugin can do this for you with the
+/// `IntGridRects` component.
 pub fn spawn_wall_collision(
     mut commands: Commands,
-    wall_query: Query<(&GridCoords, &ChildOf), Added<Wall>>,
-    parent_query: Query<&ChildOf, Without<Wall>>,
-    level_query: Query<(Entity, &LevelIid)>,
-    ldtk_projects: Query<&LdtkProjectHandle>,
-    ldtk_project_assets: Res<Assets<LdtkProject>>,
+    layer_query: Query<(&IntGridValues, &LayerMetadata, &ChildOf), Added<IntGridValues>>,
 ) {
-    /// Represents a wide wall that is 1 tile tall
-    /// Used to spawn wall collisions
-    #[derive(Clone, Eq, PartialEq, Debug, Default, Hash)]
-    struct Plate {
-        left: i32,
-        right: i32,
-    }
-
-    /// A simple rectangle type representing a wall of any size
-    struct Rect {
-        left: i32,
-        right: i32,
-        top: i32,
-        bottom: i32,
-    }
-
-    // Consider where the walls are
-    // storing them as GridCoords in a HashSet for quick, easy lookup
-    //
-    // The key of this map will be the entity of the level the wall belongs to.
-    // This has two consequences in the resulting collision entities:
-    // 1. it forces the walls to be split along level boundaries
-    // 2. it lets us easily add the collision entities as children of the appropriate level entity
-    let mut level_to_wall_locations: HashMap<Entity, HashSet<GridCoords>> = HashMap::new();
-
-    wall_query.iter().for_each(|(&grid_coords, child_of)| {
-        // An intgrid tile's direct parent will be a layer entity, not the level entity
-        // To get the level entity, you need the tile's grandparent.
-        // This is where parent_query comes in.
-        if let Ok(parent_child_of) = parent_query.get(child_of.parent()) {
-            level_to_wall_locations
-                .entry(parent_child_of.parent())
-                .or_default()
-                .insert(grid_coords);
-        }
-    });
-
-    if !wall_query.is_empty() {
-        level_query.iter().for_each(|(level_entity, level_iid)| {
-            if let Some(level_walls) = level_to_wall_locations.get(&level_entity) {
-                let ldtk_project = ldtk_project_assets
-                    .get(ldtk_projects.single().unwrap())
-                    .expect("Project should be loaded if level has spawned");
-
-                let level = ldtk_project
-                    .as_standalone()
-                    .get_loaded_level_by_iid(&level_iid.to_string())
-                    .expect("Spawned level should exist in LDtk project");
-
-                let LayerInstance {
-                    c_wid: width,
-                    c_hei: height,
-                    grid_size,
-                    ..
-                } = level.layer_instances()[0];
-
-                // combine wall tiles into flat "plates" in each individual row
-                let mut plate_stack: Vec<Vec<Plate>> = Vec::new();
-
-                for y in 0..height {
-                    let mut row_plates: Vec<Plate> = Vec::new();
-                    let mut plate_start = None;
-
-                    // + 1 to the width so the algorithm "terminates" plates that touch the right edge
-                    for x in 0..width + 1 {
-                        match (plate_start, level_walls.contains(&GridCoords { x, y })) {
-                            (Some(s), false) => {
-                                row_plates.push(Plate {
-                                    left: s,
-                                    right: x - 1,
-                                });
-                                plate_start = None;
-                            }
-                            (None, true) => plate_start = Some(x),
-                            _ => (),
-                        }
-                    }
-
-                    plate_stack.push(row_plates);
-                }
-
-                // combine "plates" into rectangles across multiple rows
-                let mut rect_builder: HashMap<Plate, Rect> = HashMap::new();
-                let mut prev_row: Vec<Plate> = Vec::new();
-                let mut wall_rects: Vec<Rect> = Vec::new();
-
-                // an extra empty row so the algorithm "finishes" the rects that touch the top edge
-                plate_stack.push(Vec::new());
-
-                for (y, current_row) in plate_stack.into_iter().enumerate() {
-                    for prev_plate in &prev_row {
-                        if !current_row.contains(prev_plate) {
-                            // remove the finished rect so that the same plate in the future starts a new rect
-                            if let Some(rect) = rect_builder.remove(prev_plate) {
-                                wall_rects.push(rect);
-                            }
-                        }
-                    }
-                    for plate in &current_row {
-                        rect_builder
-                            .entry(plate.clone())
-                            .and_modify(|e| e.top += 1)
-                            .or_insert(Rect {
-                                bottom: y as i32,
-                                top: y as i32,
-                                left: plate.left,
-                                right: plate.right,
-                            });
-                    }
-                    prev_row = current_row;
-                }
-
-                commands.entity(level_entity).with_children(|level| {
-                    // Spawn colliders for every rectangle..
-                    // Making the collider a child of the level serves two purposes:
-                    // 1. Adjusts the transforms to be relative to the level for free
-                    // 2. the colliders will be despawned automatically when levels unload
-                    for wall_rect in wall_rects {
-                        level
-                            .spawn_empty()
-                            .insert(Collider::rectangle(
-                                (wall_rect.right as f32 - wall_rect.left as f32 + 1.)
-                                    * grid_size as f32,
-                                (wall_rect.top as f32 - wall_rect.bottom as f32 + 1.)
-                                    * grid_size as f32,
-                            ))
-                            .insert(RigidBody::Static)
-                            .insert(Friction::new(1.0))
-                            .insert(Transform::from_xyz(
-                                (wall_rect.left + wall_rect.right + 1) as f32 * grid_size as f32
-                                    / 2.,
-                                (wall_rect.bottom + wall_rect.top + 1) as f32 * grid_size as f32
-                                    / 2.,
-                                0.,
-                            ))
-                            .insert(GlobalTransform::default());
-                    }
-                });
+    for (int_grid_values, layer_metadata, child_of) in layer_query.iter() {
+        let wall_rects = merge_int_grid_cells(
+            int_grid_values.values(),
+            int_grid_values.c_wid(),
+            int_grid_values.c_hei(),
+            |value| matches!(value, DIRT | STONE).then_some(()),
+        );
+
+        let grid_size = layer_metadata.grid_size as f32;
+        let layer_offset = Vec2::new(
+            layer_metadata.px_total_offset_x as f32,
+            -layer_metadata.px_total_offset_y as f32,
+        );
+
+        // An IntGrid layer's parent is the level entity
+        commands.entity(child_of.parent()).with_children(|level| {
+            // Spawn colliders for every rectangle..
+            // Making the collider a child of the level serves two purposes:
+            // 1. Adjusts the transforms to be relative to the level for free
+            // 2. the colliders will be despawned automatically when levels unload
+            for wall_rect in wall_rects.get(&()).into_iter().flatten() {
+                let wall_rect = Rect::from_corners(
+                    wall_rect.min.as_vec2() * grid_size + layer_offset,
+                    wall_rect.max.as_vec2() * grid_size + layer_offset,
+                );
+
+                level.spawn((
+                    Collider::rectangle(wall_rect.width(), wall_rect.height()),
+                    RigidBody::Static,
+                    Friction::new(1.0),
+                    Transform::from_translation(wall_rect.center().extend(0.)),
+                ));
             }
         });
     }
 }
 
-/// Plugin which spawns walls on appropriate LDtk int cells,
-/// then merges them together to reduce physics load.
+/// Plugin which spawns colliders for the walls of every level.
 ///
 /// Walls are hardcoded as int cell values 1 (dirt) and 3 (stone).
 pub struct WallPlugin;
 
 impl Plugin for WallPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, spawn_wall_collision)
-            .register_ldtk_int_cell::<WallBundle>(1) //dirt
-            .register_ldtk_int_cell::<WallBundle>(3); //stone
+        app.add_systems(Update, spawn_wall_collision);
     }
 }
@@ -0,0 +1,165 @@
+use quote::quote;
+use serde_json::Value;
+use std::path::PathBuf;
+
+pub fn expand_ldtk_enums(path: syn::LitStr) -> proc_macro::TokenStream {
+    match expand_ldtk_enums_inner(&path) {
+        Ok(tokens) => tokens.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+fn expand_ldtk_enums_inner(path: &syn::LitStr) -> syn::Result<proc_macro2::TokenStream> {
+    let error = |message: String| syn::Error::new(path.span(), message);
+
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR")
+        .map_err(|_| error("CARGO_MANIFEST_DIR is not set".to_string()))?;
+    let full_path = PathBuf::from(manifest_dir).join(path.value());
+
+    let contents = std::fs::read_to_string(&full_path)
+        .map_err(|e| error(format!("could not read {}: {e}", full_path.display())))?;
+
+    let project: Value = serde_json::from_str(&contents)
+        .map_err(|e| error(format!("could not parse {}: {e}", full_path.display())))?;
+
+    let defs = &project["defs"];
+    let enum_definitions = defs["enums"]
+        .as_array()
+        .into_iter()
+        .chain(defs["externalEnums"].as_array())
+        .flatten();
+
+    let enums = enum_definitions
+        .map(|enum_definition| expand_enum_definition(enum_definition, &error))
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    // Including the file makes cargo rebuild the invoking crate whenever the project changes.
+    let full_path = full_path.to_string_lossy();
+
+    Ok(quote! {
+        const _: &[u8] = include_bytes!(#full_path);
+
+        #(#enums)*
+    })
+}
+
+fn parse_ident(
+    identifier: &str,
+    error: &impl Fn(String) -> syn::Error,
+) -> syn::Result<proc_macro2::Ident> {
+    syn::parse_str::<proc_macro2::Ident>(identifier)
+        .map_err(|_| error(format!("{identifier:?} is not a valid Rust identifier")))
+}
+
+fn expand_enum_definition(
+    enum_definition: &Value,
+    error: &impl Fn(String) -> syn::Error,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let identifier = enum_definition["identifier"]
+        .as_str()
+        .ok_or_else(|| error("enum definition is missing its identifier".to_string()))?;
+    let enum_name = parse_ident(identifier, error)?;
+
+    let values = enum_definition["values"].as_array().ok_or_else(|| {
+        error(format!(
+            "{identifier} enum definition is missing its values"
+        ))
+    })?;
+
+    let mut variants = Vec::new();
+    let mut ids = Vec::new();
+    let mut colors = Vec::new();
+    let mut tile_rects = Vec::new();
+
+    for value in values {
+        let id = value["id"]
+            .as_str()
+            .ok_or_else(|| error(format!("{identifier} enum has a value without an id")))?;
+        variants.push(parse_ident(id, error)?);
+        ids.push(id);
+
+        let color = value["color"].as_i64().unwrap_or_default();
+        let (r, g, b) = ((color >> 16) as u8, (color >> 8) as u8, color as u8);
+        colors.push(quote! { bevy::prelude::Color::srgb_u8(#r, #g, #b) });
+
+        tile_rects.push(match &value["tileRect"] {
+            Value::Object(tile_rect) => {
+                let field =
+                    |name: &str| tile_rect.get(name).and_then(Value::as_i64).unwrap_or(0) as i32;
+                let (x, y, w, h, tileset_uid) = (
+                    field("x"),
+                    field("y"),
+                    field("w"),
+                    field("h"),
+                    field("tilesetUid"),
+                );
+
+                quote! {
+                    Some(bevy_ecs_ldtk::ldtk::TilesetRectangle {
+                        x: #x,
+                        y: #y,
+                        w: #w,
+                        h: #h,
+                        tileset_uid: #tileset_uid,
+                    })
+                }
+            }
+            _ => quote! { None },
+        });
+    }
+
+    let doc = format!(" Generated from the `{identifier}` enum definition of an LDtk project.");
+
+    Ok(quote! {
+        #[doc = #doc]
+        #[allow(non_camel_case_types)]
+        #[derive(Copy, Clone, Eq, PartialEq, Debug, Hash, bevy::prelude::Reflect)]
+        pub enum #enum_name {
+            #(#variants,)*
+        }
+
+        impl bevy_ecs_ldtk::ldtk::ldtk_enum::LdtkEnum for #enum_name {
+            const IDENTIFIER: &'static str = #identifier;
+
+            const VALUES: &'static [Self] = &[#(Self::#variants,)*];
+
+            fn as_str(&self) -> &'static str {
+                match *self {
+                    #(Self::#variants => #ids,)*
+                }
+            }
+
+            fn color(&self) -> bevy::prelude::Color {
+                match *self {
+                    #(Self::#variants => #colors,)*
+                }
+            }
+
+            fn tile_rect(&self) -> Option<bevy_ecs_ldtk::ldtk::TilesetRectangle> {
+                match *self {
+                    #(Self::#variants => #tile_rects,)*
+                }
+            }
+        }
+
+        impl std::str::FromStr for #enum_name {
+            type Err = bevy_ecs_ldtk::ldtk::ldtk_enum::ParseLdtkEnumError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                match s {
+                    #(#ids => Ok(Self::#variants),)*
+                    _ => Err(bevy_ecs_ldtk::ldtk::ldtk_enum::ParseLdtkEnumError {
+                        enum_identifier: #identifier,
+                        value: s.to_string(),
+                    }),
+                }
+            }
+        }
+
+        impl std::fmt::Display for #enum_name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str(bevy_ecs_ldtk::ldtk::ldtk_enum::LdtkEnum::as_str(self))
+            }
+        }
+    })
+}
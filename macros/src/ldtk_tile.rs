@@ -0,0 +1,150 @@
+use quote::quote;
+
+static LDTK_TILE_ATTRIBUTE_NAME: &str = "ldtk_tile";
+static FROM_TILE_INSTANCE_ATTRIBUTE_NAME: &str = "from_tile_instance";
+static WITH_ATTRIBUTE_NAME: &str = "with";
+static DEFAULT_ATTRIBUTE_NAME: &str = "default";
+
+pub fn expand_ldtk_tile_derive(ast: syn::DeriveInput) -> proc_macro::TokenStream {
+    let struct_name = &ast.ident;
+
+    let fields = match &ast.data {
+        syn::Data::Struct(syn::DataStruct {
+            fields: syn::Fields::Named(fields),
+            ..
+        }) => &fields.named,
+        _ => panic!("Expected a struct with named fields."),
+    };
+
+    let mut field_constructions = Vec::new();
+    for field in fields {
+        let field_name = field.ident.as_ref().unwrap();
+        let field_type = &field.ty;
+
+        let ldtk_tile = field
+            .attrs
+            .iter()
+            .find(|a| *a.path().get_ident().as_ref().unwrap() == LDTK_TILE_ATTRIBUTE_NAME);
+        if let Some(attribute) = ldtk_tile {
+            field_constructions.push(expand_ldtk_tile_attribute(
+                attribute, field_name, field_type,
+            ));
+            continue;
+        }
+
+        let from_tile_instance = field
+            .attrs
+            .iter()
+            .find(|a| *a.path().get_ident().as_ref().unwrap() == FROM_TILE_INSTANCE_ATTRIBUTE_NAME);
+        if let Some(attribute) = from_tile_instance {
+            field_constructions.push(expand_from_tile_instance_attribute(
+                attribute, field_name, field_type,
+            ));
+            continue;
+        }
+
+        let with = field
+            .attrs
+            .iter()
+            .find(|a| *a.path().get_ident().as_ref().unwrap() == WITH_ATTRIBUTE_NAME);
+        if let Some(attribute) = with {
+            field_constructions.push(expand_with_attribute(attribute, field_name, field_type));
+            continue;
+        }
+
+        let default = field
+            .attrs
+            .iter()
+            .find(|a| *a.path().get_ident().as_ref().unwrap() == DEFAULT_ATTRIBUTE_NAME);
+        if let Some(attribute) = default {
+            field_constructions.push(expand_default_attribute(attribute, field_name, field_type));
+            continue;
+        }
+    }
+
+    let generics = &ast.generics;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let struct_update = if field_constructions.len() < fields.len() {
+        quote! { ..<Self as std::default::Default>::default() }
+    } else {
+        quote! {}
+    };
+
+    let gen = quote! {
+        impl #impl_generics bevy_ecs_ldtk::prelude::LdtkTile for #struct_name #ty_generics #where_clause {
+            fn bundle_tile(
+                tile_instance: &bevy_ecs_ldtk::ldtk::TileInstance,
+                tileset_definition: &bevy_ecs_ldtk::ldtk::TilesetDefinition,
+            ) -> Self {
+                Self {
+                    #(#field_constructions)*
+                    #struct_update
+                }
+            }
+        }
+    };
+    gen.into()
+}
+
+fn expand_ldtk_tile_attribute(
+    attribute: &syn::Attribute,
+    field_name: &syn::Ident,
+    field_type: &syn::Type,
+) -> proc_macro2::TokenStream {
+    match attribute.meta {
+        syn::Meta::Path(_) => {
+            quote! {
+                #field_name: <#field_type as bevy_ecs_ldtk::prelude::LdtkTile>::bundle_tile(tile_instance, tileset_definition),
+            }
+        }
+        _ => panic!("#[ldtk_tile] attribute should take the form #[ldtk_tile]"),
+    }
+}
+
+fn expand_from_tile_instance_attribute(
+    attribute: &syn::Attribute,
+    field_name: &syn::Ident,
+    field_type: &syn::Type,
+) -> proc_macro2::TokenStream {
+    match attribute.meta {
+        syn::Meta::Path(_) => {
+            quote! {
+                #field_name: <#field_type as From<&bevy_ecs_ldtk::ldtk::TileInstance>>::from(tile_instance),
+            }
+        }
+        _ => {
+            panic!("#[from_tile_instance] attribute should take the form #[from_tile_instance]")
+        }
+    }
+}
+
+fn expand_with_attribute(
+    attribute: &syn::Attribute,
+    field_name: &syn::Ident,
+    _: &syn::Type,
+) -> proc_macro2::TokenStream {
+    if let syn::Meta::List(syn::MetaList { ref tokens, .. }) = attribute.meta {
+        if let Ok(path) = syn::parse2::<syn::Path>(tokens.clone()) {
+            return quote! {
+                #field_name: #path(tile_instance),
+            };
+        }
+    }
+    panic!("#[with...] attribute should take the form #[with(function_name)]")
+}
+
+fn expand_default_attribute(
+    attribute: &syn::Attribute,
+    field_name: &syn::Ident,
+    _: &syn::Type,
+) -> proc_macro2::TokenStream {
+    match attribute.meta {
+        syn::Meta::Path(_) => {
+            quote! {
+                #field_name: Default::default(),
+            }
+        }
+        _ => panic!("#[default] attribute should take the form #[default]"),
+    }
+}
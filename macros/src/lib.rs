@@ -1,7 +1,9 @@
 use proc_macro::TokenStream;
 
 mod ldtk_entity;
+mod ldtk_enums;
 mod ldtk_int_cell;
+mod ldtk_tile;
 mod long_spritesheet;
 
 #[proc_macro_derive(
@@ -32,3 +34,23 @@ pub fn ldtk_int_cell_derive(input: TokenStream) -> TokenStream {
 
     ldtk_int_cell::expand_ldtk_int_cell_derive(ast)
 }
+
+#[proc_macro_derive(LdtkTile, attributes(ldtk_tile, from_tile_instance, with, default))]
+pub fn ldtk_tile_derive(input: TokenStream) -> TokenStream {
+    let ast = syn::parse(input).unwrap();
+
+    ldtk_tile::expand_ldtk_tile_derive(ast)
+}
+
+/// Generates Rust enums from the enum definitions of an LDtk project.
+///
+/// The path is relative to the invoking crate's `Cargo.toml`.
+/// See [`LdtkEnum`] for details about the generated enums.
+///
+/// [`LdtkEnum`]: https://docs.rs/bevy_ecs_ldtk/latest/bevy_ecs_ldtk/ldtk/ldtk_enum/trait.LdtkEnum.html
+#[proc_macro]
+pub fn ldtk_enums(input: TokenStream) -> TokenStream {
+    let path = syn::parse_macro_input!(input as syn::LitStr);
+
+    ldtk_enums::expand_ldtk_enums(path)
+}
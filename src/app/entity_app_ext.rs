@@ -25,8 +25,10 @@ pub trait LdtkEntityAppExt {
     /// In these cases, registrations are prioritized in order of most to least specific:
     /// 1. `layer_identifier` and `entity_identifier` are specified
     /// 2. Just `entity_identifier` is specified
-    /// 3. Just `layer_identifier` is specified
-    /// 4. Neither `entity_identifier` nor `layer_identifier` are specified
+    /// 3. One of the entity's tags is registered with
+    ///    [LdtkEntityAppExt::register_ldtk_entity_for_tag]
+    /// 4. Just `layer_identifier` is specified
+    /// 5. Neither `entity_identifier` nor `layer_identifier` are specified
     fn register_ldtk_entity_for_layer_optional<B: LdtkEntity + Bundle>(
         &mut self,
         layer_identifier: Option<String>,
@@ -85,6 +87,40 @@ pub trait LdtkEntityAppExt {
         self.register_ldtk_entity_for_layer_optional::<B>(None, Some(entity_identifier.to_string()))
     }
 
+    /// Registers [LdtkEntity] types to be spawned for any LDtk entity whose definition has the
+    /// given tag.
+    ///
+    /// This lets new entity types that designers add to a tagged group get sensible components
+    /// without any code changes.
+    /// Registrations for an entity's identifier take precedence over registrations for its tags,
+    /// while tag registrations take precedence over registrations for just a layer.
+    /// If an entity has several registered tags, the first one in its definition's tag list is
+    /// used.
+    /// See [LdtkEntityAppExt::register_ldtk_entity_for_layer_optional] for the full precedence
+    /// order.
+    /// ```no_run
+    /// use bevy::prelude::*;
+    /// use bevy_ecs_ldtk::prelude::*;
+    ///
+    /// fn main() {
+    ///     App::empty()
+    ///         .add_plugins(LdtkPlugin)
+    ///         .register_ldtk_entity_for_tag::<EnemyBundle>("Enemy")
+    ///         // add other systems, plugins, resources...
+    ///         .run();
+    /// }
+    ///
+    /// # #[derive(Component, Default)]
+    /// # struct Enemy;
+    /// #[derive(Bundle, LdtkEntity, Default)]
+    /// pub struct EnemyBundle {
+    ///     enemy: Enemy,
+    ///     #[sprite_sheet]
+    ///     sprite: Sprite,
+    /// }
+    /// ```
+    fn register_ldtk_entity_for_tag<B: LdtkEntity + Bundle>(&mut self, tag: &str) -> &mut Self;
+
     /// Similar to [LdtkEntityAppExt::register_ldtk_entity_for_layer], except it applies the
     /// registration to all entities on the given layer.
     fn register_default_ldtk_entity_for_layer<B: LdtkEntity + Bundle>(
@@ -121,6 +157,22 @@ impl LdtkEntityAppExt for App {
         }
         self
     }
+
+    fn register_ldtk_entity_for_tag<B: LdtkEntity + Bundle>(&mut self, tag: &str) -> &mut Self {
+        let new_entry = Box::new(PhantomLdtkEntity::<B>::new());
+        match self.world_mut().get_non_send_mut::<LdtkEntityTagMap>() {
+            Some(mut entries) => {
+                entries.insert(tag.to_string(), new_entry);
+            }
+            None => {
+                let mut bundle_map = LdtkEntityTagMap::new();
+                bundle_map.insert(tag.to_string(), new_entry);
+                self.world_mut()
+                    .insert_non_send::<LdtkEntityTagMap>(bundle_map);
+            }
+        }
+        self
+    }
 }
 
 #[cfg(test)]
@@ -177,4 +229,17 @@ mod tests {
 
         assert!(ldtk_entity_map.contains_key(&(None, None)));
     }
+
+    #[test]
+    fn test_ldtk_entity_tag_registrations() {
+        let mut app = App::new();
+        app.register_ldtk_entity_for_tag::<LdtkEntityBundle>("Enemy")
+            .register_ldtk_entity_for_tag::<LdtkEntityBundle>("Pickup");
+
+        let ldtk_entity_tag_map = app.world_mut().get_non_send::<LdtkEntityTagMap>().unwrap();
+
+        assert!(ldtk_entity_tag_map.contains_key("Enemy"));
+        assert!(ldtk_entity_tag_map.contains_key("Pickup"));
+        assert!(!ldtk_entity_tag_map.contains_key("Npc"));
+    }
 }
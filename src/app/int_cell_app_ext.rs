@@ -25,8 +25,12 @@ pub trait LdtkIntCellAppExt {
     /// In these cases, registrations are prioritized in order of most to least specific:
     /// 1. `layer_identifier` and `value` are specified
     /// 2. Just `value` is specified
-    /// 3. Just `layer_identifier` is specified
-    /// 4. Neither `value` nor `layer_identifier` are specified
+    /// 3. The value's identifier is registered with
+    ///    [LdtkIntCellAppExt::register_ldtk_int_cell_by_identifier]
+    /// 4. The value's group identifier is registered with
+    ///    [LdtkIntCellAppExt::register_ldtk_int_cell_by_group]
+    /// 5. Just `layer_identifier` is specified
+    /// 6. Neither `value` nor `layer_identifier` are specified
     fn register_ldtk_int_cell_for_layer_optional<B: LdtkIntCell + Bundle>(
         &mut self,
         layer_identifier: Option<String>,
@@ -82,6 +86,51 @@ pub trait LdtkIntCellAppExt {
         self.register_ldtk_int_cell_for_layer_optional::<B>(None, Some(value))
     }
 
+    /// Registers [LdtkIntCell] types to be inserted for IntGrid values with the given identifier.
+    ///
+    /// Unlike registering by numeric value, this keeps working if designers renumber the values
+    /// of a layer.
+    /// The identifier is resolved through the [IntGridValueDefinition]s of each IntGrid layer's
+    /// definition, so it applies to values with this identifier on any layer.
+    /// ```no_run
+    /// use bevy::prelude::*;
+    /// use bevy_ecs_ldtk::prelude::*;
+    ///
+    /// fn main() {
+    ///     App::empty()
+    ///         .add_plugins(LdtkPlugin)
+    ///         .register_ldtk_int_cell_by_identifier::<WaterBundle>("Water")
+    ///         // add other systems, plugins, resources...
+    ///         .run();
+    /// }
+    ///
+    /// # #[derive(Component, Default)]
+    /// # struct Water;
+    /// #[derive(Bundle, LdtkIntCell, Default)]
+    /// pub struct WaterBundle {
+    ///     water: Water,
+    /// }
+    /// ```
+    ///
+    /// [IntGridValueDefinition]: crate::ldtk::IntGridValueDefinition
+    fn register_ldtk_int_cell_by_identifier<B: LdtkIntCell + Bundle>(
+        &mut self,
+        identifier: &str,
+    ) -> &mut Self;
+
+    /// Similar to [LdtkIntCellAppExt::register_ldtk_int_cell_by_identifier], except it applies
+    /// the registration to all IntGrid values in the group with the given identifier.
+    ///
+    /// This way, a single bundle can apply to every value in a group like "Solid".
+    /// Groups are defined by the [IntGridValueGroupDefinition]s of each IntGrid layer's
+    /// definition.
+    ///
+    /// [IntGridValueGroupDefinition]: crate::ldtk::IntGridValueGroupDefinition
+    fn register_ldtk_int_cell_by_group<B: LdtkIntCell + Bundle>(
+        &mut self,
+        group_identifier: &str,
+    ) -> &mut Self;
+
     /// Similar to [LdtkIntCellAppExt::register_ldtk_int_cell_for_layer], except it applies the
     /// registration to all tiles on the given layer.
     fn register_default_ldtk_int_cell_for_layer<B: LdtkIntCell + Bundle>(
@@ -121,6 +170,48 @@ impl LdtkIntCellAppExt for App {
         }
         self
     }
+
+    fn register_ldtk_int_cell_by_identifier<B: LdtkIntCell + Bundle>(
+        &mut self,
+        identifier: &str,
+    ) -> &mut Self {
+        register_ldtk_int_cell_for_value_identifier::<B>(
+            self,
+            IntGridValueIdentifier::Value(identifier.to_string()),
+        )
+    }
+
+    fn register_ldtk_int_cell_by_group<B: LdtkIntCell + Bundle>(
+        &mut self,
+        group_identifier: &str,
+    ) -> &mut Self {
+        register_ldtk_int_cell_for_value_identifier::<B>(
+            self,
+            IntGridValueIdentifier::Group(group_identifier.to_string()),
+        )
+    }
+}
+
+fn register_ldtk_int_cell_for_value_identifier<B: LdtkIntCell + Bundle>(
+    app: &mut App,
+    value_identifier: IntGridValueIdentifier,
+) -> &mut App {
+    let new_entry = Box::new(PhantomLdtkIntCell::<B>::new());
+    match app
+        .world_mut()
+        .get_non_send_mut::<LdtkIntCellIdentifierMap>()
+    {
+        Some(mut entries) => {
+            entries.insert(value_identifier, new_entry);
+        }
+        None => {
+            let mut bundle_map = LdtkIntCellIdentifierMap::new();
+            bundle_map.insert(value_identifier, new_entry);
+            app.world_mut()
+                .insert_non_send::<LdtkIntCellIdentifierMap>(bundle_map);
+        }
+    }
+    app
 }
 
 #[cfg(test)]
@@ -168,4 +259,25 @@ mod tests {
 
         assert!(ldtk_int_cell_map.contains_key(&(None, None)));
     }
+
+    #[test]
+    fn test_ldtk_int_cell_identifier_registrations() {
+        let mut app = App::new();
+        app.register_ldtk_int_cell_by_identifier::<LdtkIntCellBundle>("Water")
+            .register_ldtk_int_cell_by_group::<LdtkIntCellBundle>("Solid");
+
+        let ldtk_int_cell_identifier_map = app
+            .world_mut()
+            .get_non_send::<LdtkIntCellIdentifierMap>()
+            .unwrap();
+
+        assert!(ldtk_int_cell_identifier_map
+            .contains_key(&IntGridValueIdentifier::Value("Water".to_string())));
+
+        assert!(ldtk_int_cell_identifier_map
+            .contains_key(&IntGridValueIdentifier::Group("Solid".to_string())));
+
+        assert!(!ldtk_int_cell_identifier_map
+            .contains_key(&IntGridValueIdentifier::Value("Solid".to_string())));
+    }
 }
@@ -437,3 +437,7 @@ impl<B: LdtkEntity + Bundle> PhantomLdtkEntityTrait for PhantomLdtkEntity<B> {
 
 /// Used by [LdtkEntityAppExt](super::LdtkEntityAppExt) to associate Ldtk entity identifiers with [LdtkEntity]s.
 pub type LdtkEntityMap = HashMap<(Option<String>, Option<String>), Box<dyn PhantomLdtkEntityTrait>>;
+
+/// Used by [LdtkEntityAppExt](super::LdtkEntityAppExt) to associate Ldtk entity definition tags
+/// with [LdtkEntity]s.
+pub type LdtkEntityTagMap = HashMap<String, Box<dyn PhantomLdtkEntityTrait>>;
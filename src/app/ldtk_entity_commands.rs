@@ -0,0 +1,918 @@
+//! Provides [LdtkEntityCommandsExt] for spawning LDtk entities at runtime from their definitions.
+use crate::{
+    app::ldtk_entity::*,
+    assets::{LdtkProject, LevelMetadataAccessor},
+    components::{EntityIid, EntityInstanceBundle, LayerMetadata, LdtkEntityRefIids, LevelIid},
+    ldtk::{
+        EntityDefinition, EntityInstance, EnumDefinition, FieldDefinition, FieldInstance,
+        FieldValue, LayerDefinition, LayerInstance, Type,
+    },
+    utils::{calculate_transform_from_entity_instance, ldtk_entity_map_get_or_default},
+};
+use bevy::{asset::uuid::Uuid, ecs::system::SystemState, prelude::*};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use thiserror::Error;
+
+/// Provides functions for spawning LDtk entities at runtime, as if they had been placed in a level.
+///
+/// The [EntityInstance] of the spawned entity is synthesized from its [EntityDefinition]:
+/// it has the definition's default size, pivot, tags, and tile, and each field has the
+/// definition's default value unless it is overridden.
+/// Overrides must be for fields of the definition, and must match their types.
+/// It is then spawned with the [LdtkEntity] registered for it, just like entities placed in LDtk.
+/// See [LdtkEntityAppExt](super::LdtkEntityAppExt) for more details on registration.
+///
+/// The entity is spawned as a child of an Entities layer of the given level, so the level must
+/// already be spawned.
+/// If the entity can't be spawned, a warning is logged and the entity is left without any LDtk
+/// components, so commands queued on the returned [EntityCommands] still apply to it.
+///
+/// Not intended for custom implementations on your own types.
+pub trait LdtkEntityCommandsExt {
+    /// Spawns an LDtk entity with the given identifier at the given pixel coordinates of a level.
+    ///
+    /// Like [EntityInstance::px], `px` are the level-relative LDtk coordinates of the entity's
+    /// pivot, with the y-axis pointing down.
+    ///
+    /// The entity is spawned on the first Entities layer of the level whose required and excluded
+    /// tags accept the entity.
+    /// Use [LdtkEntityCommandsExt::spawn_ldtk_entity_for_layer] to choose the layer yourself.
+    ///
+    /// ```
+    /// use bevy::prelude::*;
+    /// use bevy_ecs_ldtk::prelude::*;
+    /// use std::collections::HashMap;
+    ///
+    /// #[derive(Component)]
+    /// struct Dying;
+    ///
+    /// fn drop_loot(
+    ///     mut commands: Commands,
+    ///     dying_enemies: Query<(&EntityInstance, &ChildOf), With<Dying>>,
+    ///     layers: Query<&ChildOf, With<LayerMetadata>>,
+    ///     levels: Query<&LevelIid>,
+    ///     project: Query<&LdtkProjectHandle>,
+    /// ) -> Result {
+    ///     for (enemy, layer) in &dying_enemies {
+    ///         let level_iid = levels.get(layers.get(layer.parent())?.parent())?;
+    ///
+    ///         commands.spawn_ldtk_entity(
+    ///             project.single()?,
+    ///             "Coin",
+    ///             level_iid.clone(),
+    ///             enemy.px,
+    ///             HashMap::from([("Value".to_string(), FieldValue::Int(Some(5)))]),
+    ///         );
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    fn spawn_ldtk_entity(
+        &mut self,
+        project: impl Into<AssetId<LdtkProject>>,
+        entity_identifier: &str,
+        level_iid: LevelIid,
+        px: IVec2,
+        field_overrides: HashMap<String, FieldValue>,
+    ) -> EntityCommands<'_>;
+
+    /// Similar to [LdtkEntityCommandsExt::spawn_ldtk_entity], except the entity is spawned on the
+    /// Entities layer with the given identifier.
+    fn spawn_ldtk_entity_for_layer(
+        &mut self,
+        project: impl Into<AssetId<LdtkProject>>,
+        layer_identifier: &str,
+        entity_identifier: &str,
+        level_iid: LevelIid,
+        px: IVec2,
+        field_overrides: HashMap<String, FieldValue>,
+    ) -> EntityCommands<'_>;
+}
+
+impl LdtkEntityCommandsExt for Commands<'_, '_> {
+    fn spawn_ldtk_entity(
+        &mut self,
+        project: impl Into<AssetId<LdtkProject>>,
+        entity_identifier: &str,
+        level_iid: LevelIid,
+        px: IVec2,
+        field_overrides: HashMap<String, FieldValue>,
+    ) -> EntityCommands<'_> {
+        let entity = self.spawn_empty().id();
+
+        self.queue(SpawnLdtkEntity {
+            entity,
+            project: project.into(),
+            layer_identifier: None,
+            entity_identifier: entity_identifier.to_string(),
+            level_iid,
+            px,
+            field_overrides,
+        });
+
+        self.entity(entity)
+    }
+
+    fn spawn_ldtk_entity_for_layer(
+        &mut self,
+        project: impl Into<AssetId<LdtkProject>>,
+        layer_identifier: &str,
+        entity_identifier: &str,
+        level_iid: LevelIid,
+        px: IVec2,
+        field_overrides: HashMap<String, FieldValue>,
+    ) -> EntityCommands<'_> {
+        let entity = self.spawn_empty().id();
+
+        self.queue(SpawnLdtkEntity {
+            entity,
+            project: project.into(),
+            layer_identifier: Some(layer_identifier.to_string()),
+            entity_identifier: entity_identifier.to_string(),
+            level_iid,
+            px,
+            field_overrides,
+        });
+
+        self.entity(entity)
+    }
+}
+
+#[derive(Debug, Error)]
+enum SpawnLdtkEntityError {
+    #[error("the LDtk project is not loaded")]
+    ProjectNotLoaded,
+    #[error("the LDtk project has no {0} entity definition")]
+    EntityDefinitionNotFound(String),
+    #[error("level {0} is not in the LDtk project")]
+    LevelNotFound(String),
+    #[error("level {0} is not spawned")]
+    LevelNotSpawned(String),
+    #[error("level {0} has no spawned Entities layer for this entity")]
+    LayerNotFound(String),
+    #[error("the entity definition has no {0} field to override")]
+    FieldNotFound(String),
+    #[error("the override of field {0} doesn't match the field's type")]
+    FieldTypeMismatch(String),
+    #[error("unable to give field {identifier} of type {field_type} its default value: {source}")]
+    FieldDefault {
+        identifier: String,
+        field_type: String,
+        source: serde_json::Error,
+    },
+}
+
+/// [Command] queued by the [LdtkEntityCommandsExt] methods.
+struct SpawnLdtkEntity {
+    entity: Entity,
+    project: AssetId<LdtkProject>,
+    layer_identifier: Option<String>,
+    entity_identifier: String,
+    level_iid: LevelIid,
+    px: IVec2,
+    field_overrides: HashMap<String, FieldValue>,
+}
+
+impl Command for SpawnLdtkEntity {
+    type Out = ();
+
+    fn apply(self, world: &mut World) {
+        #[allow(clippy::type_complexity)]
+        let mut system_state: SystemState<(
+            Commands,
+            Res<Assets<LdtkProject>>,
+            Res<AssetServer>,
+            ResMut<Assets<TextureAtlasLayout>>,
+            Option<NonSend<LdtkEntityMap>>,
+            Option<NonSend<LdtkEntityTagMap>>,
+            Query<(Entity, &LevelIid)>,
+            Query<&Children>,
+            Query<&LayerMetadata>,
+        )> = SystemState::new(world);
+
+        let (
+            mut commands,
+            ldtk_project_assets,
+            asset_server,
+            mut texture_atlases,
+            ldtk_entity_map,
+            ldtk_entity_tag_map,
+            level_query,
+            children_query,
+            layer_query,
+        ) = system_state.get_mut(world).unwrap();
+
+        let empty_ldtk_entity_map = LdtkEntityMap::new();
+        let empty_ldtk_entity_tag_map = LdtkEntityTagMap::new();
+
+        if let Err(e) = self.spawn(
+            &mut commands,
+            &ldtk_project_assets,
+            &asset_server,
+            &mut texture_atlases,
+            ldtk_entity_map.as_deref().unwrap_or(&empty_ldtk_entity_map),
+            ldtk_entity_tag_map
+                .as_deref()
+                .unwrap_or(&empty_ldtk_entity_tag_map),
+            &level_query,
+            &children_query,
+            &layer_query,
+        ) {
+            // The entity is left alive, since its EntityCommands were already returned
+            warn!(
+                "unable to spawn {} LDtk entity: {e}",
+                self.entity_identifier
+            );
+        }
+
+        system_state.apply(world);
+    }
+}
+
+impl SpawnLdtkEntity {
+    #[allow(clippy::too_many_arguments)]
+    fn spawn(
+        &self,
+        commands: &mut Commands,
+        ldtk_project_assets: &Assets<LdtkProject>,
+        asset_server: &AssetServer,
+        texture_atlases: &mut Assets<TextureAtlasLayout>,
+        ldtk_entity_map: &LdtkEntityMap,
+        ldtk_entity_tag_map: &LdtkEntityTagMap,
+        level_query: &Query<(Entity, &LevelIid)>,
+        children_query: &Query<&Children>,
+        layer_query: &Query<&LayerMetadata>,
+    ) -> Result<(), SpawnLdtkEntityError> {
+        let ldtk_project = ldtk_project_assets
+            .get(self.project)
+            .ok_or(SpawnLdtkEntityError::ProjectNotLoaded)?;
+
+        let defs = &ldtk_project.json_data().defs;
+
+        let entity_definition = defs
+            .entities
+            .iter()
+            .find(|entity_definition| entity_definition.identifier == self.entity_identifier)
+            .ok_or_else(|| {
+                SpawnLdtkEntityError::EntityDefinitionNotFound(self.entity_identifier.clone())
+            })?;
+
+        let level = ldtk_project
+            .get_raw_level_by_iid(&self.level_iid.to_string())
+            .ok_or_else(|| SpawnLdtkEntityError::LevelNotFound(self.level_iid.to_string()))?;
+
+        let level_entity = level_query
+            .iter()
+            .find_map(|(entity, level_iid)| (*level_iid == self.level_iid).then_some(entity))
+            .ok_or_else(|| SpawnLdtkEntityError::LevelNotSpawned(self.level_iid.to_string()))?;
+
+        let spawned_layers: Vec<_> = children_query
+            .iter_descendants(level_entity)
+            .filter_map(|entity| {
+                layer_query
+                    .get(entity)
+                    .ok()
+                    .filter(|layer| layer.layer_instance_type == Type::Entities)
+                    .map(|layer| (entity, layer))
+            })
+            .collect();
+
+        // Prefer the layer order of the project's definitions
+        let (layer_entity, layer_metadata) = defs
+            .layers
+            .iter()
+            .filter(|layer_definition| match &self.layer_identifier {
+                Some(layer_identifier) => layer_definition.identifier == *layer_identifier,
+                None => layer_accepts_entity(layer_definition, entity_definition),
+            })
+            .find_map(|layer_definition| {
+                spawned_layers
+                    .iter()
+                    .find(|(_, layer)| layer.layer_def_uid == layer_definition.uid)
+            })
+            .ok_or_else(|| SpawnLdtkEntityError::LayerNotFound(self.level_iid.to_string()))?;
+
+        let layer_instance = layer_instance_from_metadata(layer_metadata);
+
+        let entity_instance = entity_instance_from_definition(
+            entity_definition,
+            defs.enums.iter().chain(&defs.external_enums),
+            &layer_instance,
+            IVec2::new(level.world_x, level.world_y),
+            self.px,
+            &self.field_overrides,
+        )?;
+
+        let (tileset, tileset_definition) = match &entity_instance.tile {
+            Some(tile) => (
+                ldtk_project.tileset_map().get(&tile.tileset_uid),
+                defs.tilesets
+                    .iter()
+                    .find(|tileset_definition| tileset_definition.uid == tile.tileset_uid),
+            ),
+            None => (None, None),
+        };
+
+        let transform = calculate_transform_from_entity_instance(
+            &entity_instance,
+            &HashMap::from([(entity_definition.uid, entity_definition)]),
+            level.px_hei,
+        );
+
+        let default_ldtk_entity: Box<dyn PhantomLdtkEntityTrait> =
+            Box::new(PhantomLdtkEntity::<EntityInstanceBundle>::new());
+
+        let mut entity_commands = commands.entity(self.entity);
+
+        // insert Name before evaluating LdtkEntitys so that user-provided names aren't
+        // overwritten
+        entity_commands.insert((
+            EntityIid::new(entity_instance.iid.clone()),
+            Name::new(entity_instance.identifier.clone()),
+            ChildOf(*layer_entity),
+        ));
+
+        ldtk_entity_map_get_or_default(
+            &layer_instance.identifier,
+            &entity_instance,
+            &default_ldtk_entity,
+            ldtk_entity_map,
+            ldtk_entity_tag_map,
+        )
+        .evaluate(
+            &mut entity_commands,
+            &entity_instance,
+            &layer_instance,
+            tileset,
+            tileset_definition,
+            asset_server,
+            texture_atlases,
+        );
+
+        entity_commands.insert(transform);
+
+        if let Some(ref_iids) = LdtkEntityRefIids::from_entity_instance(&entity_instance) {
+            entity_commands.insert(ref_iids);
+        }
+
+        Ok(())
+    }
+}
+
+fn layer_accepts_entity(
+    layer_definition: &LayerDefinition,
+    entity_definition: &EntityDefinition,
+) -> bool {
+    let has_tag = |tag: &String| entity_definition.tags.contains(tag);
+
+    (layer_definition.required_tags.is_empty()
+        || layer_definition.required_tags.iter().any(has_tag))
+        && !layer_definition.excluded_tags.iter().any(has_tag)
+}
+
+fn layer_instance_from_metadata(layer_metadata: &LayerMetadata) -> LayerInstance {
+    LayerInstance {
+        c_hei: layer_metadata.c_hei,
+        c_wid: layer_metadata.c_wid,
+        grid_size: layer_metadata.grid_size,
+        identifier: layer_metadata.identifier.clone(),
+        opacity: layer_metadata.opacity,
+        px_total_offset_x: layer_metadata.px_total_offset_x,
+        px_total_offset_y: layer_metadata.px_total_offset_y,
+        tileset_def_uid: layer_metadata.tileset_def_uid,
+        tileset_rel_path: layer_metadata.tileset_rel_path.clone(),
+        layer_instance_type: layer_metadata.layer_instance_type,
+        iid: layer_metadata.iid.clone(),
+        layer_def_uid: layer_metadata.layer_def_uid,
+        level_id: layer_metadata.level_id,
+        optional_rules: layer_metadata.optional_rules.clone(),
+        override_tileset_uid: layer_metadata.override_tileset_uid,
+        px_offset_x: layer_metadata.px_offset_x,
+        px_offset_y: layer_metadata.px_offset_y,
+        seed: layer_metadata.seed,
+        visible: layer_metadata.visible,
+        ..Default::default()
+    }
+}
+
+/// Clamps a value to the optional `min` and `max` of a field definition.
+fn clamp_to_field_range(value: f32, field_definition: &FieldDefinition) -> f32 {
+    let value = field_definition.min.map_or(value, |min| value.max(min));
+    field_definition.max.map_or(value, |max| value.min(max))
+}
+
+/// The json value LDtk would give a new instance of the field.
+fn default_field_value<'a>(
+    field_definition: &FieldDefinition,
+    mut enum_definitions: impl Iterator<Item = &'a EnumDefinition>,
+) -> Value {
+    if field_definition.is_array {
+        return json!([]);
+    }
+
+    let default_param = field_definition
+        .default_override
+        .as_ref()
+        .and_then(|default_override| default_override.get("params"))
+        .and_then(|params| params.get(0))
+        .cloned();
+
+    match (
+        field_definition.field_definition_type.as_str(),
+        default_param,
+    ) {
+        // Colors defaults are stored as integers
+        ("Color", Some(Value::Number(color))) => color
+            .as_u64()
+            .map(|color| json!(format!("#{color:06x}")))
+            .unwrap_or(json!("#000000")),
+        ("Color", _) => json!("#000000"),
+        ("Bool", None) => json!(false),
+        ("Point" | "Tile" | "EntityRef", _) => Value::Null,
+        (_, Some(default_param)) => default_param,
+        (_, None) if field_definition.can_be_null => Value::Null,
+        // Non-nullable fields without a default start at their type's zero value
+        ("Int", None) => json!(clamp_to_field_range(0., field_definition) as i32),
+        ("Float", None) => json!(clamp_to_field_range(0., field_definition)),
+        ("String" | "Multilines" | "FilePath", None) => json!(""),
+        (field_type, None) => field_type
+            .strip_prefix("LocalEnum.")
+            .or_else(|| field_type.strip_prefix("ExternEnum."))
+            .and_then(|enum_identifier| {
+                enum_definitions
+                    .find(|enum_definition| enum_definition.identifier == enum_identifier)
+            })
+            .and_then(|enum_definition| enum_definition.values.first())
+            .map(|value| json!(value.id))
+            .unwrap_or(Value::Null),
+    }
+}
+
+fn entity_instance_from_definition<'a>(
+    entity_definition: &EntityDefinition,
+    enum_definitions: impl Iterator<Item = &'a EnumDefinition> + Clone,
+    layer_instance: &LayerInstance,
+    level_world_coords: IVec2,
+    px: IVec2,
+    field_overrides: &HashMap<String, FieldValue>,
+) -> Result<EntityInstance, SpawnLdtkEntityError> {
+    if let Some(identifier) = field_overrides.keys().find(|identifier| {
+        !entity_definition
+            .field_defs
+            .iter()
+            .any(|field_definition| field_definition.identifier == **identifier)
+    }) {
+        return Err(SpawnLdtkEntityError::FieldNotFound(identifier.clone()));
+    }
+
+    let mut field_instances = Vec::new();
+
+    for field_definition in &entity_definition.field_defs {
+        let mut field_instance = FieldInstance::from_definition_value(
+            field_definition,
+            default_field_value(field_definition, enum_definitions.clone()),
+        )
+        .map_err(|source| SpawnLdtkEntityError::FieldDefault {
+            identifier: field_definition.identifier.clone(),
+            field_type: field_definition.field_definition_type.clone(),
+            source,
+        })?;
+
+        if let Some(value) = field_overrides.get(&field_definition.identifier) {
+            if std::mem::discriminant(value) != std::mem::discriminant(&field_instance.value) {
+                return Err(SpawnLdtkEntityError::FieldTypeMismatch(
+                    field_definition.identifier.clone(),
+                ));
+            }
+
+            field_instance.value = value.clone();
+        }
+
+        field_instances.push(field_instance);
+    }
+
+    let grid = if layer_instance.grid_size > 0 {
+        px / layer_instance.grid_size
+    } else {
+        IVec2::ZERO
+    };
+
+    let world_coords = level_world_coords + px;
+
+    Ok(EntityInstance {
+        grid,
+        identifier: entity_definition.identifier.clone(),
+        pivot: Vec2::new(entity_definition.pivot_x, entity_definition.pivot_y),
+        smart_color: entity_definition.color,
+        tags: entity_definition.tags.clone(),
+        tile: entity_definition.tile_rect,
+        world_x: Some(world_coords.x),
+        world_y: Some(world_coords.y),
+        def_uid: entity_definition.uid,
+        field_instances,
+        height: entity_definition.height,
+        iid: Uuid::new_v4().to_string(),
+        px,
+        width: entity_definition.width,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ldtk::{ldtk_fields::LdtkFields, EnumValueDefinition, TilesetRectangle};
+
+    fn field_definition(
+        identifier: &str,
+        field_definition_type: &str,
+        default_override: Option<Value>,
+    ) -> FieldDefinition {
+        FieldDefinition {
+            identifier: identifier.to_string(),
+            field_definition_type: field_definition_type.to_string(),
+            is_array: field_definition_type.starts_with("Array<"),
+            default_override,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn entity_instance_is_synthesized_from_definition() {
+        let entity_definition = EntityDefinition {
+            identifier: "Enemy".to_string(),
+            uid: 7,
+            width: 16,
+            height: 24,
+            pivot_x: 0.5,
+            pivot_y: 1.,
+            tags: vec!["Hostile".to_string()],
+            tile_rect: Some(TilesetRectangle {
+                tileset_uid: 3,
+                ..Default::default()
+            }),
+            field_defs: vec![
+                field_definition(
+                    "Health",
+                    "Int",
+                    Some(json!({"id": "V_Int", "params": [10]})),
+                ),
+                field_definition("Speed", "Float", None),
+                FieldDefinition {
+                    can_be_null: true,
+                    ..field_definition("Target", "Float", None)
+                },
+                FieldDefinition {
+                    min: Some(3.),
+                    ..field_definition("Armor", "Int", None)
+                },
+                field_definition("Title", "String", None),
+                field_definition("Mood", "LocalEnum.Mood", None),
+                field_definition("Boss", "Bool", None),
+                field_definition(
+                    "Tint",
+                    "Color",
+                    Some(json!({"id": "V_Int", "params": [0xff0000]})),
+                ),
+                field_definition("Patrol", "Array<Point>", None),
+                field_definition(
+                    "Name",
+                    "String",
+                    Some(json!({"id": "V_String", "params": ["Grunt"]})),
+                ),
+            ],
+            ..Default::default()
+        };
+
+        let layer_instance = LayerInstance {
+            grid_size: 8,
+            ..Default::default()
+        };
+
+        let enum_definitions = [EnumDefinition {
+            identifier: "Mood".to_string(),
+            values: vec![
+                EnumValueDefinition {
+                    id: "Calm".to_string(),
+                    ..Default::default()
+                },
+                EnumValueDefinition {
+                    id: "Angry".to_string(),
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        }];
+
+        let entity_instance = entity_instance_from_definition(
+            &entity_definition,
+            enum_definitions.iter(),
+            &layer_instance,
+            IVec2::new(256, 0),
+            IVec2::new(20, 40),
+            &HashMap::from([("Name".to_string(), FieldValue::String(Some("Elite".into())))]),
+        )
+        .unwrap();
+
+        assert_eq!(entity_instance.identifier, "Enemy");
+        assert_eq!(entity_instance.def_uid, 7);
+        assert_eq!(entity_instance.width, 16);
+        assert_eq!(entity_instance.height, 24);
+        assert_eq!(entity_instance.pivot, Vec2::new(0.5, 1.));
+        assert_eq!(entity_instance.tags, vec!["Hostile".to_string()]);
+        assert_eq!(entity_instance.tile, entity_definition.tile_rect);
+        assert_eq!(entity_instance.px, IVec2::new(20, 40));
+        assert_eq!(entity_instance.grid, IVec2::new(2, 5));
+        assert_eq!(entity_instance.world_x, Some(276));
+        assert_eq!(entity_instance.world_y, Some(40));
+        assert!(!entity_instance.iid.is_empty());
+
+        assert_eq!(entity_instance.get_int_field("Health"), Ok(&10));
+        assert_eq!(entity_instance.get_float_field("Speed"), Ok(&0.));
+        assert_eq!(entity_instance.get_maybe_float_field("Target"), Ok(&None));
+        assert_eq!(entity_instance.get_int_field("Armor"), Ok(&3));
+        assert_eq!(
+            entity_instance.get_string_field("Title"),
+            Ok(&String::new())
+        );
+        assert_eq!(
+            entity_instance.get_enum_field("Mood"),
+            Ok(&"Calm".to_string())
+        );
+        assert_eq!(entity_instance.get_bool_field("Boss"), Ok(&false));
+        assert_eq!(
+            entity_instance.get_color_field("Tint"),
+            Ok(&Color::srgb(1., 0., 0.))
+        );
+        assert_eq!(
+            entity_instance.get_maybe_points_field("Patrol"),
+            Ok(&[][..])
+        );
+        assert_eq!(
+            entity_instance.get_string_field("Name"),
+            Ok(&"Elite".to_string())
+        );
+    }
+
+    #[test]
+    fn invalid_field_overrides_are_rejected() {
+        let entity_definition = EntityDefinition {
+            field_defs: vec![field_definition("Health", "Int", None)],
+            ..Default::default()
+        };
+
+        let from_overrides = |field_overrides: HashMap<String, FieldValue>| {
+            entity_instance_from_definition(
+                &entity_definition,
+                [].iter(),
+                &LayerInstance::default(),
+                IVec2::ZERO,
+                IVec2::ZERO,
+                &field_overrides,
+            )
+        };
+
+        assert!(from_overrides(HashMap::from([(
+            "Health".to_string(),
+            FieldValue::Int(Some(5))
+        )]))
+        .is_ok());
+        assert!(matches!(
+            from_overrides(HashMap::from([(
+                "Health".to_string(),
+                FieldValue::Float(Some(5.))
+            )])),
+            Err(SpawnLdtkEntityError::FieldTypeMismatch(identifier)) if identifier == "Health"
+        ));
+        assert!(matches!(
+            from_overrides(HashMap::from([(
+                "Mana".to_string(),
+                FieldValue::Int(Some(5))
+            )])),
+            Err(SpawnLdtkEntityError::FieldNotFound(identifier)) if identifier == "Mana"
+        ));
+    }
+
+    #[test]
+    fn fields_without_valid_defaults_are_rejected() {
+        let entity_definition = EntityDefinition {
+            field_defs: vec![field_definition("Gadget", "Widget", None)],
+            ..Default::default()
+        };
+
+        assert!(matches!(
+            entity_instance_from_definition(
+                &entity_definition,
+                [].iter(),
+                &LayerInstance::default(),
+                IVec2::ZERO,
+                IVec2::ZERO,
+                &HashMap::new(),
+            ),
+            Err(SpawnLdtkEntityError::FieldDefault { identifier, field_type, .. })
+                if identifier == "Gadget" && field_type == "Widget"
+        ));
+    }
+
+    #[test]
+    fn entity_is_kept_when_it_cant_be_spawned() {
+        #[derive(Component)]
+        struct Marker;
+
+        let mut app = App::new();
+        app.add_plugins((TaskPoolPlugin::default(), AssetPlugin::default()))
+            .init_asset::<LdtkProject>()
+            .init_asset::<TextureAtlasLayout>();
+
+        let entity = app
+            .world_mut()
+            .commands()
+            .spawn_ldtk_entity(
+                AssetId::<LdtkProject>::default(),
+                "Coin",
+                LevelIid::new("missing"),
+                IVec2::ZERO,
+                HashMap::new(),
+            )
+            .insert(Marker)
+            .id();
+
+        app.update();
+
+        let entity = app.world().entity(entity);
+        assert!(entity.contains::<Marker>());
+        assert!(!entity.contains::<EntityInstance>());
+    }
+
+    #[cfg(feature = "internal_levels")]
+    #[test]
+    fn entity_is_spawned_on_layer_with_registered_bundle() {
+        use crate::{
+            app::LdtkEntityAppExt,
+            assets::{LdtkJsonWithMetadata, LdtkProjectData, LevelIndices, LevelMetadata},
+            ldtk::{LdtkJson, Level, TilesetDefinition},
+        };
+
+        #[derive(Component, Debug, PartialEq)]
+        struct CoinValue(i32);
+
+        #[derive(Bundle)]
+        struct CoinBundle {
+            value: CoinValue,
+            entity_instance: EntityInstance,
+        }
+
+        impl LdtkEntity for CoinBundle {
+            fn bundle_entity(
+                entity_instance: &EntityInstance,
+                _: &LayerInstance,
+                _: Option<&Handle<Image>>,
+                _: Option<&TilesetDefinition>,
+                _: &AssetServer,
+                _: &mut Assets<TextureAtlasLayout>,
+            ) -> Self {
+                CoinBundle {
+                    value: CoinValue(*entity_instance.get_int_field("Value").unwrap()),
+                    entity_instance: entity_instance.clone(),
+                }
+            }
+        }
+
+        let entities_layer = |identifier: &str, uid| LayerDefinition {
+            identifier: identifier.to_string(),
+            purple_type: Type::Entities,
+            uid,
+            grid_size: 16,
+            ..Default::default()
+        };
+
+        let mut json = LdtkJson::default();
+        json.levels.push(Level {
+            iid: "level".to_string(),
+            px_hei: 64,
+            ..Default::default()
+        });
+        json.defs.layers = vec![entities_layer("Enemies", 1), entities_layer("Items", 2)];
+        // only items are accepted by the items layer
+        json.defs.layers[0].excluded_tags = vec!["Item".to_string()];
+        json.defs.entities.push(EntityDefinition {
+            identifier: "Coin".to_string(),
+            uid: 3,
+            width: 16,
+            height: 16,
+            pivot_x: 0.5,
+            pivot_y: 1.,
+            tags: vec!["Item".to_string()],
+            field_defs: vec![
+                field_definition("Value", "Int", Some(json!({"id": "V_Int", "params": [1]}))),
+                field_definition("Shiny", "Bool", None),
+            ],
+            ..Default::default()
+        });
+
+        let project = LdtkProject::new(
+            LdtkProjectData::Standalone(LdtkJsonWithMetadata::new(
+                json,
+                HashMap::from([(
+                    "level".to_string(),
+                    LevelMetadata::new(None, LevelIndices::in_root(0)),
+                )]),
+            )),
+            HashMap::new(),
+            None,
+            false,
+        );
+
+        let mut app = App::new();
+        app.add_plugins((TaskPoolPlugin::default(), AssetPlugin::default()))
+            .init_asset::<LdtkProject>()
+            .init_asset::<TextureAtlasLayout>()
+            .register_ldtk_entity::<CoinBundle>("Coin");
+
+        let handle = app
+            .world_mut()
+            .resource_mut::<Assets<LdtkProject>>()
+            .add(project);
+
+        // a spawned level, with its Entities layers
+        let level = app.world_mut().spawn(LevelIid::new("level")).id();
+        let layer = |app: &mut App, identifier: &str, layer_def_uid| {
+            app.world_mut()
+                .spawn((
+                    LayerMetadata::from(&LayerInstance {
+                        identifier: identifier.to_string(),
+                        layer_instance_type: Type::Entities,
+                        layer_def_uid,
+                        grid_size: 16,
+                        c_wid: 4,
+                        c_hei: 4,
+                        ..Default::default()
+                    }),
+                    ChildOf(level),
+                ))
+                .id()
+        };
+        layer(&mut app, "Enemies", 1);
+        let items_layer = layer(&mut app, "Items", 2);
+
+        let entity = app
+            .world_mut()
+            .commands()
+            .spawn_ldtk_entity(
+                &handle,
+                "Coin",
+                LevelIid::new("level"),
+                IVec2::new(24, 32),
+                HashMap::from([("Value".to_string(), FieldValue::Int(Some(5)))]),
+            )
+            .id();
+
+        app.update();
+
+        let entity = app.world().entity(entity);
+
+        assert_eq!(entity.get::<CoinValue>(), Some(&CoinValue(5)));
+        assert_eq!(
+            entity.get::<ChildOf>().map(ChildOf::parent),
+            Some(items_layer)
+        );
+        assert_eq!(
+            entity.get::<Transform>(),
+            Some(&Transform::from_xyz(24., 40., 0.))
+        );
+        assert!(entity.contains::<EntityIid>());
+
+        let entity_instance = entity.get::<EntityInstance>().unwrap();
+        assert_eq!(entity_instance.get_int_field("Value"), Ok(&5));
+        assert_eq!(entity_instance.get_bool_field("Shiny"), Ok(&false));
+    }
+
+    #[test]
+    fn layers_accept_entities_by_tags() {
+        let entity_definition = EntityDefinition {
+            tags: vec!["Item".to_string()],
+            ..Default::default()
+        };
+
+        let layer = |required: &[&str], excluded: &[&str]| LayerDefinition {
+            required_tags: required.iter().map(|tag| tag.to_string()).collect(),
+            excluded_tags: excluded.iter().map(|tag| tag.to_string()).collect(),
+            ..Default::default()
+        };
+
+        assert!(layer_accepts_entity(&layer(&[], &[]), &entity_definition));
+        assert!(layer_accepts_entity(
+            &layer(&["Item", "Enemy"], &[]),
+            &entity_definition
+        ));
+        assert!(!layer_accepts_entity(
+            &layer(&["Enemy"], &[]),
+            &entity_definition
+        ));
+        assert!(!layer_accepts_entity(
+            &layer(&[], &["Item"]),
+            &entity_definition
+        ));
+    }
+}
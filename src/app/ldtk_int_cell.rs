@@ -240,3 +240,21 @@ impl<B: LdtkIntCell + Bundle> PhantomLdtkIntCellTrait for PhantomLdtkIntCell<B>
 
 /// Used by [LdtkIntCellAppExt](super::LdtkIntCellAppExt) to associate Ldtk IntGrid values with [LdtkIntCell]s.
 pub type LdtkIntCellMap = HashMap<(Option<String>, Option<i32>), Box<dyn PhantomLdtkIntCellTrait>>;
+
+/// Identifier of an IntGrid value or IntGrid value group, as defined in a layer's definition.
+///
+/// Used by [LdtkIntCellAppExt](super::LdtkIntCellAppExt) for registering [LdtkIntCell]s by name
+/// rather than by numeric value.
+#[derive(Clone, Eq, PartialEq, Debug, Hash)]
+pub enum IntGridValueIdentifier {
+    /// The identifier of an [IntGridValueDefinition](crate::ldtk::IntGridValueDefinition).
+    Value(String),
+    /// The identifier of an
+    /// [IntGridValueGroupDefinition](crate::ldtk::IntGridValueGroupDefinition).
+    Group(String),
+}
+
+/// Used by [LdtkIntCellAppExt](super::LdtkIntCellAppExt) to associate Ldtk IntGrid value and
+/// value group identifiers with [LdtkIntCell]s.
+pub type LdtkIntCellIdentifierMap =
+    HashMap<IntGridValueIdentifier, Box<dyn PhantomLdtkIntCellTrait>>;
@@ -0,0 +1,220 @@
+use crate::ldtk::{TileInstance, TilesetDefinition};
+use bevy::{ecs::system::EntityCommands, prelude::*};
+use std::{collections::HashMap, marker::PhantomData};
+
+/// [LdtkTileAppExt]: super::LdtkTileAppExt
+/// [Bundle]: bevy::prelude::Bundle
+/// [App]: bevy::prelude::App
+/// [Component]: bevy::prelude::Component
+///
+/// Provides a constructor which can be used for spawning additional components on the tiles of
+/// Tile and AutoLayer layers.
+///
+/// After implementing this trait on a [Bundle], you can register it to spawn automatically for
+/// tiles with a given enum tag value or tile id via [LdtkTileAppExt] on your [App].
+///
+/// For common use cases, you'll want to use derive-macro `#[derive(LdtkTile)]`, but you can
+/// also provide a custom implementation.
+///
+/// You can also implement this trait on non-[Bundle] types, but only [Bundle]s can be registered.
+///
+/// Tiles that don't match any registration are still spawned with their
+/// [TileMetadata](crate::components::TileMetadata) and
+/// [TileEnumTags](crate::components::TileEnumTags), if they have any.
+///
+/// *Derive macro requires the "derive" feature, which is enabled by default*
+///
+/// ## Derive macro usage
+/// Using `#[derive(LdtkTile)]` on a [Bundle] struct will allow the type to be registered to the
+/// [App] via [LdtkTileAppExt] functions:
+/// ```no_run
+/// use bevy::prelude::*;
+/// use bevy_ecs_ldtk::prelude::*;
+///
+/// fn main() {
+///     App::empty()
+///         .add_plugins(LdtkPlugin)
+///         .register_ldtk_tile::<SpikesBundle>("Dungeon", "Spikes")
+///         // add other systems, plugins, resources...
+///         .run();
+/// }
+///
+/// # #[derive(Component, Default)]
+/// # struct Hazard;
+/// # #[derive(Component, Default)]
+/// # struct Damage;
+/// #[derive(Bundle, LdtkTile, Default)]
+/// pub struct SpikesBundle {
+///     hazard: Hazard,
+///     damage: Damage,
+/// }
+/// ```
+/// Now, when loading your ldtk file, any tiles of the "Dungeon" tileset tagged with the "Spikes"
+/// enum value will be spawned with `SpikesBundle` inserted.
+///
+/// By default, each component or nested bundle in the bundle will be consumed from bundle's
+/// [Default] implementation, which means that deriving (or implementing manually) [Default]
+/// is required (unless all fields are overriden, see below).
+/// However, this behavior can be overriden with some field attribute macros...
+///
+/// ### `#[ldtk_tile]`
+/// Indicates that a component or bundle that implements [LdtkTile] should be created with
+/// [LdtkTile::bundle_tile], allowing for nested [LdtkTile]s.
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_ecs_ldtk::prelude::*;
+/// # #[derive(Component, Default)]
+/// # struct Hazard;
+/// # #[derive(Component, Default)]
+/// # struct Slow;
+/// #[derive(Bundle, LdtkTile, Default)]
+/// pub struct Spikes {
+///     hazard: Hazard,
+/// }
+///
+/// #[derive(Bundle, LdtkTile, Default)]
+/// pub struct StickySpikes {
+///     #[ldtk_tile]
+///     spikes: Spikes,
+///     slow: Slow,
+/// }
+/// ```
+///
+/// ### `#[from_tile_instance]`
+/// Indicates that a component or bundle that implements [`From<&TileInstance>`] should be created
+/// using that conversion.
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_ecs_ldtk::{prelude::*, ldtk::TileInstance};
+/// # #[derive(Component, Default)]
+/// # struct Hazard;
+/// #[derive(Component, Default)]
+/// struct Opacity(f32);
+///
+/// impl From<&TileInstance> for Opacity {
+///     fn from(tile_instance: &TileInstance) -> Opacity {
+///         Opacity(tile_instance.a)
+///     }
+/// }
+///
+/// #[derive(Bundle, LdtkTile, Default)]
+/// pub struct Spikes {
+///     #[from_tile_instance]
+///     opacity: Opacity,
+///     hazard: Hazard,
+/// }
+/// ```
+///
+/// ### `#[with(...)]`
+///
+/// Indicates that this component or bundle should be initialized with the given
+/// function.
+///
+/// Note: The given function should have signature `fn (tile_instance: &TileInstance) -> T`
+/// where `T` is the field type. The function should also be accessible in the scope.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_ecs_ldtk::{prelude::*, ldtk::TileInstance};
+/// # #[derive(Component, Default)]
+/// # struct Damage(i32);
+/// # #[derive(Component, Default)]
+/// # struct Hazard;
+/// fn initial_damage(_: &TileInstance) -> Damage {
+///     Damage(10)
+/// }
+///
+/// #[derive(Bundle, LdtkTile, Default)]
+/// pub struct Spikes {
+///     #[with(initial_damage)]
+///     damage: Damage,
+///     hazard: Hazard,
+/// }
+/// ```
+///
+/// ### `#[default]`
+///
+/// Indicates that this component or bundle should be initialized using
+/// [`Default::default`].
+/// This can be useful when implementing `Default` for the whole bundle is not easily possible,
+/// because some of the fields do not implement `Default`.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_ecs_ldtk::{prelude::*, ldtk::TileInstance};
+/// # mod other_crate {
+/// #     #[derive(bevy::prelude::Component)]
+/// #     pub struct ForeignComponentWithNoDefault;
+/// # }
+/// # fn custom_constructor(_: &TileInstance) -> ForeignComponentWithNoDefault { todo!(); }
+/// # #[derive(Component, Default)]
+/// # struct Hazard;
+/// use other_crate::ForeignComponentWithNoDefault;
+///
+/// #[derive(Bundle, LdtkTile)]
+/// pub struct MyBundle {
+///     #[default]
+///     hazard: Hazard,
+///     #[with(custom_constructor)]
+///     foreign: ForeignComponentWithNoDefault,
+/// }
+/// ```
+pub trait LdtkTile {
+    /// The constructor used by the plugin when spawning additional components on tiles.
+    /// If you need access to more of the [World](bevy::prelude::World), you can create a system
+    /// that queries for `Added<TileEnumTags>` or `Added<TileMetadata>`, and flesh out the entity
+    /// from there, instead of implementing this trait.
+    ///
+    /// Note: a [bevy_ecs_tilemap::tiles::TileBundle] and a [Transform] will be inserted
+    /// **before** this bundle, so be careful not to overwrite the components provided by them.
+    /// The bundle is only inserted when the tile spawns, so tiles changed by
+    /// [AutoTileUpdates](crate::resources::AutoTileUpdates) keep the bundles they spawned with.
+    fn bundle_tile(tile_instance: &TileInstance, tileset_definition: &TilesetDefinition) -> Self;
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default, Hash)]
+pub struct PhantomLdtkTile<B: LdtkTile + Bundle> {
+    ldtk_tile: PhantomData<B>,
+}
+
+impl<B: LdtkTile + Bundle> PhantomLdtkTile<B> {
+    pub fn new() -> Self {
+        PhantomLdtkTile::<B> {
+            ldtk_tile: PhantomData,
+        }
+    }
+}
+
+pub trait PhantomLdtkTileTrait {
+    fn evaluate<'a, 'b>(
+        &self,
+        entity_commands: &'b mut EntityCommands<'a>,
+        tile_instance: &TileInstance,
+        tileset_definition: &TilesetDefinition,
+    ) -> &'b mut EntityCommands<'a>;
+}
+
+impl<B: LdtkTile + Bundle> PhantomLdtkTileTrait for PhantomLdtkTile<B> {
+    fn evaluate<'a, 'b>(
+        &self,
+        entity_commands: &'b mut EntityCommands<'a>,
+        tile_instance: &TileInstance,
+        tileset_definition: &TilesetDefinition,
+    ) -> &'b mut EntityCommands<'a> {
+        entity_commands.insert(B::bundle_tile(tile_instance, tileset_definition))
+    }
+}
+
+/// Selects tiles of a tileset for [LdtkTileAppExt](super::LdtkTileAppExt) registrations.
+#[derive(Clone, Eq, PartialEq, Debug, Hash)]
+pub enum TileSelector {
+    /// Tiles tagged with the given value of the tileset's
+    /// [tags_source_enum_uid](crate::ldtk::TilesetDefinition::tags_source_enum_uid) enum.
+    EnumTag(String),
+    /// The tile with the given id.
+    TileId(i32),
+}
+
+/// Used by [LdtkTileAppExt](super::LdtkTileAppExt) to associate tileset identifiers and
+/// [TileSelector]s with [LdtkTile]s.
+pub type LdtkTileMap = HashMap<(String, TileSelector), Box<dyn PhantomLdtkTileTrait>>;
@@ -3,9 +3,15 @@
 mod entity_app_ext;
 mod int_cell_app_ext;
 mod ldtk_entity;
+mod ldtk_entity_commands;
 mod ldtk_int_cell;
+mod ldtk_tile;
+mod tile_app_ext;
 
 pub use entity_app_ext::*;
 pub use int_cell_app_ext::*;
 pub use ldtk_entity::*;
+pub use ldtk_entity_commands::*;
 pub use ldtk_int_cell::*;
+pub use ldtk_tile::*;
+pub use tile_app_ext::*;
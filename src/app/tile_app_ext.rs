@@ -0,0 +1,196 @@
+//! Provides [LdtkTileAppExt] for registering bundles to spawn for given tiles of a tileset.
+use crate::{app::ldtk_tile::*, assets::TileMetadataRegistry};
+use bevy::prelude::*;
+use serde::de::DeserializeOwned;
+
+/// [Bundle]: bevy::prelude::Bundle
+/// [App]: bevy::prelude::App
+///
+/// Provides functions to register [Bundle]s to bevy's [App] for particular tiles of a tileset.
+///
+/// After being registered, these [Bundle]s will be inserted on the tiles of Tile and AutoLayer
+/// layers that meet the criteria you specify.
+/// The tiles of IntGrid layers with AutoLayer rules are included.
+///
+/// Not intended for custom implementations on your own types.
+pub trait LdtkTileAppExt {
+    /// Used internally by all the other LDtk tile registration functions.
+    ///
+    /// Registers [LdtkTile] types to be inserted for the tiles of the tileset with the given
+    /// identifier that match the `selector`.
+    ///
+    /// A particular tile may match multiple registrations.
+    /// In these cases, only one registration applies, prioritized in this order:
+    /// 1. The tile's id is registered with [TileSelector::TileId]
+    /// 2. One of the tile's enum tag values is registered with [TileSelector::EnumTag], in the
+    ///    order that the values are defined in the tileset's enum
+    fn register_ldtk_tile_for_selector<B: LdtkTile + Bundle>(
+        &mut self,
+        tileset_identifier: &str,
+        selector: TileSelector,
+    ) -> &mut Self;
+
+    /// Registers [LdtkTile] types to be inserted for tiles tagged with the given enum value in the
+    /// tileset with the given identifier.
+    ///
+    /// This example lets the plugin know that it should spawn a SpikesBundle on any tile of the
+    /// "Dungeon" tileset tagged with "Spikes".
+    /// ```no_run
+    /// use bevy::prelude::*;
+    /// use bevy_ecs_ldtk::prelude::*;
+    ///
+    /// fn main() {
+    ///     App::empty()
+    ///         .add_plugins(LdtkPlugin)
+    ///         .register_ldtk_tile::<SpikesBundle>("Dungeon", "Spikes")
+    ///         // add other systems, plugins, resources...
+    ///         .run();
+    /// }
+    ///
+    /// # #[derive(Component, Default)]
+    /// # struct Hazard;
+    /// #[derive(Bundle, LdtkTile, Default)]
+    /// pub struct SpikesBundle {
+    ///     hazard: Hazard,
+    /// }
+    /// ```
+    ///
+    /// You can find more details on the `#[derive(LdtkTile)]` macro at [LdtkTile].
+    fn register_ldtk_tile<B: LdtkTile + Bundle>(
+        &mut self,
+        tileset_identifier: &str,
+        enum_tag_value: &str,
+    ) -> &mut Self {
+        self.register_ldtk_tile_for_selector::<B>(
+            tileset_identifier,
+            TileSelector::EnumTag(enum_tag_value.to_string()),
+        )
+    }
+
+    /// Similar to [LdtkTileAppExt::register_ldtk_tile], except it applies the registration to the
+    /// tile with the given id, rather than to tiles with an enum tag.
+    fn register_ldtk_tile_for_tile_id<B: LdtkTile + Bundle>(
+        &mut self,
+        tileset_identifier: &str,
+        tile_id: i32,
+    ) -> &mut Self {
+        self.register_ldtk_tile_for_selector::<B>(tileset_identifier, TileSelector::TileId(tile_id))
+    }
+
+    /// Registers a [Component] to be parsed from the custom data of the tiles of the tileset with
+    /// the given identifier.
+    ///
+    /// The custom data is parsed as JSON, or as [RON] if it isn't valid JSON, when the
+    /// [LdtkProject] loads.
+    /// Custom data that can't be parsed as `T`, or as any other type registered for the tileset,
+    /// is reported with a warning, naming the tileset, tile id, and types, and skipped.
+    ///
+    /// A clone of the parsed component is inserted on the tile's entity when it spawns, alongside
+    /// its [TileMetadata], so the custom data is only parsed once per tile id.
+    /// It's also available without spawning any levels via
+    /// [LdtkProject::parsed_tile_metadata].
+    ///
+    /// Only projects loaded after this registration are affected.
+    /// ```no_run
+    /// use bevy::prelude::*;
+    /// use bevy_ecs_ldtk::prelude::*;
+    /// use serde::Deserialize;
+    ///
+    /// fn main() {
+    ///     App::empty()
+    ///         .add_plugins(LdtkPlugin)
+    ///         .register_tile_metadata::<Friction>("Terrain")
+    ///         // add other systems, plugins, resources...
+    ///         .run();
+    /// }
+    ///
+    /// // Parsed from custom data like `{ "coefficient": 0.5 }` or `(coefficient: 0.5)`
+    /// #[derive(Clone, Component, Deserialize)]
+    /// pub struct Friction {
+    ///     coefficient: f32,
+    /// }
+    /// ```
+    ///
+    /// [RON]: https://docs.rs/ron
+    /// [LdtkProject]: crate::assets::LdtkProject
+    /// [TileMetadata]: crate::components::TileMetadata
+    /// [LdtkProject::parsed_tile_metadata]: crate::assets::LdtkProject::parsed_tile_metadata
+    fn register_tile_metadata<T: DeserializeOwned + Component + Clone>(
+        &mut self,
+        tileset_identifier: &str,
+    ) -> &mut Self;
+}
+
+impl LdtkTileAppExt for App {
+    fn register_ldtk_tile_for_selector<B: LdtkTile + Bundle>(
+        &mut self,
+        tileset_identifier: &str,
+        selector: TileSelector,
+    ) -> &mut Self {
+        let new_entry = Box::new(PhantomLdtkTile::<B>::new());
+        let key = (tileset_identifier.to_string(), selector);
+        match self.world_mut().get_non_send_mut::<LdtkTileMap>() {
+            Some(mut entries) => {
+                entries.insert(key, new_entry);
+            }
+            None => {
+                let mut bundle_map = LdtkTileMap::new();
+                bundle_map.insert(key, new_entry);
+                self.world_mut().insert_non_send::<LdtkTileMap>(bundle_map);
+            }
+        }
+        self
+    }
+
+    fn register_tile_metadata<T: DeserializeOwned + Component + Clone>(
+        &mut self,
+        tileset_identifier: &str,
+    ) -> &mut Self {
+        self.world_mut()
+            .get_resource_or_init::<TileMetadataRegistry>()
+            .register::<T>(tileset_identifier);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ldtk::{TileInstance, TilesetDefinition};
+
+    #[derive(Default, Component, Debug)]
+    struct ComponentA;
+
+    #[derive(Default, Component, Debug)]
+    struct ComponentB;
+
+    #[derive(Default, Bundle)]
+    struct LdtkTileBundle {
+        a: ComponentA,
+        b: ComponentB,
+    }
+
+    impl LdtkTile for LdtkTileBundle {
+        fn bundle_tile(_: &TileInstance, _: &TilesetDefinition) -> LdtkTileBundle {
+            LdtkTileBundle::default()
+        }
+    }
+
+    #[test]
+    fn test_ldtk_tile_registrations() {
+        let mut app = App::new();
+        app.register_ldtk_tile::<LdtkTileBundle>("tileset", "Spikes")
+            .register_ldtk_tile_for_tile_id::<LdtkTileBundle>("tileset", 4);
+
+        let ldtk_tile_map = app.world_mut().get_non_send::<LdtkTileMap>().unwrap();
+
+        assert!(ldtk_tile_map.contains_key(&(
+            "tileset".to_string(),
+            TileSelector::EnumTag("Spikes".to_string())
+        )));
+
+        assert!(ldtk_tile_map.contains_key(&("tileset".to_string(), TileSelector::TileId(4))));
+
+        assert!(!ldtk_tile_map.contains_key(&("other".to_string(), TileSelector::TileId(4))));
+    }
+}
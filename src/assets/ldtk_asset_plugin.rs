@@ -1,3 +1,5 @@
+#[cfg(feature = "internal_levels")]
+use crate::assets::LdtkSimplifiedExportLoader;
 #[cfg(feature = "external_levels")]
 use crate::assets::{ldtk_external_level::LdtkExternalLevelLoader, LdtkExternalLevel};
 use crate::assets::{ldtk_project::LdtkProjectLoader, LdtkProject};
@@ -12,6 +14,9 @@ impl Plugin for LdtkAssetPlugin {
         app.init_asset::<LdtkProject>()
             .init_asset_loader::<LdtkProjectLoader>();
 
+        #[cfg(feature = "internal_levels")]
+        app.init_asset_loader::<LdtkSimplifiedExportLoader>();
+
         #[cfg(feature = "external_levels")]
         {
             app.init_asset::<LdtkExternalLevel>()
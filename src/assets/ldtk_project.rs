@@ -2,17 +2,26 @@ use std::io;
 
 use crate::{
     assets::{
-        LdtkJsonWithMetadata, LdtkProjectData, LevelIndices, LevelMetadata, LevelMetadataAccessor,
+        ExportedImages, LdtkJsonWithMetadata, LdtkProjectData, LevelIndices, LevelMetadata,
+        LevelMetadataAccessor, ParsedTileMetadata, TileMetadataRegistry,
+    },
+    components::EntityIid,
+    ldtk::{
+        raw_level_accessor::RawLevelAccessor, toc_instance::TocInstance, EntityDefinition,
+        ImageExportMode, LdtkJson, LdtkTableOfContentEntry, Level,
     },
-    ldtk::{raw_level_accessor::RawLevelAccessor, LdtkJson, Level},
 };
 use bevy::{
-    asset::{io::Reader, AssetLoader, AssetPath, LoadContext, ParseAssetPathError},
+    asset::{
+        io::{AssetReaderError, Reader},
+        AssetLoadError, AssetLoader, AssetPath, LoadContext, LoadDirectError, ParseAssetPathError,
+    },
     prelude::*,
     reflect::Reflect,
 };
 use derive_getters::Getters;
 use derive_more::From;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use thiserror::Error;
 
@@ -20,9 +29,18 @@ use thiserror::Error;
 use crate::assets::InternalLevels;
 
 #[cfg(feature = "external_levels")]
-use crate::assets::{ExternalLevelMetadata, ExternalLevels};
+use crate::assets::{ExternalLevelMetadata, ExternalLevels, LazyLevelPaths, LdtkExternalLevel};
+
+#[cfg(feature = "external_levels")]
+use std::{
+    hash::{DefaultHasher, Hash, Hasher},
+    marker::PhantomData,
+};
+
+#[cfg(feature = "external_levels")]
+use bevy::asset::uuid::Uuid;
 
-fn ldtk_path_to_asset_path<'b>(
+pub(crate) fn ldtk_path_to_asset_path<'b>(
     ldtk_path: &AssetPath<'b>,
     rel_path: &str,
 ) -> Result<AssetPath<'b>, ParseAssetPathError> {
@@ -80,21 +98,43 @@ pub struct LdtkProject {
     tileset_map: HashMap<i32, Handle<Image>>,
     /// Image used for rendering int grid colors.
     int_grid_image_handle: Option<Handle<Image>>,
+    /// Whether this project was synthesized from LDtk's super simple export.
+    simplified_export: bool,
+    /// Custom data of tiles, parsed into the types registered with
+    /// [`LdtkTileAppExt::register_tile_metadata`].
+    ///
+    /// [`LdtkTileAppExt::register_tile_metadata`]: crate::app::LdtkTileAppExt::register_tile_metadata
+    #[reflect(ignore)]
+    parsed_tile_metadata: ParsedTileMetadata,
 }
 
 impl LdtkProject {
     /// Construct a new [`LdtkProject`].
     ///
-    /// Private to preserve type guarantees about loaded levels.
-    fn new(
+    /// Crate-private to preserve type guarantees about loaded levels.
+    pub(crate) fn new(
         data: LdtkProjectData,
         tileset_map: HashMap<i32, Handle<Image>>,
         int_grid_image_handle: Option<Handle<Image>>,
+        simplified_export: bool,
     ) -> LdtkProject {
         LdtkProject {
             data,
             tileset_map,
             int_grid_image_handle,
+            simplified_export,
+            parsed_tile_metadata: ParsedTileMetadata::default(),
+        }
+    }
+
+    /// Set the parsed custom data of the project's tiles.
+    pub(crate) fn with_parsed_tile_metadata(
+        self,
+        parsed_tile_metadata: ParsedTileMetadata,
+    ) -> LdtkProject {
+        LdtkProject {
+            parsed_tile_metadata,
+            ..self
         }
     }
 
@@ -128,6 +168,65 @@ impl LdtkProject {
     pub fn as_parent(&self) -> &LdtkJsonWithMetadata<ExternalLevels> {
         self.data.as_parent()
     }
+
+    /// Iterate through all entity instances listed in the project's table of contents.
+    ///
+    /// This data is available without loading or spawning any levels.
+    /// See [`TocInstance`] for more details.
+    pub fn iter_toc_instances(&self) -> impl Iterator<Item = TocInstance<'_>> {
+        self.iter_toc_entry_instances(|_| true)
+    }
+
+    /// Iterate through the table of contents' instances of the entity with the given identifier.
+    pub fn iter_toc_instances_by_identifier<'a>(
+        &'a self,
+        identifier: &'a str,
+    ) -> impl Iterator<Item = TocInstance<'a>> {
+        self.iter_toc_entry_instances(move |entry| entry.identifier == identifier)
+    }
+
+    /// Find the table of contents' entry for the entity instance with the given iid.
+    pub fn get_toc_instance(&self, entity_iid: &EntityIid) -> Option<TocInstance<'_>> {
+        let (entry, data) = self.json_data().toc.iter().find_map(|entry| {
+            entry
+                .instances_data
+                .iter()
+                .find(|data| data.iids.entity_iid == entity_iid.as_str())
+                .map(|data| (entry, data))
+        })?;
+
+        Some(TocInstance::new(
+            &entry.identifier,
+            data,
+            self.get_entity_definition(&entry.identifier),
+        ))
+    }
+
+    fn get_entity_definition(&self, identifier: &str) -> Option<&EntityDefinition> {
+        self.json_data()
+            .defs
+            .entities
+            .iter()
+            .find(|entity_definition| entity_definition.identifier == identifier)
+    }
+
+    fn iter_toc_entry_instances<'a>(
+        &'a self,
+        filter: impl Fn(&LdtkTableOfContentEntry) -> bool + 'a,
+    ) -> impl Iterator<Item = TocInstance<'a>> {
+        self.json_data()
+            .toc
+            .iter()
+            .filter(move |entry| filter(entry))
+            .flat_map(move |entry| {
+                let entity_definition = self.get_entity_definition(&entry.identifier);
+
+                entry
+                    .instances_data
+                    .iter()
+                    .map(move |data| TocInstance::new(&entry.identifier, data, entity_definition))
+            })
+    }
 }
 
 impl RawLevelAccessor for LdtkProject {
@@ -171,17 +270,308 @@ pub enum LdtkProjectLoaderError {
     /// Unable to parse relative path in LDtk file.
     #[error("unable to parse relative path in LDtk file: {0}")]
     ParseRelativePath(#[from] ParseAssetPathError),
+    /// Unable to load an image exported by LDtk.
+    #[error("unable to load exported image: {0}")]
+    LoadExportedImage(#[from] Box<LoadDirectError>),
+}
+
+/// Settings for loading an [`LdtkProject`] asset.
+///
+/// These can be provided with [`AssetServer::load_with_settings`].
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_ecs_ldtk::{assets::LdtkProjectLoaderSettings, prelude::*};
+/// fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+///     commands.spawn(LdtkWorldBundle {
+///         ldtk_handle: asset_server
+///             .load_with_settings("my_project.ldtk", |s: &mut LdtkProjectLoaderSettings| {
+///                 s.load_exported_images = true;
+///             })
+///             .into(),
+///         ..Default::default()
+///     });
+/// }
+/// # bevy::ecs::system::assert_is_system(setup);
+/// ```
+///
+/// [`AssetServer::load_with_settings`]: https://docs.rs/bevy/latest/bevy/asset/struct.AssetServer.html#method.load_with_settings
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct LdtkProjectLoaderSettings {
+    /// Whether or not to load the PNGs LDtk exported for the project's levels and layers.
+    ///
+    /// LDtk exports these when the project's [`image_export_mode`] isn't `None`.
+    /// They are expected in a `png` folder inside the folder LDtk creates next to the project,
+    /// named according to the project's [`png_file_pattern`].
+    /// Level images are expected to be named after their level.
+    /// Images that don't exist are skipped, but other errors, like an image that can't be decoded,
+    /// fail the project load.
+    ///
+    /// Note that this loads the images of every level while the project itself loads, one after
+    /// another: the level's image and an image per layer definition, for each level.
+    /// For projects with many levels or layers, this can make loading the project noticeably
+    /// slower, and all of the images stay in memory for as long as the project does.
+    ///
+    /// The loaded images are stored in [`LevelMetadata::exported_images`], and are used for
+    /// rendering with [`TileLayerRendering::ExportedImages`].
+    ///
+    /// [`image_export_mode`]: LdtkJson::image_export_mode
+    /// [`png_file_pattern`]: LdtkJson::png_file_pattern
+    /// [`TileLayerRendering::ExportedImages`]: crate::prelude::TileLayerRendering::ExportedImages
+    pub load_exported_images: bool,
+    /// Whether or not external levels are only loaded while they are in a [`LevelSet`].
+    ///
+    /// By default, the `.ldtkl` files and background images of all levels are loaded as
+    /// dependencies of the project.
+    /// With this enabled, they are loaded when the level enters the [`LevelSet`] of a world using
+    /// the project, and unloaded when it leaves all of them.
+    /// Levels are spawned as soon as their own assets finish loading.
+    ///
+    /// Until it is loaded, the level's [`ExternalLevelMetadata::external_handle`] doesn't point
+    /// to any asset data, and its [`LevelMetadata::bg_image`] is always [`None`].
+    ///
+    /// Has no effect on projects using internal levels.
+    ///
+    /// [`LevelSet`]: crate::components::LevelSet
+    #[cfg(feature = "external_levels")]
+    pub lazy_external_levels: bool,
+    /// Tilesets whose images shouldn't be loaded.
+    ///
+    /// Tiles using skipped tilesets are still spawned, along with their [`TileMetadata`] and
+    /// [`TileEnumTags`], but their tilemaps are hidden.
+    ///
+    /// [`TileMetadata`]: crate::components::TileMetadata
+    /// [`TileEnumTags`]: crate::components::TileEnumTags
+    pub skipped_tilesets: SkippedTilesets,
+    /// Replacement image paths for tilesets, keyed by tileset identifier.
+    ///
+    /// Like the paths in the project, these are relative to the project file.
+    pub tileset_paths: HashMap<String, String>,
+    /// Whether or not to skip loading the background images of levels.
+    ///
+    /// Levels are still spawned with their background colors.
+    pub skip_bg_images: bool,
+    /// Identifiers of the worlds to include in the project, or [`None`] to include all of them.
+    ///
+    /// Levels of excluded worlds are removed from the project entirely, and their assets aren't
+    /// loaded.
+    /// Has no effect on projects that aren't in multi-worlds mode.
+    pub worlds: Option<Vec<String>>,
+    /// Whether or not to remove the [`cached_pixel_data`] of tilesets from the project.
+    ///
+    /// This data is only used by the LDtk editor.
+    ///
+    /// [`cached_pixel_data`]: crate::ldtk::TilesetDefinition::cached_pixel_data
+    pub strip_cached_pixel_data: bool,
+    /// Whether or not to remove the auto-layer rule definitions of layers from the project.
+    ///
+    /// The auto-layer tiles LDtk already generated are unaffected, but they can no longer be
+    /// updated with [`AutoTileUpdates::OnIntGridChange`].
+    ///
+    /// [`AutoTileUpdates::OnIntGridChange`]: crate::prelude::AutoTileUpdates::OnIntGridChange
+    pub strip_auto_layer_rules: bool,
+}
+
+/// Option in [`LdtkProjectLoaderSettings`] that determines which tilesets aren't loaded.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub enum SkippedTilesets {
+    /// All tilesets are loaded.
+    #[default]
+    None,
+    /// Only the tilesets with the given identifiers are skipped.
+    Only(Vec<String>),
+    /// No tilesets are loaded, for headless contexts that don't render them.
+    All,
+}
+
+impl SkippedTilesets {
+    /// Returns true if the tileset with the given identifier should be skipped.
+    pub fn contains(&self, identifier: &str) -> bool {
+        match self {
+            SkippedTilesets::None => false,
+            SkippedTilesets::Only(identifiers) => identifiers.iter().any(|i| i == identifier),
+            SkippedTilesets::All => true,
+        }
+    }
+}
+
+impl LdtkProjectLoaderSettings {
+    /// Removes the data these settings exclude from the project, before any of its assets load.
+    fn strip(&self, data: &mut LdtkJson) {
+        if let Some(worlds) = &self.worlds {
+            data.worlds
+                .retain(|world| worlds.contains(&world.identifier));
+        }
+
+        if self.skip_bg_images {
+            for level in data
+                .worlds
+                .iter_mut()
+                .flat_map(|world| world.levels.iter_mut())
+                .chain(data.levels.iter_mut())
+            {
+                level.bg_rel_path = None;
+            }
+        }
+
+        for tileset in &mut data.defs.tilesets {
+            if let Some(path) = self.tileset_paths.get(&tileset.identifier) {
+                tileset.rel_path = Some(path.clone());
+            }
+
+            if self.strip_cached_pixel_data {
+                tileset.cached_pixel_data = None;
+            }
+        }
+
+        if self.strip_auto_layer_rules {
+            for layer in &mut data.defs.layers {
+                layer.auto_rule_groups.clear();
+            }
+        }
+    }
 }
 
 /// AssetLoader for [`LdtkProject`].
-#[derive(Default, TypePath)]
-pub struct LdtkProjectLoader;
+#[derive(TypePath)]
+pub struct LdtkProjectLoader {
+    tile_metadata_registry: TileMetadataRegistry,
+}
+
+impl FromWorld for LdtkProjectLoader {
+    fn from_world(world: &mut World) -> Self {
+        LdtkProjectLoader {
+            tile_metadata_registry: world.get_resource_or_init::<TileMetadataRegistry>().clone(),
+        }
+    }
+}
+
+/// File name pattern LDtk uses for exported layer images when the project doesn't specify one.
+const DEFAULT_PNG_FILE_PATTERN: &str = "%level_name__%layer_name";
+
+/// Substitutes the placeholders of an LDtk [`png_file_pattern`] for a particular layer.
+///
+/// [`png_file_pattern`]: LdtkJson::png_file_pattern
+fn exported_layer_file_name(
+    pattern: &str,
+    world_identifier: &str,
+    level_identifier: &str,
+    level_index: usize,
+    layer_identifier: &str,
+    layer_index: usize,
+) -> String {
+    pattern
+        .replace("%world", world_identifier)
+        .replace("%level_name", level_identifier)
+        .replace("%level_idx", &format!("{level_index:04}"))
+        .replace("%layer_name", layer_identifier)
+        .replace("%layer_idx", &format!("{layer_index:02}"))
+}
+
+/// Loads an exported image relative to the project's export folder, if it exists.
+///
+/// Only missing images result in [`None`], other errors are returned.
+async fn load_exported_image(
+    load_context: &mut LoadContext<'_>,
+    file_name: &str,
+    label: String,
+) -> Result<Option<Handle<Image>>, LdtkProjectLoaderError> {
+    let project_stem = load_context
+        .path()
+        .path()
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default();
+
+    let asset_path = ldtk_path_to_asset_path(
+        load_context.path(),
+        &format!("{project_stem}/png/{file_name}.png"),
+    )?;
+
+    match load_context
+        .load_builder()
+        .load_value::<Image>(asset_path)
+        .await
+    {
+        Ok(loaded_image) => Ok(Some(
+            load_context.add_loaded_labeled_asset(label, loaded_image),
+        )),
+        Err(LoadDirectError::LoadError {
+            error: AssetLoadError::AssetReaderError(AssetReaderError::NotFound(_)),
+            ..
+        }) => Ok(None),
+        Err(e) => Err(Box::new(e).into()),
+    }
+}
+
+async fn load_exported_images(
+    load_context: &mut LoadContext<'_>,
+    data: &LdtkJson,
+    level_indices: LevelIndices,
+    level: &Level,
+) -> Result<ExportedImages, LdtkProjectLoaderError> {
+    let (export_levels, export_layers) = match data.image_export_mode {
+        ImageExportMode::None => (false, false),
+        ImageExportMode::OneImagePerLevel => (true, false),
+        ImageExportMode::OneImagePerLayer => (false, true),
+        ImageExportMode::LayersAndLevels => (true, true),
+    };
+
+    let level_image = if export_levels {
+        load_exported_image(
+            load_context,
+            &level.identifier,
+            format!("exported_images/{}", level.iid),
+        )
+        .await?
+    } else {
+        None
+    };
+
+    let mut layer_images = HashMap::new();
+
+    if export_layers {
+        let pattern = data
+            .png_file_pattern
+            .as_deref()
+            .unwrap_or(DEFAULT_PNG_FILE_PATTERN);
+
+        let world_identifier = level_indices
+            .world
+            .and_then(|world_index| data.worlds.get(world_index))
+            .map(|world| world.identifier.as_str())
+            .unwrap_or_default();
+
+        for (layer_index, layer_definition) in data.defs.layers.iter().enumerate() {
+            let file_name = exported_layer_file_name(
+                pattern,
+                world_identifier,
+                &level.identifier,
+                level_indices.level,
+                &layer_definition.identifier,
+                layer_index,
+            );
+
+            if let Some(handle) = load_exported_image(
+                load_context,
+                &file_name,
+                format!("exported_images/{}/{}", level.iid, layer_definition.uid),
+            )
+            .await?
+            {
+                layer_images.insert(layer_definition.uid, handle);
+            }
+        }
+    }
+
+    Ok(ExportedImages::new(level_image, layer_images))
+}
 
 fn load_level_metadata(
     load_context: &mut LoadContext,
     level_indices: LevelIndices,
     level: &Level,
     expect_level_loaded: bool,
+    exported_images: ExportedImages,
 ) -> Result<LevelMetadata, LdtkProjectLoaderError> {
     let bg_image = level
         .bg_rel_path
@@ -196,50 +586,93 @@ fn load_level_metadata(
         Err(LdtkProjectLoaderError::InternalLevelWithNullLayers)?;
     }
 
-    let level_metadata = LevelMetadata::new(bg_image, level_indices);
+    let level_metadata =
+        LevelMetadata::new(bg_image, level_indices).with_exported_images(exported_images);
 
     Ok(level_metadata)
 }
 
+/// Creates the placeholder handle of a lazily-loaded external level.
+///
+/// The handle is derived from the level's path, so it stays the same when the project reloads.
+#[cfg(feature = "external_levels")]
+fn lazy_external_level_handle(external_level_path: &AssetPath) -> Handle<LdtkExternalLevel> {
+    let hash = |salt: u64| {
+        let mut hasher = DefaultHasher::new();
+        (salt, external_level_path).hash(&mut hasher);
+        hasher.finish()
+    };
+
+    Handle::Uuid(Uuid::from_u64_pair(hash(0), hash(1)), PhantomData)
+}
+
 #[cfg(feature = "external_levels")]
 fn load_external_level_metadata(
     load_context: &mut LoadContext,
     level_indices: LevelIndices,
     level: &Level,
+    exported_images: ExportedImages,
+    lazy: bool,
 ) -> Result<ExternalLevelMetadata, LdtkProjectLoaderError> {
-    let level_metadata = load_level_metadata(load_context, level_indices, level, false)?;
-
     let external_level_path = ldtk_path_to_asset_path(
         load_context.path(),
         level
             .external_rel_path
             .as_ref()
             .ok_or(LdtkProjectLoaderError::ExternalLevelWithNullPath)?,
-    )?;
+    )?
+    .into_owned();
 
-    let external_handle = load_context.load(external_level_path.clone());
+    if lazy {
+        let bg_image_path = level
+            .bg_rel_path
+            .as_ref()
+            .map(|rel_path| {
+                ldtk_path_to_asset_path(load_context.path(), rel_path).map(AssetPath::into_owned)
+            })
+            .transpose()?;
+
+        let level_metadata =
+            LevelMetadata::new(None, level_indices).with_exported_images(exported_images);
+
+        return Ok(ExternalLevelMetadata::new(
+            level_metadata,
+            lazy_external_level_handle(&external_level_path),
+        )
+        .with_lazy_paths(LazyLevelPaths::new(external_level_path, bg_image_path)));
+    }
+
+    let level_metadata =
+        load_level_metadata(load_context, level_indices, level, false, exported_images)?;
+
+    let external_handle = load_context.load(external_level_path);
 
     Ok(ExternalLevelMetadata::new(level_metadata, external_handle))
 }
 
 impl AssetLoader for LdtkProjectLoader {
     type Asset = LdtkProject;
-    type Settings = ();
+    type Settings = LdtkProjectLoaderSettings;
     type Error = LdtkProjectLoaderError;
 
     async fn load(
         &self,
         reader: &mut dyn Reader,
-        _settings: &Self::Settings,
+        settings: &Self::Settings,
         load_context: &mut LoadContext<'_>,
     ) -> Result<Self::Asset, Self::Error> {
         let mut bytes = Vec::new();
         reader.read_to_end(&mut bytes).await?;
-        let data: LdtkJson = serde_json::from_slice(&bytes)?;
+        let mut data: LdtkJson = serde_json::from_slice(&bytes)?;
+        settings.strip(&mut data);
+
+        let parsed_tile_metadata = self.tile_metadata_registry.parse(&data.defs.tilesets);
 
         let mut tileset_map: HashMap<i32, Handle<Image>> = HashMap::new();
         for tileset in &data.defs.tilesets {
-            if let Some(tileset_path) = &tileset.rel_path {
+            if settings.skipped_tilesets.contains(&tileset.identifier) {
+                continue;
+            } else if let Some(tileset_path) = &tileset.rel_path {
                 let asset_path = ldtk_path_to_asset_path(load_context.path(), tileset_path)?;
 
                 tileset_map.insert(tileset.uid, load_context.load(asset_path));
@@ -262,8 +695,19 @@ impl AssetLoader for LdtkProjectLoader {
                 let mut level_map = HashMap::new();
 
                 for (level_indices, level) in data.iter_raw_levels_with_indices() {
-                    let level_metadata =
-                        load_external_level_metadata(load_context, level_indices, level)?;
+                    let exported_images = if settings.load_exported_images {
+                        load_exported_images(load_context, &data, level_indices, level).await?
+                    } else {
+                        ExportedImages::default()
+                    };
+
+                    let level_metadata = load_external_level_metadata(
+                        load_context,
+                        level_indices,
+                        level,
+                        exported_images,
+                        settings.lazy_external_levels,
+                    )?;
 
                     level_map.insert(level.iid.clone(), level_metadata);
                 }
@@ -272,6 +716,7 @@ impl AssetLoader for LdtkProjectLoader {
                     LdtkProjectData::Parent(LdtkJsonWithMetadata::new(data, level_map)),
                     tileset_map,
                     int_grid_image_handle,
+                    false,
                 )
             }
 
@@ -285,8 +730,19 @@ impl AssetLoader for LdtkProjectLoader {
                 let mut level_map = HashMap::new();
 
                 for (level_indices, level) in data.iter_raw_levels_with_indices() {
-                    let level_metadata =
-                        load_level_metadata(load_context, level_indices, level, true)?;
+                    let exported_images = if settings.load_exported_images {
+                        load_exported_images(load_context, &data, level_indices, level).await?
+                    } else {
+                        ExportedImages::default()
+                    };
+
+                    let level_metadata = load_level_metadata(
+                        load_context,
+                        level_indices,
+                        level,
+                        true,
+                        exported_images,
+                    )?;
 
                     level_map.insert(level.iid.clone(), level_metadata);
                 }
@@ -295,6 +751,7 @@ impl AssetLoader for LdtkProjectLoader {
                     LdtkProjectData::Standalone(LdtkJsonWithMetadata::new(data, level_map)),
                     tileset_map,
                     int_grid_image_handle,
+                    false,
                 )
             }
 
@@ -304,7 +761,7 @@ impl AssetLoader for LdtkProjectLoader {
             }
         };
 
-        Ok(ldtk_project)
+        Ok(ldtk_project.with_parsed_tile_metadata(parsed_tile_metadata))
     }
 
     fn extensions(&self) -> &[&str] {
@@ -348,10 +805,121 @@ mod tests {
                 data,
                 tileset_map,
                 int_grid_image_handle: Some(Handle::Uuid(UUIDv4.fake(), PhantomData)),
+                simplified_export: false,
+                parsed_tile_metadata: ParsedTileMetadata::default(),
             }
         }
     }
 
+    #[test]
+    fn substitutes_png_file_pattern() {
+        assert_eq!(
+            exported_layer_file_name(DEFAULT_PNG_FILE_PATTERN, "World", "Level_0", 0, "Ground", 1),
+            "Level_0__Ground"
+        );
+        assert_eq!(
+            exported_layer_file_name(
+                "%world/%level_idx-%layer_idx-%layer_name",
+                "Overworld",
+                "Level_12",
+                12,
+                "Walls",
+                3
+            ),
+            "Overworld/0012-03-Walls"
+        );
+    }
+
+    #[cfg(feature = "external_levels")]
+    #[test]
+    fn lazy_external_level_handles_are_stable() {
+        let level_0 = AssetPath::from("project/Level_0.ldtkl");
+        let level_1 = AssetPath::from("project/Level_1.ldtkl");
+
+        assert_eq!(
+            lazy_external_level_handle(&level_0),
+            lazy_external_level_handle(&level_0.clone())
+        );
+        assert_ne!(
+            lazy_external_level_handle(&level_0),
+            lazy_external_level_handle(&level_1)
+        );
+    }
+
+    #[test]
+    fn skipped_tilesets() {
+        assert!(!SkippedTilesets::None.contains("Tiles"));
+        assert!(SkippedTilesets::All.contains("Tiles"));
+
+        let only = SkippedTilesets::Only(vec!["Tiles".to_string()]);
+        assert!(only.contains("Tiles"));
+        assert!(!only.contains("Props"));
+    }
+
+    #[test]
+    fn settings_strip_project_data() {
+        use crate::ldtk::{AutoLayerRuleGroup, LayerDefinition, TilesetDefinition, World};
+
+        let level = |identifier: &str| Level {
+            identifier: identifier.to_string(),
+            bg_rel_path: Some("bg.png".to_string()),
+            ..Default::default()
+        };
+
+        let mut data = LdtkJson {
+            worlds: vec![
+                World {
+                    identifier: "Overworld".to_string(),
+                    levels: vec![level("Level_0")],
+                    ..Default::default()
+                },
+                World {
+                    identifier: "Underworld".to_string(),
+                    levels: vec![level("Level_1")],
+                    ..Default::default()
+                },
+            ],
+            defs: crate::ldtk::Definitions {
+                tilesets: vec![TilesetDefinition {
+                    identifier: "Tiles".to_string(),
+                    rel_path: Some("tiles.png".to_string()),
+                    cached_pixel_data: Some(HashMap::new()),
+                    ..Default::default()
+                }],
+                layers: vec![LayerDefinition {
+                    auto_rule_groups: vec![AutoLayerRuleGroup::default()],
+                    ..Default::default()
+                }],
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let mut unstripped = data.clone();
+        LdtkProjectLoaderSettings::default().strip(&mut unstripped);
+        assert_eq!(unstripped, data);
+
+        LdtkProjectLoaderSettings {
+            worlds: Some(vec!["Overworld".to_string()]),
+            skip_bg_images: true,
+            tileset_paths: HashMap::from([("Tiles".to_string(), "server/tiles.png".to_string())]),
+            strip_cached_pixel_data: true,
+            strip_auto_layer_rules: true,
+            ..Default::default()
+        }
+        .strip(&mut data);
+
+        assert_eq!(data.worlds.len(), 1);
+        assert_eq!(data.worlds[0].identifier, "Overworld");
+        assert_eq!(data.worlds[0].levels[0].bg_rel_path, None);
+        assert_eq!(
+            data.defs.tilesets[0].rel_path.as_deref(),
+            Some("server/tiles.png")
+        );
+        assert_eq!(data.defs.tilesets[0].cached_pixel_data, None);
+        assert!(data.defs.layers[0].auto_rule_groups.is_empty());
+    }
+
     #[test]
     fn normalizes_asset_paths() {
         let resolve_path = |project_path: &'static str, rel_path| {
@@ -453,6 +1021,66 @@ mod tests {
                 None
             );
         }
+
+        #[test]
+        fn toc_instance_accessors() {
+            use crate::ldtk::{LdtkTocInstanceData, ReferenceToAnEntityInstance};
+
+            let toc_instance_data = |entity_iid: &str| LdtkTocInstanceData {
+                iids: ReferenceToAnEntityInstance {
+                    entity_iid: entity_iid.to_string(),
+                    ..Default::default()
+                },
+                ..Default::default()
+            };
+
+            let json_data = LdtkJson {
+                toc: vec![
+                    LdtkTableOfContentEntry {
+                        identifier: "Door".to_string(),
+                        instances: None,
+                        instances_data: vec![
+                            toc_instance_data("door_0"),
+                            toc_instance_data("door_1"),
+                        ],
+                    },
+                    LdtkTableOfContentEntry {
+                        identifier: "Chest".to_string(),
+                        instances: None,
+                        instances_data: vec![toc_instance_data("chest_0")],
+                    },
+                ],
+                ..Default::default()
+            };
+
+            let project = LdtkProject::new(
+                LdtkProjectData::Standalone(LdtkJsonWithMetadata::new(json_data, HashMap::new())),
+                HashMap::new(),
+                None,
+                false,
+            );
+
+            assert_eq!(project.iter_toc_instances().count(), 3);
+
+            let doors: Vec<_> = project
+                .iter_toc_instances_by_identifier("Door")
+                .map(|instance| instance.entity_iid())
+                .collect();
+            assert_eq!(
+                doors,
+                vec![EntityIid::new("door_0"), EntityIid::new("door_1")]
+            );
+
+            assert_eq!(
+                project
+                    .get_toc_instance(&EntityIid::new("chest_0"))
+                    .map(|instance| instance.identifier()),
+                Some("Chest")
+            );
+            assert!(project
+                .get_toc_instance(&EntityIid::new("this_entity_doesnt_exist"))
+                .is_none());
+        }
     }
 
     #[cfg(feature = "external_levels")]
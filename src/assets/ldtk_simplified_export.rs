@@ -0,0 +1,883 @@
+use crate::{
+    assets::{
+        ldtk_project::ldtk_path_to_asset_path, ExportedImages, LdtkJsonWithMetadata, LdtkProject,
+        LdtkProjectData, LevelIndices, LevelMetadata,
+    },
+    ldtk::{
+        Definitions, EntityDefinition, EntityInstance, FieldInstance, FieldValue,
+        IntGridValueDefinition, LayerDefinition, LayerInstance, LdtkJson, Level, NeighbourLevel,
+        ReferenceToAnEntityInstance, Type, WorldLayout,
+    },
+};
+use bevy::{
+    asset::{
+        io::{AssetReaderError, Reader},
+        AssetLoader, AssetPath, LoadContext, ParseAssetPathError, ReadAssetBytesError,
+    },
+    color::HexColorError,
+    prelude::*,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use thiserror::Error;
+
+#[allow(unused_imports)]
+use crate::{components::LdtkWorldBundle, prelude::TileLayerRendering};
+
+/// Contents of a level's `data.json` in LDtk's super simple export.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SimplifiedLevel {
+    identifier: String,
+    // LDtk misspells this key
+    #[serde(rename = "uniqueIdentifer", alias = "uniqueIdentifier")]
+    unique_identifier: String,
+    x: i32,
+    y: i32,
+    width: i32,
+    height: i32,
+    #[serde(default)]
+    bg_color: Option<String>,
+    #[serde(default)]
+    neighbour_levels: Vec<SimplifiedNeighbour>,
+    #[serde(default)]
+    custom_fields: BTreeMap<String, Value>,
+    #[serde(default)]
+    layers: Vec<String>,
+    #[serde(default)]
+    entities: BTreeMap<String, Vec<SimplifiedEntity>>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SimplifiedNeighbour {
+    level_iid: String,
+    dir: String,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SimplifiedEntity {
+    iid: String,
+    layer: String,
+    x: i32,
+    y: i32,
+    width: i32,
+    height: i32,
+    #[serde(default)]
+    color: u32,
+    #[serde(default)]
+    custom_fields: BTreeMap<String, Value>,
+}
+
+/// Settings for loading an [`LdtkProject`] from LDtk's super simple export.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct LdtkSimplifiedExportLoaderSettings {
+    /// Identifiers of other levels of the same export to load into the project.
+    ///
+    /// Each level of the super simple export is written to its own folder, with no file listing
+    /// them all.
+    /// So, by default, the project only contains the level whose `data.json` was loaded.
+    /// The levels listed here are loaded from the `data.json` of the sibling folder with the same
+    /// name, and are added to the project after the loaded level.
+    pub levels: Vec<String>,
+}
+
+/// Errors that can occur when loading an [`LdtkProject`] from LDtk's super simple export.
+#[derive(Debug, Error)]
+pub enum LdtkSimplifiedExportLoaderError {
+    /// Encountered IO error reading the simplified level
+    #[error("encountered IO error reading simplified level: {0}")]
+    Io(#[from] std::io::Error),
+    /// Unable to read a file of the simplified export
+    #[error("unable to read file of simplified export: {0}")]
+    ReadAssetBytes(#[from] ReadAssetBytesError),
+    /// Unable to deserialize the simplified level
+    #[error("unable to deserialize simplified level: {0}")]
+    Deserialize(#[from] serde_json::Error),
+    /// Unable to parse the background color of the simplified level
+    #[error("unable to parse background color of simplified level: {0}")]
+    BgColor(#[from] HexColorError),
+    /// An IntGrid CSV of the simplified level is malformed
+    #[error("IntGrid CSV of layer \"{0}\" is malformed")]
+    MalformedIntGridCsv(String),
+    /// Unable to parse relative path in the simplified export.
+    #[error("unable to parse relative path in simplified export: {0}")]
+    ParseRelativePath(#[from] ParseAssetPathError),
+}
+
+/// AssetLoader for [`LdtkProject`]s from LDtk's super simple export.
+///
+/// When the project's `simplified_export` option is enabled, LDtk writes a folder for every
+/// level containing a `data.json`, a `_composite.png` of the entire level, and a PNG for every
+/// layer, as well as a CSV for every IntGrid layer.
+/// Loading a level's `data.json` as an [`LdtkProject`] produces a project synthesized from these
+/// files, so it can be spawned with an [`LdtkWorldBundle`] like any other project.
+///
+/// Since `data.json` is too generic a file name to claim, this loader isn't registered for any
+/// extension.
+/// Instead, select it with a `data.json.meta` file next to the level's `data.json`:
+/// ```ron
+/// (
+///     meta_format_version: "1.0",
+///     asset: Load(
+///         loader: "bevy_ecs_ldtk::assets::LdtkSimplifiedExportLoader",
+///         settings: (
+///             levels: [],
+///         ),
+///     ),
+/// )
+/// ```
+/// This requires the [`AssetPlugin::meta_check`] to allow reading the meta file, which it does by
+/// default.
+/// The level can then be loaded like any other asset:
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_ecs_ldtk::prelude::*;
+/// fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+///     commands.spawn(LdtkWorldBundle {
+///         ldtk_handle: asset_server
+///             .load::<LdtkProject>("my_project/simplified/Level_0/data.json")
+///             .into(),
+///         ..Default::default()
+///     });
+/// }
+/// # bevy::ecs::system::assert_is_system(setup);
+/// ```
+///
+/// The synthesized project has no tilesets or auto-layer rules.
+/// So, levels are always rendered with their exported images, as if by
+/// [`TileLayerRendering::ExportedImages`], while IntGrid layers still spawn their cells and
+/// entity layers still spawn their entities.
+///
+/// Some information is lost in the super simple export, so the synthesized project is
+/// approximate:
+/// - entity positions are treated as their top-left corner,
+/// - the types of custom fields are inferred from their values,
+/// - and IntGrid values have no identifiers or colors.
+///
+/// Other levels of the same export can be added to the project with
+/// [`LdtkSimplifiedExportLoaderSettings::levels`].
+///
+/// [`AssetPlugin::meta_check`]: bevy::asset::AssetPlugin::meta_check
+#[derive(Default, TypePath)]
+#[type_path = "bevy_ecs_ldtk::assets"]
+pub struct LdtkSimplifiedExportLoader;
+
+/// Parses an IntGrid CSV of the super simple export, returning its width, height, and values.
+fn parse_int_grid_csv(csv: &str) -> Option<(i32, i32, Vec<i32>)> {
+    let rows = csv
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            line.split(',')
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+                .map(|value| value.parse::<i32>().ok())
+                .collect::<Option<Vec<_>>>()
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    let c_wid = rows.first()?.len();
+
+    if c_wid == 0 || rows.iter().any(|row| row.len() != c_wid) {
+        return None;
+    }
+
+    Some((c_wid as i32, rows.len() as i32, rows.concat()))
+}
+
+/// Infers the LDtk field type of a custom field value of the super simple export.
+fn infer_field_value(value: &Value) -> Option<(String, FieldValue)> {
+    let is_color = |s: &str| s.len() == 7 && s.starts_with('#') && Srgba::hex(s).is_ok();
+
+    let scalar = match value {
+        Value::Null => ("String", FieldValue::String(None)),
+        Value::Bool(b) => ("Bool", FieldValue::Bool(*b)),
+        Value::Number(n) => match n.as_i64().and_then(|i| i32::try_from(i).ok()) {
+            Some(i) => ("Int", FieldValue::Int(Some(i))),
+            None => ("Float", FieldValue::Float(n.as_f64().map(|f| f as f32))),
+        },
+        Value::String(s) if is_color(s) => ("Color", FieldValue::Color(Srgba::hex(s).ok()?.into())),
+        Value::String(s) => ("String", FieldValue::String(Some(s.clone()))),
+        Value::Object(o) if o.contains_key("cx") && o.contains_key("cy") => {
+            let cx = o.get("cx")?.as_i64()? as i32;
+            let cy = o.get("cy")?.as_i64()? as i32;
+            ("Point", FieldValue::Point(Some(IVec2::new(cx, cy))))
+        }
+        Value::Object(o) if o.contains_key("entityIid") => (
+            "EntityRef",
+            FieldValue::EntityRef(Some(ReferenceToAnEntityInstance::deserialize(value).ok()?)),
+        ),
+        Value::Object(_) => return None,
+        Value::Array(values) => {
+            let elements = values
+                .iter()
+                .filter(|v| !v.is_null())
+                .map(infer_field_value)
+                .collect::<Option<Vec<_>>>()?;
+
+            let element_type = elements
+                .first()
+                .map(|(t, _)| t.clone())
+                .unwrap_or("String".to_string());
+
+            if elements.iter().any(|(t, _)| *t != element_type) {
+                return None;
+            }
+
+            let values = elements.into_iter().map(|(_, v)| v);
+
+            let array = match element_type.as_str() {
+                "Bool" => FieldValue::Bools(
+                    values
+                        .filter_map(|v| match v {
+                            FieldValue::Bool(b) => Some(b),
+                            _ => None,
+                        })
+                        .collect(),
+                ),
+                "Int" => FieldValue::Ints(
+                    values
+                        .filter_map(|v| match v {
+                            FieldValue::Int(i) => Some(i),
+                            _ => None,
+                        })
+                        .collect(),
+                ),
+                "Float" => FieldValue::Floats(
+                    values
+                        .filter_map(|v| match v {
+                            FieldValue::Float(f) => Some(f),
+                            _ => None,
+                        })
+                        .collect(),
+                ),
+                "Color" => FieldValue::Colors(
+                    values
+                        .filter_map(|v| match v {
+                            FieldValue::Color(c) => Some(c),
+                            _ => None,
+                        })
+                        .collect(),
+                ),
+                "Point" => FieldValue::Points(
+                    values
+                        .filter_map(|v| match v {
+                            FieldValue::Point(p) => Some(p),
+                            _ => None,
+                        })
+                        .collect(),
+                ),
+                "EntityRef" => FieldValue::EntityRefs(
+                    values
+                        .filter_map(|v| match v {
+                            FieldValue::EntityRef(r) => Some(r),
+                            _ => None,
+                        })
+                        .collect(),
+                ),
+                _ => FieldValue::Strings(
+                    values
+                        .filter_map(|v| match v {
+                            FieldValue::String(s) => Some(s),
+                            _ => None,
+                        })
+                        .collect(),
+                ),
+            };
+
+            return Some((format!("Array<{element_type}>"), array));
+        }
+    };
+
+    Some((scalar.0.to_string(), scalar.1))
+}
+
+fn field_instances(custom_fields: &BTreeMap<String, Value>) -> Vec<FieldInstance> {
+    custom_fields
+        .iter()
+        .filter_map(|(identifier, value)| {
+            let Some((field_instance_type, value)) = infer_field_value(value) else {
+                warn!("unable to infer the type of simplified export field \"{identifier}\", skipping it");
+                return None;
+            };
+
+            Some(FieldInstance {
+                identifier: identifier.clone(),
+                tile: None,
+                field_instance_type,
+                value,
+                def_uid: 0,
+                real_editor_values: Vec::new(),
+            })
+        })
+        .collect()
+}
+
+/// Definitions synthesized from the levels of a super simple export.
+///
+/// Layers and entities are identified by their identifiers, so levels agree on their uids.
+#[derive(Debug, Default)]
+struct SimplifiedDefinitions {
+    layers: Vec<LayerDefinition>,
+    entities: Vec<EntityDefinition>,
+}
+
+impl SimplifiedDefinitions {
+    fn layer_uid(&mut self, identifier: &str, layer_type: Type, grid_size: i32) -> i32 {
+        if let Some(layer) = self.layers.iter().find(|l| l.identifier == identifier) {
+            return layer.uid;
+        }
+
+        let uid = self.layers.len() as i32 + 1;
+
+        self.layers.push(LayerDefinition {
+            identifier: identifier.to_string(),
+            uid,
+            layer_definition_type: format!("{layer_type:?}"),
+            purple_type: layer_type,
+            grid_size,
+            display_opacity: 1.,
+            ..default()
+        });
+
+        uid
+    }
+
+    fn add_int_grid_values(&mut self, layer_uid: i32, values: &[i32]) {
+        let Some(layer) = self.layers.iter_mut().find(|l| l.uid == layer_uid) else {
+            return;
+        };
+
+        let mut known = layer
+            .int_grid_values
+            .iter()
+            .map(|v| v.value)
+            .collect::<BTreeSet<_>>();
+
+        for value in values.iter().filter(|v| **v != 0) {
+            if known.insert(*value) {
+                layer.int_grid_values.push(IntGridValueDefinition {
+                    value: *value,
+                    ..default()
+                });
+            }
+        }
+    }
+
+    fn entity_uid(&mut self, identifier: &str, entity: &SimplifiedEntity) -> i32 {
+        if let Some(entity) = self.entities.iter().find(|e| e.identifier == identifier) {
+            return entity.uid;
+        }
+
+        let uid = self.entities.len() as i32 + 1;
+
+        self.entities.push(EntityDefinition {
+            identifier: identifier.to_string(),
+            uid,
+            width: entity.width,
+            height: entity.height,
+            color: simplified_color(entity.color),
+            ..default()
+        });
+
+        uid
+    }
+}
+
+fn simplified_color(color: u32) -> Color {
+    let [_, r, g, b] = color.to_be_bytes();
+    Color::srgb_u8(r, g, b)
+}
+
+/// Reads a file of the super simple export, returning [`None`] if it doesn't exist.
+async fn read_optional_bytes(
+    load_context: &mut LoadContext<'_>,
+    path: AssetPath<'_>,
+) -> Result<Option<Vec<u8>>, LdtkSimplifiedExportLoaderError> {
+    match load_context.read_asset_bytes(path).await {
+        Ok(bytes) => Ok(Some(bytes)),
+        Err(ReadAssetBytesError::AssetReaderError(AssetReaderError::NotFound(_))) => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Converts a simplified level to a [`Level`] with complete layer data, loading its images.
+async fn load_simplified_level(
+    load_context: &mut LoadContext<'_>,
+    data_path: &AssetPath<'static>,
+    simplified: SimplifiedLevel,
+    level_index: usize,
+    definitions: &mut SimplifiedDefinitions,
+) -> Result<(Level, ExportedImages), LdtkSimplifiedExportLoaderError> {
+    let composite = load_context.load(ldtk_path_to_asset_path(data_path, "_composite.png")?);
+
+    let mut layer_instances = Vec::new();
+    let mut layer_images = HashMap::new();
+
+    // IntGrid layers are read first, so their grid sizes can be used for entity layers
+    let mut int_grid_layers = Vec::new();
+    for file_name in &simplified.layers {
+        let identifier = file_name.strip_suffix(".png").unwrap_or(file_name);
+
+        let csv_path = ldtk_path_to_asset_path(data_path, &format!("{identifier}.csv"))?;
+        let int_grid = read_optional_bytes(load_context, csv_path)
+            .await?
+            .map(|bytes| {
+                parse_int_grid_csv(&String::from_utf8_lossy(&bytes)).ok_or_else(|| {
+                    LdtkSimplifiedExportLoaderError::MalformedIntGridCsv(identifier.to_string())
+                })
+            })
+            .transpose()?;
+
+        int_grid_layers.push((file_name, identifier, int_grid));
+    }
+
+    let default_grid_size = int_grid_layers
+        .iter()
+        .find_map(|(_, _, int_grid)| {
+            int_grid
+                .as_ref()
+                .map(|(c_wid, _, _)| simplified.width / (*c_wid).max(1))
+        })
+        .unwrap_or(16)
+        .max(1);
+
+    let entity_layer_identifiers = simplified
+        .entities
+        .values()
+        .flatten()
+        .map(|entity| entity.layer.clone())
+        .collect::<BTreeSet<_>>();
+
+    let layer_instance =
+        |identifier: &str, layer_type, layer_def_uid, grid_size: i32| LayerInstance {
+            identifier: identifier.to_string(),
+            iid: format!("{}-{identifier}", simplified.unique_identifier),
+            layer_instance_type: layer_type,
+            layer_def_uid,
+            level_id: level_index as i32,
+            grid_size,
+            c_wid: simplified.width / grid_size,
+            c_hei: simplified.height / grid_size,
+            opacity: 1.,
+            visible: true,
+            ..default()
+        };
+
+    for (file_name, identifier, int_grid) in int_grid_layers {
+        let layer_def_uid = match int_grid {
+            Some((c_wid, c_hei, int_grid_csv)) => {
+                let grid_size = (simplified.width / c_wid).max(1);
+                let layer_def_uid = definitions.layer_uid(identifier, Type::IntGrid, grid_size);
+                definitions.add_int_grid_values(layer_def_uid, &int_grid_csv);
+
+                layer_instances.push(LayerInstance {
+                    c_wid,
+                    c_hei,
+                    int_grid_csv,
+                    ..layer_instance(identifier, Type::IntGrid, layer_def_uid, grid_size)
+                });
+
+                layer_def_uid
+            }
+            None if entity_layer_identifiers.contains(identifier) => {
+                definitions.layer_uid(identifier, Type::Entities, default_grid_size)
+            }
+            None => {
+                let layer_def_uid =
+                    definitions.layer_uid(identifier, Type::Tiles, default_grid_size);
+
+                layer_instances.push(layer_instance(
+                    identifier,
+                    Type::Tiles,
+                    layer_def_uid,
+                    default_grid_size,
+                ));
+
+                layer_def_uid
+            }
+        };
+
+        let image_path = ldtk_path_to_asset_path(data_path, file_name)?;
+        layer_images.insert(layer_def_uid, load_context.load(image_path));
+    }
+
+    // Entity layers are spawned above the others, in no particular order
+    for layer_identifier in entity_layer_identifiers.iter().rev() {
+        let layer_def_uid =
+            definitions.layer_uid(layer_identifier, Type::Entities, default_grid_size);
+
+        let entity_instances = simplified
+            .entities
+            .iter()
+            .flat_map(|(identifier, entities)| entities.iter().map(move |e| (identifier, e)))
+            .filter(|(_, entity)| entity.layer == *layer_identifier)
+            .map(|(identifier, entity)| EntityInstance {
+                identifier: identifier.clone(),
+                iid: entity.iid.clone(),
+                def_uid: definitions.entity_uid(identifier, entity),
+                grid: IVec2::new(entity.x, entity.y) / default_grid_size,
+                px: IVec2::new(entity.x, entity.y),
+                width: entity.width,
+                height: entity.height,
+                smart_color: simplified_color(entity.color),
+                world_x: Some(simplified.x + entity.x),
+                world_y: Some(simplified.y + entity.y),
+                field_instances: field_instances(&entity.custom_fields),
+                ..default()
+            })
+            .collect();
+
+        layer_instances.insert(
+            0,
+            LayerInstance {
+                entity_instances,
+                ..layer_instance(
+                    layer_identifier,
+                    Type::Entities,
+                    layer_def_uid,
+                    default_grid_size,
+                )
+            },
+        );
+    }
+
+    let level = Level {
+        identifier: simplified.identifier,
+        iid: simplified.unique_identifier,
+        uid: level_index as i32,
+        world_x: simplified.x,
+        world_y: simplified.y,
+        px_wid: simplified.width,
+        px_hei: simplified.height,
+        bg_color: simplified
+            .bg_color
+            .as_deref()
+            .map(Srgba::hex)
+            .transpose()?
+            .map(Color::from)
+            .unwrap_or_default(),
+        neighbours: simplified
+            .neighbour_levels
+            .into_iter()
+            .map(|neighbour| NeighbourLevel {
+                dir: neighbour.dir,
+                level_iid: neighbour.level_iid,
+                ..default()
+            })
+            .collect(),
+        field_instances: field_instances(&simplified.custom_fields),
+        layer_instances: Some(layer_instances),
+        ..default()
+    };
+
+    Ok((level, ExportedImages::new(Some(composite), layer_images)))
+}
+
+impl AssetLoader for LdtkSimplifiedExportLoader {
+    type Asset = LdtkProject;
+    type Settings = LdtkSimplifiedExportLoaderSettings;
+    type Error = LdtkSimplifiedExportLoaderError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        settings: &Self::Settings,
+        load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+
+        let mut simplified_levels = vec![(
+            load_context.path().clone(),
+            serde_json::from_slice::<SimplifiedLevel>(&bytes)?,
+        )];
+
+        for identifier in &settings.levels {
+            let data_path = ldtk_path_to_asset_path(
+                load_context.path(),
+                &format!("../{identifier}/data.json"),
+            )?
+            .into_owned();
+
+            let bytes = load_context.read_asset_bytes(data_path.clone()).await?;
+            simplified_levels.push((data_path, serde_json::from_slice(&bytes)?));
+        }
+
+        let mut definitions = SimplifiedDefinitions::default();
+        let mut levels = Vec::new();
+        let mut level_map = HashMap::new();
+
+        for (level_index, (data_path, simplified)) in simplified_levels.into_iter().enumerate() {
+            let (level, exported_images) = load_simplified_level(
+                load_context,
+                &data_path,
+                simplified,
+                level_index,
+                &mut definitions,
+            )
+            .await?;
+
+            level_map.insert(
+                level.iid.clone(),
+                LevelMetadata::new(None, LevelIndices::in_root(level_index))
+                    .with_exported_images(exported_images),
+            );
+            levels.push(level);
+        }
+
+        let data = LdtkJson {
+            levels,
+            defs: Definitions {
+                layers: definitions.layers,
+                entities: definitions.entities,
+                ..default()
+            },
+            world_layout: Some(WorldLayout::Free),
+            simplified_export: true,
+            ..default()
+        };
+
+        let int_grid_image_handle = data
+            .defs
+            .create_int_grid_image()
+            .map(|image| load_context.add_labeled_asset("int_grid_image".to_string(), image));
+
+        Ok(LdtkProject::new(
+            LdtkProjectData::Standalone(LdtkJsonWithMetadata::new(data, level_map)),
+            HashMap::new(),
+            int_grid_image_handle,
+            true,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_int_grid_csv() {
+        assert_eq!(
+            parse_int_grid_csv("0,1,0,\n2,0,0,\n"),
+            Some((3, 2, vec![0, 1, 0, 2, 0, 0]))
+        );
+        assert_eq!(
+            parse_int_grid_csv("0,1\r\n2,0\r\n"),
+            Some((2, 2, vec![0, 1, 2, 0]))
+        );
+
+        assert_eq!(parse_int_grid_csv("0,1,\n2,\n"), None);
+        assert_eq!(parse_int_grid_csv("0,a,\n"), None);
+        assert_eq!(parse_int_grid_csv(""), None);
+    }
+
+    #[test]
+    fn infers_field_types() {
+        let infer = |json: &str| infer_field_value(&serde_json::from_str(json).unwrap());
+
+        assert_eq!(
+            infer("3"),
+            Some(("Int".to_string(), FieldValue::Int(Some(3))))
+        );
+        assert_eq!(
+            infer("1.5"),
+            Some(("Float".to_string(), FieldValue::Float(Some(1.5))))
+        );
+        assert_eq!(
+            infer("\"hello\""),
+            Some((
+                "String".to_string(),
+                FieldValue::String(Some("hello".to_string()))
+            ))
+        );
+        assert_eq!(
+            infer("\"#FF0000\""),
+            Some((
+                "Color".to_string(),
+                FieldValue::Color(Color::srgb(1., 0., 0.))
+            ))
+        );
+        assert_eq!(
+            infer("{\"cx\": 1, \"cy\": 2}"),
+            Some((
+                "Point".to_string(),
+                FieldValue::Point(Some(IVec2::new(1, 2)))
+            ))
+        );
+        assert_eq!(
+            infer("[1, 2]"),
+            Some((
+                "Array<Int>".to_string(),
+                FieldValue::Ints(vec![Some(1), Some(2)])
+            ))
+        );
+        assert_eq!(infer("[1, \"a\"]"), None);
+        assert_eq!(infer("{\"a\": 1}"), None);
+    }
+
+    #[test]
+    fn definitions_are_shared_by_identifier() {
+        let mut definitions = SimplifiedDefinitions::default();
+
+        let collisions = definitions.layer_uid("Collisions", Type::IntGrid, 8);
+        let entities = definitions.layer_uid("Entities", Type::Entities, 8);
+
+        assert_ne!(collisions, entities);
+        assert_eq!(
+            definitions.layer_uid("Collisions", Type::IntGrid, 8),
+            collisions
+        );
+
+        definitions.add_int_grid_values(collisions, &[0, 1, 2, 1]);
+        definitions.add_int_grid_values(collisions, &[3, 2]);
+
+        assert_eq!(
+            definitions.layers[0]
+                .int_grid_values
+                .iter()
+                .map(|v| v.value)
+                .collect::<Vec<_>>(),
+            vec![1, 2, 3]
+        );
+        assert_eq!(definitions.layers[0].purple_type, Type::IntGrid);
+    }
+
+    #[test]
+    fn loads_simplified_export_fixture() {
+        use crate::assets::{LdtkAssetPlugin, LevelMetadataAccessor};
+
+        let mut app = App::new();
+        app.add_plugins((
+            TaskPoolPlugin::default(),
+            AssetPlugin::default(),
+            LdtkAssetPlugin,
+        ))
+        .init_asset::<Image>();
+
+        // Level_0's data.json.meta selects the loader, and adds Level_1 to the project
+        let handle: Handle<LdtkProject> = app
+            .world()
+            .resource::<AssetServer>()
+            .load("simplified_export/simplified/Level_0/data.json");
+
+        for _ in 0..1000 {
+            app.update();
+
+            let load_state = app.world().resource::<AssetServer>().load_state(&handle);
+            if !load_state.is_loading() {
+                break;
+            }
+
+            std::thread::sleep(std::time::Duration::from_millis(5));
+        }
+
+        let load_state = app.world().resource::<AssetServer>().load_state(&handle);
+        assert!(load_state.is_loaded(), "{load_state:?}");
+
+        let project = app
+            .world()
+            .resource::<Assets<LdtkProject>>()
+            .get(&handle)
+            .unwrap();
+        let data = project.json_data();
+
+        assert!(data.simplified_export);
+        assert_eq!(
+            data.levels
+                .iter()
+                .map(|level| level.identifier.as_str())
+                .collect::<Vec<_>>(),
+            vec!["Level_0", "Level_1"]
+        );
+
+        let level_0 = &data.levels[0];
+        assert_eq!(level_0.world_x, 0);
+        assert_eq!(level_0.neighbours[0].level_iid, data.levels[1].iid);
+        assert_eq!(level_0.bg_color, Color::srgb_u8(0x40, 0x46, 0x5b));
+
+        let layer_instances = level_0.layer_instances.as_ref().unwrap();
+        let entities = layer_instances
+            .iter()
+            .find(|layer| layer.identifier == "Entities")
+            .unwrap();
+        assert_eq!(entities.grid_size, 16);
+        assert_eq!(entities.entity_instances[0].identifier, "Player");
+        assert_eq!(entities.entity_instances[0].px, IVec2::new(16, 16));
+        assert_eq!(entities.entity_instances[0].grid, IVec2::new(1, 1));
+
+        let collisions = layer_instances
+            .iter()
+            .find(|layer| layer.identifier == "Collisions")
+            .unwrap();
+        assert_eq!(collisions.layer_instance_type, Type::IntGrid);
+        assert_eq!((collisions.c_wid, collisions.c_hei), (4, 2));
+        assert_eq!(collisions.int_grid_csv, vec![1, 0, 0, 2, 1, 1, 1, 1]);
+
+        assert_eq!(data.defs.layers.len(), 2);
+        assert_eq!(
+            data.defs.layers[0]
+                .int_grid_values
+                .iter()
+                .map(|value| value.value)
+                .collect::<Vec<_>>(),
+            vec![1, 2]
+        );
+
+        let exported_images = project
+            .get_level_metadata_by_iid(&level_0.iid)
+            .unwrap()
+            .exported_images();
+        assert!(exported_images.level().is_some());
+        assert!(exported_images
+            .layers()
+            .contains_key(&collisions.layer_def_uid));
+    }
+
+    #[test]
+    fn deserializes_simplified_level() {
+        let simplified: SimplifiedLevel = serde_json::from_str(
+            r##"{
+                "identifier": "Level_0",
+                "uniqueIdentifer": "a1b2",
+                "x": 256,
+                "y": 0,
+                "width": 128,
+                "height": 64,
+                "bgColor": "#40465B",
+                "neighbourLevels": [{ "levelIid": "c3d4", "dir": "w" }],
+                "customFields": { "difficulty": 2 },
+                "layers": ["Collisions.png", "Background.png"],
+                "entities": {
+                    "Player": [{
+                        "id": "Player",
+                        "iid": "e5f6",
+                        "layer": "Entities",
+                        "x": 16,
+                        "y": 32,
+                        "width": 16,
+                        "height": 16,
+                        "color": 16711680,
+                        "customFields": { "health": 10 }
+                    }]
+                }
+            }"##,
+        )
+        .unwrap();
+
+        assert_eq!(simplified.unique_identifier, "a1b2");
+        assert_eq!(simplified.neighbour_levels[0].level_iid, "c3d4");
+        assert_eq!(simplified.entities["Player"][0].layer, "Entities");
+        assert_eq!(
+            simplified_color(simplified.entities["Player"][0].color),
+            Color::srgb_u8(255, 0, 0)
+        );
+        assert_eq!(
+            field_instances(&simplified.entities["Player"][0].custom_fields)[0].value,
+            FieldValue::Int(Some(10))
+        );
+    }
+}
@@ -1,10 +1,14 @@
 use crate::assets::LevelIndices;
 use bevy::{prelude::*, reflect::Reflect};
 use derive_getters::Getters;
+use std::collections::HashMap;
 
 #[cfg(feature = "external_levels")]
 use crate::assets::LdtkExternalLevel;
 
+#[cfg(feature = "external_levels")]
+use bevy::asset::AssetPath;
+
 /// Metadata produced for every level during [`LdtkProject`] loading.
 ///
 /// [`LdtkProject`]: crate::assets::LdtkProject
@@ -14,12 +18,63 @@ pub struct LevelMetadata {
     bg_image: Option<Handle<Image>>,
     /// Indices of this level in the project.
     indices: LevelIndices,
+    /// Handles for the images LDtk exported for this level and its layers, if they were loaded.
+    exported_images: ExportedImages,
 }
 
 impl LevelMetadata {
     /// Construct a new [`LevelMetadata`].
     pub fn new(bg_image: Option<Handle<Image>>, indices: LevelIndices) -> Self {
-        LevelMetadata { bg_image, indices }
+        LevelMetadata {
+            bg_image,
+            indices,
+            exported_images: ExportedImages::default(),
+        }
+    }
+
+    /// Replaces the background image of the [`LevelMetadata`].
+    ///
+    /// Used for lazily-loaded external levels, whose background images are loaded on demand.
+    #[cfg(feature = "external_levels")]
+    pub(crate) fn with_bg_image(self, bg_image: Option<Handle<Image>>) -> Self {
+        LevelMetadata { bg_image, ..self }
+    }
+
+    /// Adds the images LDtk exported for this level to the [`LevelMetadata`].
+    pub fn with_exported_images(self, exported_images: ExportedImages) -> Self {
+        LevelMetadata {
+            exported_images,
+            ..self
+        }
+    }
+}
+
+/// Handles for the PNGs LDtk exported for a level, according to the project's
+/// [`image_export_mode`].
+///
+/// These are only loaded if [`LdtkProjectLoaderSettings::load_exported_images`] is enabled,
+/// and are used for rendering with [`TileLayerRendering::ExportedImages`].
+///
+/// [`image_export_mode`]: crate::ldtk::LdtkJson::image_export_mode
+/// [`LdtkProjectLoaderSettings::load_exported_images`]: crate::assets::LdtkProjectLoaderSettings::load_exported_images
+/// [`TileLayerRendering::ExportedImages`]: crate::prelude::TileLayerRendering::ExportedImages
+#[derive(Clone, Debug, Default, Eq, PartialEq, Getters, Reflect)]
+pub struct ExportedImages {
+    /// Image of the entire level, if one was exported.
+    level: Option<Handle<Image>>,
+    /// Images of individual layers, keyed by layer definition uid.
+    layers: HashMap<i32, Handle<Image>>,
+}
+
+impl ExportedImages {
+    /// Construct a new [`ExportedImages`].
+    pub fn new(level: Option<Handle<Image>>, layers: HashMap<i32, Handle<Image>>) -> Self {
+        ExportedImages { level, layers }
+    }
+
+    /// Returns true if no images were exported for the level.
+    pub fn is_empty(&self) -> bool {
+        self.level.is_none() && self.layers.is_empty()
     }
 }
 
@@ -32,7 +87,14 @@ pub struct ExternalLevelMetadata {
     /// Common metadata for this level.
     metadata: LevelMetadata,
     /// Handle to this external level's asset data.
+    ///
+    /// For levels that are loaded on demand, this is a placeholder handle that only points to
+    /// asset data while the level is in a [`LevelSet`].
+    ///
+    /// [`LevelSet`]: crate::components::LevelSet
     external_handle: Handle<LdtkExternalLevel>,
+    /// Paths of this level's assets, if they are loaded on demand rather than with the project.
+    lazy_paths: Option<LazyLevelPaths>,
 }
 
 #[cfg(feature = "external_levels")]
@@ -42,10 +104,41 @@ impl ExternalLevelMetadata {
         ExternalLevelMetadata {
             metadata,
             external_handle,
+            lazy_paths: None,
+        }
+    }
+
+    /// Marks the level as loaded on demand, from the given paths.
+    pub fn with_lazy_paths(self, lazy_paths: LazyLevelPaths) -> Self {
+        ExternalLevelMetadata {
+            lazy_paths: Some(lazy_paths),
+            ..self
         }
     }
 }
 
+#[cfg(feature = "external_levels")]
+/// Paths of the assets of an external level that is loaded on demand.
+///
+/// See [`LdtkProjectLoaderSettings::lazy_external_levels`] for more details.
+///
+/// [`LdtkProjectLoaderSettings::lazy_external_levels`]: crate::assets::LdtkProjectLoaderSettings::lazy_external_levels
+#[derive(Clone, Debug, Default, Eq, PartialEq, Getters, Reflect)]
+pub struct LazyLevelPaths {
+    /// Path to the level's `.ldtkl` file.
+    level: AssetPath<'static>,
+    /// Path to the level's background image, if it has one.
+    bg_image: Option<AssetPath<'static>>,
+}
+
+#[cfg(feature = "external_levels")]
+impl LazyLevelPaths {
+    /// Construct a new [`LazyLevelPaths`].
+    pub fn new(level: AssetPath<'static>, bg_image: Option<AssetPath<'static>>) -> Self {
+        LazyLevelPaths { level, bg_image }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -64,6 +157,18 @@ mod tests {
 
         assert_eq!(*level_metadata.bg_image(), Some(Handle::<Image>::default()),);
         assert_eq!(*level_metadata.indices(), LevelIndices::in_world(2, 3));
+        assert!(level_metadata.exported_images().is_empty());
+
+        let level_metadata = level_metadata.with_exported_images(ExportedImages::new(
+            None,
+            HashMap::from([(1, Handle::<Image>::default())]),
+        ));
+
+        assert_eq!(
+            level_metadata.exported_images().layers().get(&1),
+            Some(&Handle::<Image>::default())
+        );
+        assert_eq!(*level_metadata.indices(), LevelIndices::in_world(2, 3));
     }
 
     #[cfg(feature = "external_levels")]
@@ -79,5 +184,11 @@ mod tests {
             *external_level_metadata.external_handle(),
             Handle::default()
         );
+        assert_eq!(*external_level_metadata.lazy_paths(), None);
+
+        let lazy_paths = LazyLevelPaths::new("project/Level_0.ldtkl".into(), None);
+        let external_level_metadata = external_level_metadata.with_lazy_paths(lazy_paths.clone());
+
+        assert_eq!(*external_level_metadata.lazy_paths(), Some(lazy_paths));
     }
 }
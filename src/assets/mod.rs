@@ -4,10 +4,10 @@ mod ldtk_asset_plugin;
 pub use ldtk_asset_plugin::LdtkAssetPlugin;
 
 mod level_metadata;
-pub use level_metadata::LevelMetadata;
+pub use level_metadata::{ExportedImages, LevelMetadata};
 
 #[cfg(feature = "external_levels")]
-pub use level_metadata::ExternalLevelMetadata;
+pub use level_metadata::{ExternalLevelMetadata, LazyLevelPaths};
 
 mod level_locale;
 
@@ -33,7 +33,19 @@ mod ldtk_project_data;
 pub use ldtk_project_data::LdtkProjectData;
 
 mod ldtk_project;
-pub use ldtk_project::LdtkProject;
+pub use ldtk_project::{LdtkProject, LdtkProjectLoaderSettings, SkippedTilesets};
+
+#[cfg(feature = "internal_levels")]
+mod ldtk_simplified_export;
+
+#[cfg(feature = "internal_levels")]
+pub use ldtk_simplified_export::{
+    LdtkSimplifiedExportLoader, LdtkSimplifiedExportLoaderError, LdtkSimplifiedExportLoaderSettings,
+};
 
 mod level_indices;
 pub use level_indices::LevelIndices;
+
+mod tile_metadata;
+pub use tile_metadata::ParsedTileMetadata;
+pub(crate) use tile_metadata::TileMetadataRegistry;
@@ -0,0 +1,405 @@
+//! Contains [`ParsedTileMetadata`] and the registry of types tile custom data is parsed into.
+use crate::ldtk::{TileCustomMetadata, TilesetDefinition};
+use bevy::{ecs::system::EntityCommands, prelude::*};
+use serde::de::DeserializeOwned;
+use std::{
+    any::{type_name, Any, TypeId},
+    collections::HashMap,
+    fmt,
+    sync::{Arc, PoisonError, RwLock},
+};
+use thiserror::Error;
+
+/// Error for custom data that is neither valid JSON nor valid RON for the expected type.
+#[derive(Debug, Error)]
+#[error("expected JSON ({json}) or RON ({ron})")]
+struct TileMetadataDataError {
+    json: serde_json::Error,
+    ron: ron::error::SpannedError,
+}
+
+/// Error for custom data of a tile that couldn't be parsed into a registered type.
+#[derive(Debug, Error)]
+#[error("{type_name}: {source}")]
+struct TileMetadataTypeError {
+    type_name: &'static str,
+    source: TileMetadataDataError,
+}
+
+/// Error for custom data of a tile that couldn't be parsed into any of its tileset's registered
+/// types.
+#[derive(Debug, Error)]
+#[error(
+    "unable to parse custom data of tile {tile_id} in tileset \"{tileset_identifier}\" as any registered type: {}",
+    join_errors(.errors)
+)]
+struct TileMetadataParseError {
+    tileset_identifier: String,
+    tile_id: i32,
+    errors: Vec<TileMetadataTypeError>,
+}
+
+fn join_errors(errors: &[TileMetadataTypeError]) -> String {
+    errors
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/// Parses tile custom data as JSON, falling back to RON.
+fn parse_tile_metadata<T: DeserializeOwned>(data: &str) -> Result<T, TileMetadataDataError> {
+    serde_json::from_str(data)
+        .or_else(|json| ron::from_str(data).map_err(|ron| TileMetadataDataError { json, ron }))
+}
+
+fn parse_tile_metadata_value<T: DeserializeOwned + Component + Clone>(
+    data: &str,
+) -> Result<Arc<dyn Any + Send + Sync>, TileMetadataDataError> {
+    Ok(Arc::new(parse_tile_metadata::<T>(data)?))
+}
+
+/// Inserts a clone of the value parsed at load time, which is always a `T`.
+fn insert_tile_metadata<T: DeserializeOwned + Component + Clone>(
+    value: &(dyn Any + Send + Sync),
+    entity_commands: &mut EntityCommands,
+) {
+    if let Some(tile_metadata) = value.downcast_ref::<T>() {
+        entity_commands.insert(tile_metadata.clone());
+    }
+}
+
+#[derive(Copy, Clone)]
+struct TileMetadataParser {
+    type_id: TypeId,
+    type_name: &'static str,
+    parse: fn(&str) -> Result<Arc<dyn Any + Send + Sync>, TileMetadataDataError>,
+    insert: fn(&(dyn Any + Send + Sync), &mut EntityCommands),
+}
+
+/// Types registered with [`LdtkTileAppExt::register_tile_metadata`], keyed by tileset identifier.
+///
+/// Shared with the [`LdtkProject`] loader, so registrations made after the loader is created
+/// still apply to projects loaded afterwards.
+///
+/// [`LdtkTileAppExt::register_tile_metadata`]: crate::app::LdtkTileAppExt::register_tile_metadata
+/// [`LdtkProject`]: crate::assets::LdtkProject
+#[derive(Clone, Default, Resource)]
+pub(crate) struct TileMetadataRegistry(Arc<RwLock<HashMap<String, Vec<TileMetadataParser>>>>);
+
+impl TileMetadataRegistry {
+    /// Register `T` to be parsed from the custom data of the given tileset's tiles.
+    pub(crate) fn register<T: DeserializeOwned + Component + Clone>(
+        &self,
+        tileset_identifier: &str,
+    ) {
+        let mut registry = self.0.write().unwrap_or_else(PoisonError::into_inner);
+        let parsers = registry.entry(tileset_identifier.to_string()).or_default();
+
+        if parsers
+            .iter()
+            .all(|parser| parser.type_id != TypeId::of::<T>())
+        {
+            parsers.push(TileMetadataParser {
+                type_id: TypeId::of::<T>(),
+                type_name: type_name::<T>(),
+                parse: parse_tile_metadata_value::<T>,
+                insert: insert_tile_metadata::<T>,
+            });
+        }
+    }
+
+    /// Parse the custom data of the given tilesets into their registered types.
+    ///
+    /// Custom data that can't be parsed as any of its tileset's registered types is skipped with a
+    /// warning.
+    pub(crate) fn parse(&self, tileset_definitions: &[TilesetDefinition]) -> ParsedTileMetadata {
+        let registry = self.0.read().unwrap_or_else(PoisonError::into_inner);
+
+        let mut parsed_tile_metadata = ParsedTileMetadata::default();
+
+        for tileset_definition in tileset_definitions {
+            let Some(parsers) = registry.get(&tileset_definition.identifier) else {
+                continue;
+            };
+
+            for TileCustomMetadata { data, tile_id } in &tileset_definition.custom_data {
+                let (entries, errors) = parse_custom_data(parsers, data);
+
+                let error = TileMetadataParseError {
+                    tileset_identifier: tileset_definition.identifier.clone(),
+                    tile_id: *tile_id,
+                    errors,
+                };
+
+                // Tiles are expected to only have data for some of the registered types
+                if entries.is_empty() {
+                    warn!("{error}");
+                    continue;
+                } else if !error.errors.is_empty() {
+                    debug!("{error}");
+                }
+
+                parsed_tile_metadata
+                    .tilesets
+                    .entry(tileset_definition.identifier.clone())
+                    .or_default()
+                    .insert(*tile_id, entries);
+            }
+        }
+
+        parsed_tile_metadata
+    }
+}
+
+/// Parses custom data with each of the given parsers, returning the parsed values and the errors
+/// of the parsers that failed.
+fn parse_custom_data(
+    parsers: &[TileMetadataParser],
+    data: &str,
+) -> (Vec<ParsedTileMetadataEntry>, Vec<TileMetadataTypeError>) {
+    let mut entries = Vec::new();
+    let mut errors = Vec::new();
+
+    for parser in parsers {
+        match (parser.parse)(data) {
+            Ok(value) => entries.push(ParsedTileMetadataEntry {
+                value,
+                insert: parser.insert,
+            }),
+            Err(source) => errors.push(TileMetadataTypeError {
+                type_name: parser.type_name,
+                source,
+            }),
+        }
+    }
+
+    (entries, errors)
+}
+
+#[derive(Clone)]
+struct ParsedTileMetadataEntry {
+    value: Arc<dyn Any + Send + Sync>,
+    insert: fn(&(dyn Any + Send + Sync), &mut EntityCommands),
+}
+
+impl fmt::Debug for ParsedTileMetadataEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ParsedTileMetadataEntry")
+            .finish_non_exhaustive()
+    }
+}
+
+impl PartialEq for ParsedTileMetadataEntry {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.value, &other.value)
+    }
+}
+
+/// The custom data of tiles, parsed into the types registered with
+/// [`LdtkTileAppExt::register_tile_metadata`].
+///
+/// Parsing happens when the [`LdtkProject`] loads, so this data is available without spawning
+/// any levels.
+/// Can be accessed via [`LdtkProject::parsed_tile_metadata`].
+///
+/// [`LdtkTileAppExt::register_tile_metadata`]: crate::app::LdtkTileAppExt::register_tile_metadata
+/// [`LdtkProject`]: crate::assets::LdtkProject
+/// [`LdtkProject::parsed_tile_metadata`]: crate::assets::LdtkProject::parsed_tile_metadata
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ParsedTileMetadata {
+    tilesets: HashMap<String, HashMap<i32, Vec<ParsedTileMetadataEntry>>>,
+}
+
+impl ParsedTileMetadata {
+    /// Get the `T` parsed from the custom data of the tile with the given id in the given
+    /// tileset.
+    pub fn get<T: Component>(&self, tileset_identifier: &str, tile_id: i32) -> Option<&T> {
+        self.tilesets
+            .get(tileset_identifier)?
+            .get(&tile_id)?
+            .iter()
+            .find_map(|entry| entry.value.downcast_ref::<T>())
+    }
+
+    /// Iterate through the tile ids of the given tileset along with the `T` parsed from their
+    /// custom data.
+    pub fn iter<'a, T: Component>(
+        &'a self,
+        tileset_identifier: &str,
+    ) -> impl Iterator<Item = (i32, &'a T)> {
+        self.tilesets
+            .get(tileset_identifier)
+            .into_iter()
+            .flatten()
+            .filter_map(|(tile_id, entries)| {
+                entries
+                    .iter()
+                    .find_map(|entry| entry.value.downcast_ref::<T>())
+                    .map(|value| (*tile_id, value))
+            })
+    }
+
+    /// Insert clones of the types parsed from a tile's custom data on its entity.
+    pub(crate) fn insert_for_tile(
+        &self,
+        tileset_identifier: &str,
+        tile_id: i32,
+        entity_commands: &mut EntityCommands,
+    ) {
+        let Some(entries) = self
+            .tilesets
+            .get(tileset_identifier)
+            .and_then(|tiles| tiles.get(&tile_id))
+        else {
+            return;
+        };
+
+        for entry in entries {
+            (entry.insert)(entry.value.as_ref(), entity_commands);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::ecs::world::CommandQueue;
+    use serde::Deserialize;
+
+    #[derive(Clone, Component, Debug, Deserialize, PartialEq)]
+    struct Friction {
+        value: f32,
+    }
+
+    #[derive(Clone, Component, Debug, Deserialize, PartialEq)]
+    struct Sound(String);
+
+    #[test]
+    fn custom_data_is_parsed_as_json_or_ron() {
+        let registry = TileMetadataRegistry::default();
+        registry.register::<Friction>("Terrain");
+        registry.register::<Friction>("Terrain");
+        registry.register::<Sound>("Terrain");
+
+        let tileset_definitions = [
+            TilesetDefinition {
+                identifier: "Terrain".to_string(),
+                custom_data: vec![
+                    TileCustomMetadata {
+                        data: r#"{ "value": 0.5 }"#.to_string(),
+                        tile_id: 1,
+                    },
+                    TileCustomMetadata {
+                        data: "(value: 0.25)".to_string(),
+                        tile_id: 2,
+                    },
+                    TileCustomMetadata {
+                        data: r#""splash""#.to_string(),
+                        tile_id: 3,
+                    },
+                    TileCustomMetadata {
+                        data: "not metadata".to_string(),
+                        tile_id: 4,
+                    },
+                ],
+                ..Default::default()
+            },
+            TilesetDefinition {
+                identifier: "Unregistered".to_string(),
+                custom_data: vec![TileCustomMetadata {
+                    data: r#"{ "value": 1.0 }"#.to_string(),
+                    tile_id: 1,
+                }],
+                ..Default::default()
+            },
+        ];
+
+        let parsed = registry.parse(&tileset_definitions);
+
+        assert_eq!(
+            parsed.get::<Friction>("Terrain", 1),
+            Some(&Friction { value: 0.5 })
+        );
+        assert_eq!(
+            parsed.get::<Friction>("Terrain", 2),
+            Some(&Friction { value: 0.25 })
+        );
+        assert_eq!(parsed.get::<Friction>("Terrain", 3), None);
+        assert_eq!(
+            parsed.get::<Sound>("Terrain", 3),
+            Some(&Sound("splash".to_string()))
+        );
+        assert_eq!(parsed.get::<Friction>("Terrain", 4), None);
+        assert_eq!(parsed.get::<Friction>("Unregistered", 1), None);
+        assert_eq!(parsed.iter::<Friction>("Unregistered").count(), 0);
+
+        let mut frictions = parsed.iter::<Friction>("Terrain").collect::<Vec<_>>();
+        frictions.sort_by_key(|(tile_id, _)| *tile_id);
+        assert_eq!(
+            frictions,
+            vec![
+                (1, &Friction { value: 0.5 }),
+                (2, &Friction { value: 0.25 })
+            ]
+        );
+        assert_eq!(parsed.tilesets["Terrain"][&1].len(), 1);
+    }
+
+    #[test]
+    fn parse_errors_name_every_registered_type() {
+        let registry = TileMetadataRegistry::default();
+        registry.register::<Friction>("Terrain");
+        registry.register::<Sound>("Terrain");
+
+        let registry = registry.0.read().unwrap();
+        let parsers = &registry["Terrain"];
+
+        // data for one of the types isn't an error
+        let (entries, errors) = parse_custom_data(parsers, r#""splash""#);
+        assert_eq!(entries.len(), 1);
+        assert_eq!(errors.len(), 1);
+
+        let (entries, errors) = parse_custom_data(parsers, "not metadata");
+        assert!(entries.is_empty());
+
+        let message = TileMetadataParseError {
+            tileset_identifier: "Terrain".to_string(),
+            tile_id: 4,
+            errors,
+        }
+        .to_string();
+
+        assert!(message.contains("tile 4 in tileset \"Terrain\""));
+        assert!(message.contains(type_name::<Friction>()));
+        assert!(message.contains(type_name::<Sound>()));
+    }
+
+    #[test]
+    fn parsed_values_are_cloned_onto_tiles() {
+        let registry = TileMetadataRegistry::default();
+        registry.register::<Friction>("Terrain");
+
+        let parsed = registry.parse(&[TilesetDefinition {
+            identifier: "Terrain".to_string(),
+            custom_data: vec![TileCustomMetadata {
+                data: r#"{ "value": 0.5 }"#.to_string(),
+                tile_id: 1,
+            }],
+            ..Default::default()
+        }]);
+
+        let mut world = World::new();
+        let tile = world.spawn_empty().id();
+        let other_tile = world.spawn_empty().id();
+
+        let mut queue = CommandQueue::default();
+        let mut commands = Commands::new(&mut queue, &world);
+        parsed.insert_for_tile("Terrain", 1, &mut commands.entity(tile));
+        parsed.insert_for_tile("Terrain", 2, &mut commands.entity(other_tile));
+        queue.apply(&mut world);
+
+        assert_eq!(world.get::<Friction>(tile), Some(&Friction { value: 0.5 }));
+        assert_eq!(world.get::<Friction>(other_tile), None);
+    }
+}
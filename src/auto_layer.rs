@@ -0,0 +1,711 @@
+//! Evaluation of LDtk auto-layer rules at runtime.
+//!
+//! LDtk bakes the results of auto-layer rules into the `auto_layer_tiles` of each layer instance.
+//! This module re-evaluates those rules so that auto-layers can be updated when IntGrid values
+//! change at runtime.
+//!
+//! All coordinates here are LDtk cell coordinates, where y increases downward.
+use crate::ldtk::{
+    AutoLayerRuleDefinition, Checker, LayerDefinition, TileInstance, TileMode, TilesetDefinition,
+};
+use bevy::prelude::*;
+use std::{
+    cmp::Reverse,
+    collections::{HashMap, HashSet},
+};
+
+/// Pattern value that matches any non-zero IntGrid value.
+const ANYTHING: i32 = 1000001;
+
+/// IntGrid values that auto-layer rules are evaluated against.
+pub(crate) struct AutoLayerSource<'a> {
+    values: &'a [i32],
+    c_wid: i32,
+    c_hei: i32,
+    /// Map from IntGrid values to the uid of their group.
+    value_groups: HashMap<i32, i32>,
+}
+
+impl<'a> AutoLayerSource<'a> {
+    /// Creates a source from the row-major values of an IntGrid layer and its definition.
+    pub(crate) fn new(
+        values: &'a [i32],
+        c_wid: i32,
+        c_hei: i32,
+        source_layer_definition: &LayerDefinition,
+    ) -> Self {
+        let value_groups = source_layer_definition
+            .int_grid_values
+            .iter()
+            .map(|value_definition| (value_definition.value, value_definition.group_uid))
+            .collect();
+
+        AutoLayerSource {
+            values,
+            c_wid,
+            c_hei,
+            value_groups,
+        }
+    }
+
+    fn get(&self, cell: IVec2) -> Option<i32> {
+        if cell.x < 0 || cell.y < 0 || cell.x >= self.c_wid || cell.y >= self.c_hei {
+            return None;
+        }
+
+        self.values
+            .get((cell.y * self.c_wid + cell.x) as usize)
+            .copied()
+    }
+
+    fn pattern_value_matches(&self, pattern_value: i32, value: i32) -> bool {
+        let matches = match pattern_value.abs() {
+            ANYTHING => value != 0,
+            group_value if group_value > 999 => {
+                value != 0 && self.value_groups.get(&value) == Some(&(group_value / 1000 - 1))
+            }
+            pattern_value => value == pattern_value,
+        };
+
+        if pattern_value > 0 {
+            matches
+        } else {
+            !matches
+        }
+    }
+}
+
+/// Instance-specific data of the auto-layer that rules are evaluated for.
+pub(crate) struct AutoLayerInstance<'a> {
+    pub seed: i32,
+    pub optional_rules: &'a [i32],
+    pub c_wid: i32,
+    pub grid_size: i32,
+    pub tileset_definition: Option<&'a TilesetDefinition>,
+}
+
+/// Deterministic pseudo-random number in `0..max` for the given seed and cell.
+fn rand_seed_coords(seed: i32, cell: IVec2, max: i32) -> i32 {
+    if max <= 1 {
+        return 0;
+    }
+
+    let mut h = seed
+        .wrapping_add(cell.x.wrapping_mul(374761393))
+        .wrapping_add(cell.y.wrapping_mul(668265263));
+    h = (h ^ (h >> 13)).wrapping_mul(1274126177);
+    (h ^ (h >> 16)).rem_euclid(max)
+}
+
+/// Returns the active rules of the layer definition, in priority order.
+fn active_rules<'a>(
+    layer_definition: &'a LayerDefinition,
+    instance: &AutoLayerInstance,
+) -> Vec<&'a AutoLayerRuleDefinition> {
+    layer_definition
+        .auto_rule_groups
+        .iter()
+        .filter(|group| {
+            group.active && (!group.is_optional || instance.optional_rules.contains(&group.uid))
+        })
+        .flat_map(|group| group.rules.iter())
+        .filter(|rule| rule.active)
+        .collect()
+}
+
+fn passes_modulo(rule: &AutoLayerRuleDefinition, cell: IVec2) -> bool {
+    let x_modulo = rule.x_modulo.max(1);
+    let y_modulo = rule.y_modulo.max(1);
+
+    let x_passes = match rule.checker {
+        Checker::Horizontal => (cell.x + (cell.y / y_modulo) % 2 - rule.x_offset) % x_modulo == 0,
+        _ => (cell.x - rule.x_offset) % x_modulo == 0,
+    };
+
+    let y_passes = match rule.checker {
+        Checker::Vertical => (cell.y + (cell.x / x_modulo) % 2 - rule.y_offset) % y_modulo == 0,
+        _ => (cell.y - rule.y_offset) % y_modulo == 0,
+    };
+
+    x_passes && y_passes
+}
+
+fn pattern_matches(
+    rule: &AutoLayerRuleDefinition,
+    source: &AutoLayerSource,
+    cell: IVec2,
+    direction: IVec2,
+) -> bool {
+    let radius = rule.size / 2;
+
+    for y in 0..rule.size {
+        for x in 0..rule.size {
+            let pattern_value = rule
+                .pattern
+                .get((y * rule.size + x) as usize)
+                .copied()
+                .unwrap_or_default();
+
+            if pattern_value == 0 {
+                continue;
+            }
+
+            let checked_cell = cell + IVec2::new(x - radius, y - radius) * direction;
+
+            let Some(value) = source.get(checked_cell).or(rule.out_of_bounds_value) else {
+                return false;
+            };
+
+            if !source.pattern_value_matches(pattern_value, value) {
+                return false;
+            }
+        }
+    }
+
+    true
+}
+
+/// Returns the flips the rule matched with at the given cell, if it matched.
+fn rule_match(
+    rule: &AutoLayerRuleDefinition,
+    instance: &AutoLayerInstance,
+    source: &AutoLayerSource,
+    cell: IVec2,
+) -> Option<BVec2> {
+    if !passes_modulo(rule, cell) {
+        return None;
+    }
+
+    if rule.chance < 1.
+        && rand_seed_coords(instance.seed.wrapping_add(rule.uid), cell, 100) as f32
+            >= rule.chance * 100.
+    {
+        return None;
+    }
+
+    [
+        BVec2::new(false, false),
+        BVec2::new(true, false),
+        BVec2::new(false, true),
+        BVec2::new(true, true),
+    ]
+    .into_iter()
+    .filter(|flip| (!flip.x || rule.flip_x) && (!flip.y || rule.flip_y))
+    .find(|flip| {
+        let direction = IVec2::new(if flip.x { -1 } else { 1 }, if flip.y { -1 } else { 1 });
+        pattern_matches(rule, source, cell, direction)
+    })
+}
+
+/// Returns the rectangles of tile ids the rule may pick from.
+fn tile_rects(rule: &AutoLayerRuleDefinition) -> Vec<Vec<i32>> {
+    if !rule.tile_rects_ids.is_empty() {
+        return rule.tile_rects_ids.clone();
+    }
+
+    // Projects older than LDtk 1.5.0 only provide tile ids.
+    let tile_ids = rule.tile_ids.clone().unwrap_or_default();
+    match rule.tile_mode {
+        TileMode::Single => tile_ids.into_iter().map(|tile_id| vec![tile_id]).collect(),
+        TileMode::Stamp if tile_ids.is_empty() => Vec::new(),
+        TileMode::Stamp => vec![tile_ids],
+    }
+}
+
+/// Returns the tileset cell of the given tile id.
+fn tileset_cell(tile_id: i32, tileset_definition: Option<&TilesetDefinition>) -> IVec2 {
+    match tileset_definition {
+        Some(tileset_definition) if tileset_definition.c_wid > 0 => IVec2::new(
+            tile_id % tileset_definition.c_wid,
+            tile_id / tileset_definition.c_wid,
+        ),
+        _ => IVec2::ZERO,
+    }
+}
+
+fn random_offset(seed: i32, cell: IVec2, min: i32, max: i32) -> i32 {
+    if max > min {
+        min + rand_seed_coords(seed, cell, max - min + 1)
+    } else {
+        min
+    }
+}
+
+fn rule_tiles(
+    rule: &AutoLayerRuleDefinition,
+    instance: &AutoLayerInstance,
+    cell: IVec2,
+    flip: BVec2,
+) -> Vec<TileInstance> {
+    let rects = tile_rects(rule);
+    if rects.is_empty() {
+        return Vec::new();
+    }
+
+    let seed = instance.seed.wrapping_add(rule.uid);
+    let rect = &rects[rand_seed_coords(seed, cell, rects.len() as i32) as usize];
+
+    let tile_cells = rect
+        .iter()
+        .map(|tile_id| tileset_cell(*tile_id, instance.tileset_definition))
+        .collect::<Vec<_>>();
+
+    let min = tile_cells
+        .iter()
+        .copied()
+        .reduce(IVec2::min)
+        .unwrap_or_default();
+    let max = tile_cells
+        .iter()
+        .copied()
+        .reduce(IVec2::max)
+        .unwrap_or_default();
+    let pivot = ((max - min).as_vec2() * Vec2::new(rule.pivot_x, rule.pivot_y))
+        .floor()
+        .as_ivec2();
+
+    let direction = IVec2::new(if flip.x { -1 } else { 1 }, if flip.y { -1 } else { 1 });
+
+    let px_offset = IVec2::new(
+        rule.tile_x_offset
+            + random_offset(
+                seed.wrapping_add(1),
+                cell,
+                rule.tile_random_x_min,
+                rule.tile_random_x_max,
+            ),
+        rule.tile_y_offset
+            + random_offset(
+                seed.wrapping_add(2),
+                cell,
+                rule.tile_random_y_min,
+                rule.tile_random_y_max,
+            ),
+    ) * direction;
+
+    rect.iter()
+        .zip(tile_cells)
+        .map(|(tile_id, tile_cell)| {
+            let cell_offset = (tile_cell - min - pivot) * direction;
+
+            TileInstance {
+                a: rule.alpha,
+                d: vec![rule.uid, cell.y * instance.c_wid + cell.x],
+                f: flip.x as i32 | (flip.y as i32) << 1,
+                px: (cell + cell_offset) * instance.grid_size + px_offset,
+                src: instance
+                    .tileset_definition
+                    .map(|tileset_definition| {
+                        IVec2::splat(tileset_definition.padding)
+                            + tile_cell
+                                * (tileset_definition.tile_grid_size + tileset_definition.spacing)
+                    })
+                    .unwrap_or_default(),
+                t: *tile_id,
+            }
+        })
+        .collect()
+}
+
+/// Evaluates the auto-layer rules of the layer definition at each of the given cells.
+///
+/// Returns the generated tiles in drawing order, like the `auto_layer_tiles` of a layer instance.
+/// So, tiles of higher-priority rules come last.
+///
+/// Perlin filtering and biomes aren't supported, so rules using them are applied wherever their
+/// pattern matches.
+pub(crate) fn evaluate_auto_layer_rules(
+    layer_definition: &LayerDefinition,
+    instance: &AutoLayerInstance,
+    source: &AutoLayerSource,
+    cells: impl IntoIterator<Item = IVec2>,
+) -> Vec<TileInstance> {
+    let rules = active_rules(layer_definition, instance);
+
+    let mut tiles = Vec::new();
+
+    for cell in cells {
+        for (priority, rule) in rules.iter().enumerate() {
+            let Some(flip) = rule_match(rule, instance, source, cell) else {
+                continue;
+            };
+
+            tiles.extend(
+                rule_tiles(rule, instance, cell, flip)
+                    .into_iter()
+                    .map(|tile| (priority, tile)),
+            );
+
+            if rule.break_on_match {
+                break;
+            }
+        }
+    }
+
+    // stable, so tiles of the same rule keep their order
+    tiles.sort_by_key(|(priority, _)| Reverse(*priority));
+
+    tiles.into_iter().map(|(_, tile)| tile).collect()
+}
+
+/// Evaluates the auto-layer rules of the layer definition again at the given cells, replacing
+/// the tiles of rules that matched there.
+///
+/// `tiles` are the current tiles of the layer in drawing order, like the `auto_layer_tiles` of a
+/// layer instance, and are kept in drawing order.
+/// Tiles of rules that matched at other cells are left as they are.
+pub(crate) fn reevaluate_auto_layer_tiles(
+    layer_definition: &LayerDefinition,
+    instance: &AutoLayerInstance,
+    source: &AutoLayerSource,
+    tiles: &mut Vec<TileInstance>,
+    cells: &HashSet<IVec2>,
+) {
+    let c_wid = instance.c_wid.max(1);
+
+    // the second value of a tile's `d` is the index of the cell its rule matched at
+    tiles.retain(|tile| match tile.d.as_slice() {
+        [_, coord_id, ..] => !cells.contains(&IVec2::new(coord_id % c_wid, coord_id / c_wid)),
+        _ => true,
+    });
+
+    let mut cells = cells.iter().copied().collect::<Vec<_>>();
+    cells.sort_by_key(|cell| (cell.y, cell.x));
+
+    tiles.extend(evaluate_auto_layer_rules(
+        layer_definition,
+        instance,
+        source,
+        cells,
+    ));
+
+    let priorities = active_rules(layer_definition, instance)
+        .into_iter()
+        .enumerate()
+        .map(|(priority, rule)| (rule.uid, priority))
+        .collect::<HashMap<_, _>>();
+
+    // stable, so tiles of the same rule keep their order
+    tiles.sort_by_key(|tile| {
+        Reverse(
+            tile.d
+                .first()
+                .and_then(|rule_uid| priorities.get(rule_uid))
+                .copied()
+                .unwrap_or(usize::MAX),
+        )
+    });
+}
+
+/// Returns how many cells away from a changed IntGrid cell the layer's tiles may be affected.
+///
+/// The first value is the largest pattern radius of the layer's rules, and the second is the
+/// furthest, in cells, that a rule can place a tile from the cell it matched.
+pub(crate) fn auto_layer_reach(
+    layer_definition: &LayerDefinition,
+    instance: &AutoLayerInstance,
+) -> (i32, i32) {
+    let rules = active_rules(layer_definition, instance);
+
+    let pattern_radius = rules
+        .iter()
+        .map(|rule| rule.size / 2)
+        .max()
+        .unwrap_or_default();
+
+    let grid_size = instance.grid_size.max(1);
+    let tile_reach = rules
+        .iter()
+        .map(|rule| {
+            let rect_reach = tile_rects(rule)
+                .iter()
+                .map(|rect| {
+                    let tile_cells = rect
+                        .iter()
+                        .map(|tile_id| tileset_cell(*tile_id, instance.tileset_definition));
+                    let min = tile_cells.clone().reduce(IVec2::min).unwrap_or_default();
+                    let max = tile_cells.reduce(IVec2::max).unwrap_or_default();
+                    (max - min).max_element()
+                })
+                .max()
+                .unwrap_or_default();
+
+            let px_reach = rule.tile_x_offset.abs().max(rule.tile_y_offset.abs())
+                + rule
+                    .tile_random_x_min
+                    .abs()
+                    .max(rule.tile_random_x_max.abs())
+                    .max(rule.tile_random_y_min.abs())
+                    .max(rule.tile_random_y_max.abs());
+
+            // round up, since tiles with offsets can overlap the next cell
+            rect_reach + (px_reach + grid_size - 1) / grid_size
+        })
+        .max()
+        .unwrap_or_default();
+
+    (pattern_radius, tile_reach)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ldtk::{AutoLayerRuleGroup, IntGridValueDefinition};
+
+    fn rule(uid: i32, pattern: Vec<i32>, tile_id: i32) -> AutoLayerRuleDefinition {
+        AutoLayerRuleDefinition {
+            active: true,
+            alpha: 1.,
+            break_on_match: true,
+            chance: 1.,
+            checker: Checker::None,
+            pattern,
+            size: 3,
+            tile_rects_ids: vec![vec![tile_id]],
+            uid,
+            x_modulo: 1,
+            y_modulo: 1,
+            ..Default::default()
+        }
+    }
+
+    fn layer_definition(rules: Vec<AutoLayerRuleDefinition>) -> LayerDefinition {
+        LayerDefinition {
+            auto_rule_groups: vec![AutoLayerRuleGroup {
+                active: true,
+                rules,
+                ..Default::default()
+            }],
+            int_grid_values: vec![
+                IntGridValueDefinition {
+                    value: 1,
+                    group_uid: 1,
+                    ..Default::default()
+                },
+                IntGridValueDefinition {
+                    value: 2,
+                    group_uid: 1,
+                    ..Default::default()
+                },
+                IntGridValueDefinition {
+                    value: 3,
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        }
+    }
+
+    fn instance() -> AutoLayerInstance<'static> {
+        AutoLayerInstance {
+            seed: 0,
+            optional_rules: &[],
+            c_wid: 3,
+            grid_size: 16,
+            tileset_definition: None,
+        }
+    }
+
+    #[rustfmt::skip]
+    const VALUES: [i32; 9] = [
+        0, 0, 0,
+        1, 1, 0,
+        2, 3, 0,
+    ];
+
+    #[test]
+    fn patterns_match_values_groups_and_anything() {
+        let layer_definition = layer_definition(vec![]);
+        let source = AutoLayerSource::new(&VALUES, 3, 3, &layer_definition);
+
+        assert!(source.pattern_value_matches(1, 1));
+        assert!(!source.pattern_value_matches(1, 2));
+        assert!(source.pattern_value_matches(-1, 2));
+        assert!(source.pattern_value_matches(ANYTHING, 3));
+        assert!(!source.pattern_value_matches(ANYTHING, 0));
+        assert!(source.pattern_value_matches(-ANYTHING, 0));
+        assert!(source.pattern_value_matches(2000, 2));
+        assert!(!source.pattern_value_matches(2000, 3));
+        assert!(source.pattern_value_matches(-2000, 3));
+    }
+
+    #[test]
+    fn break_on_match_and_priority_order() {
+        // ground with empty space above
+        #[rustfmt::skip]
+        let top = rule(1, vec![
+            0, -ANYTHING, 0,
+            0, 1, 0,
+            0, 0, 0,
+        ], 10);
+        let mut any = rule(2, vec![0, 0, 0, 0, ANYTHING, 0, 0, 0, 0], 20);
+        any.break_on_match = false;
+        let fill = rule(3, vec![0, 0, 0, 0, ANYTHING, 0, 0, 0, 0], 30);
+
+        let layer_definition = layer_definition(vec![top, any, fill]);
+        let source = AutoLayerSource::new(&VALUES, 3, 3, &layer_definition);
+
+        let tiles = evaluate_auto_layer_rules(
+            &layer_definition,
+            &instance(),
+            &source,
+            [IVec2::new(0, 1), IVec2::new(0, 2)],
+        );
+
+        let tile_ids = tiles
+            .iter()
+            .map(|tile| (tile.t, tile.px))
+            .collect::<Vec<_>>();
+
+        // the top rule breaks at (0, 1), while (0, 2) falls through to the fill rule
+        assert_eq!(
+            tile_ids,
+            vec![
+                (30, IVec2::new(0, 32)),
+                (20, IVec2::new(0, 32)),
+                (10, IVec2::new(0, 16)),
+            ]
+        );
+    }
+
+    #[test]
+    fn flipped_patterns_set_flip_bits() {
+        // wall to the left
+        let mut rule = rule(1, vec![0, 0, 0, 3, 1, 0, 0, 0, 0], 10);
+        rule.flip_x = true;
+
+        let layer_definition = layer_definition(vec![rule]);
+
+        #[rustfmt::skip]
+        let values = [
+            0, 0, 0,
+            0, 1, 3,
+            0, 0, 0,
+        ];
+        let source = AutoLayerSource::new(&values, 3, 3, &layer_definition);
+
+        let tiles =
+            evaluate_auto_layer_rules(&layer_definition, &instance(), &source, [IVec2::ONE]);
+
+        assert_eq!(tiles.len(), 1);
+        assert_eq!(tiles[0].f, 1);
+        assert_eq!(tiles[0].d, vec![1, 4]);
+    }
+
+    #[test]
+    fn out_of_bounds_cells_fail_unless_value_provided() {
+        // only checks the cell above
+        let mut rule = rule(1, vec![0, ANYTHING, 0, 0, 0, 0, 0, 0, 0], 10);
+        let top_cells = [IVec2::new(0, 0), IVec2::new(1, 0)];
+
+        let without_value = layer_definition(vec![rule.clone()]);
+        let source = AutoLayerSource::new(&VALUES, 3, 3, &without_value);
+        assert!(
+            evaluate_auto_layer_rules(&without_value, &instance(), &source, top_cells).is_empty()
+        );
+
+        rule.out_of_bounds_value = Some(1);
+        let with_value = layer_definition(vec![rule]);
+        let source = AutoLayerSource::new(&VALUES, 3, 3, &with_value);
+        assert_eq!(
+            evaluate_auto_layer_rules(&with_value, &instance(), &source, top_cells).len(),
+            2
+        );
+    }
+
+    #[test]
+    fn stamps_are_placed_around_pivot() {
+        let tileset_definition = TilesetDefinition {
+            c_wid: 4,
+            tile_grid_size: 16,
+            ..Default::default()
+        };
+        let instance = AutoLayerInstance {
+            tileset_definition: Some(&tileset_definition),
+            ..instance()
+        };
+
+        let mut rule = rule(1, vec![0, 0, 0, 0, 3, 0, 0, 0, 0], 0);
+        rule.tile_mode = TileMode::Stamp;
+        // 2x2 rectangle of tiles at the top left of the tileset
+        rule.tile_rects_ids = vec![vec![0, 1, 4, 5]];
+        rule.pivot_x = 1.;
+        rule.pivot_y = 1.;
+
+        let layer_definition = layer_definition(vec![rule]);
+        let source = AutoLayerSource::new(&VALUES, 3, 3, &layer_definition);
+
+        let tiles =
+            evaluate_auto_layer_rules(&layer_definition, &instance, &source, [IVec2::new(1, 2)]);
+
+        assert_eq!(
+            tiles
+                .iter()
+                .map(|tile| (tile.t, tile.px, tile.src))
+                .collect::<Vec<_>>(),
+            vec![
+                (0, IVec2::new(0, 16), IVec2::new(0, 0)),
+                (1, IVec2::new(16, 16), IVec2::new(16, 0)),
+                (4, IVec2::new(0, 32), IVec2::new(0, 16)),
+                (5, IVec2::new(16, 32), IVec2::new(16, 16)),
+            ]
+        );
+
+        assert_eq!(auto_layer_reach(&layer_definition, &instance), (1, 1));
+    }
+
+    #[test]
+    fn only_given_cells_are_reevaluated() {
+        let mut fill = rule(1, vec![0, 0, 0, 0, ANYTHING, 0, 0, 0, 0], 10);
+        fill.break_on_match = false;
+        // picks one of several tiles at random
+        let mut decoration = rule(2, vec![0, 0, 0, 0, 1, 0, 0, 0, 0], 20);
+        decoration.tile_rects_ids = vec![vec![20], vec![21], vec![22]];
+
+        let layer_definition = layer_definition(vec![decoration, fill]);
+
+        // tiles LDtk baked for the first two cells of the middle row, with different random picks
+        let baked_tile = |rule_uid, tile_id, cell: IVec2| TileInstance {
+            a: 1.,
+            d: vec![rule_uid, cell.y * 3 + cell.x],
+            px: cell * 16,
+            t: tile_id,
+            ..Default::default()
+        };
+        let mut tiles = vec![
+            baked_tile(1, 10, IVec2::new(0, 1)),
+            baked_tile(1, 10, IVec2::new(1, 1)),
+            baked_tile(2, 99, IVec2::new(0, 1)),
+            baked_tile(2, 99, IVec2::new(1, 1)),
+        ];
+
+        // (1, 1) changed from 1 to 3
+        #[rustfmt::skip]
+        let values = [
+            0, 0, 0,
+            1, 3, 0,
+            2, 3, 0,
+        ];
+        let source = AutoLayerSource::new(&values, 3, 3, &layer_definition);
+
+        reevaluate_auto_layer_tiles(
+            &layer_definition,
+            &instance(),
+            &source,
+            &mut tiles,
+            &HashSet::from([IVec2::new(1, 1)]),
+        );
+
+        assert_eq!(
+            tiles
+                .iter()
+                .map(|tile| (tile.t, tile.px))
+                .collect::<Vec<_>>(),
+            vec![
+                (10, IVec2::new(0, 16)),
+                (10, IVec2::new(16, 16)),
+                (99, IVec2::new(0, 16)),
+            ]
+        );
+    }
+}
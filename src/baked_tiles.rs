@@ -0,0 +1,217 @@
+//! Baking of tile layers into a single image, for [TileLayerRendering::Baked].
+use crate::ldtk::TileInstance;
+use bevy::{
+    asset::RenderAssetUsages,
+    image::TextureAccessError,
+    prelude::*,
+    render::render_resource::{Extent3d, TextureDimension, TextureFormat},
+};
+
+#[allow(unused_imports)]
+use crate::resources::TileLayerRendering;
+
+/// Image containing every tile of a layer, and where it should be placed.
+pub(crate) struct BakedTiles {
+    pub image: Image,
+    /// Pixel coordinates of the image's top-left corner in the layer, in LDtk space.
+    ///
+    /// This is negative if tiles are bigger than the layer's grid size, since they may overflow
+    /// the layer.
+    pub top_left: IVec2,
+}
+
+/// Composes the given tiles into a single image, in order, so that later tiles are drawn over
+/// earlier ones like overlapping tiles are stacked in LDtk.
+///
+/// Tile flips, alpha, and the layer's opacity are applied.
+/// Tiles larger than the grid are positioned according to the layer definition's tile pivot.
+pub(crate) fn bake_tiles(
+    tileset: &Image,
+    tile_size: i32,
+    grid_size: i32,
+    tile_pivot: Vec2,
+    layer_grid_size: IVec2,
+    tiles: &[TileInstance],
+    opacity: f32,
+) -> Result<BakedTiles, TextureAccessError> {
+    let margin = (tile_size - grid_size).max(0);
+    let top_left = IVec2::splat(-margin);
+    let image_size = layer_grid_size * grid_size + IVec2::splat(margin * 2);
+
+    let mut image = Image::new_fill(
+        Extent3d {
+            width: image_size.x as u32,
+            height: image_size.y as u32,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        &[0, 0, 0, 0],
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::default(),
+    );
+
+    let pivot_offset = (tile_pivot * (grid_size - tile_size) as f32)
+        .round()
+        .as_ivec2();
+
+    for tile in tiles {
+        let destination = tile.px + pivot_offset - top_left;
+        let (flip_x, flip_y) = (tile.f & 1 != 0, tile.f & 2 != 0);
+
+        for y in 0..tile_size {
+            for x in 0..tile_size {
+                let pixel = destination + IVec2::new(x, y);
+                if pixel.cmplt(IVec2::ZERO).any() || pixel.cmpge(image_size).any() {
+                    continue;
+                }
+
+                let source_x = if flip_x { tile_size - 1 - x } else { x };
+                let source_y = if flip_y { tile_size - 1 - y } else { y };
+                let source = tileset
+                    .get_color_at(
+                        (tile.src.x + source_x) as u32,
+                        (tile.src.y + source_y) as u32,
+                    )?
+                    .to_linear();
+
+                let alpha = source.alpha * tile.a * opacity;
+                if alpha <= 0. {
+                    continue;
+                }
+
+                // the canvas is written to directly, since converting colors with
+                // Image::set_color_at truncates rather than rounds
+                let canvas_pixel = image
+                    .pixel_bytes_mut(pixel.as_uvec2().extend(0))
+                    .expect("pixel should be within the bounds of the image");
+                let destination_color = LinearRgba::from(Srgba::from_u8_array([
+                    canvas_pixel[0],
+                    canvas_pixel[1],
+                    canvas_pixel[2],
+                    canvas_pixel[3],
+                ]));
+
+                canvas_pixel.copy_from_slice(
+                    &Srgba::from(blend_over(source.with_alpha(alpha), destination_color))
+                        .to_u8_array(),
+                );
+            }
+        }
+    }
+
+    Ok(BakedTiles { image, top_left })
+}
+
+/// Draws `source` over `destination`, with straight alpha.
+fn blend_over(source: LinearRgba, destination: LinearRgba) -> LinearRgba {
+    let alpha = source.alpha + destination.alpha * (1. - source.alpha);
+    if alpha <= 0. {
+        return LinearRgba::NONE;
+    }
+
+    let blend =
+        |s: f32, d: f32| (s * source.alpha + d * destination.alpha * (1. - source.alpha)) / alpha;
+
+    LinearRgba::new(
+        blend(source.red, destination.red),
+        blend(source.green, destination.green),
+        blend(source.blue, destination.blue),
+        alpha,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 4x2 tileset with two 2x2 tiles.
+    ///
+    /// The first tile is red on the left and blue on the right.
+    /// The second tile is green on top and transparent on the bottom.
+    fn tileset() -> Image {
+        let red = [255, 0, 0, 255];
+        let blue = [0, 0, 255, 255];
+        let green = [0, 255, 0, 255];
+        let clear = [0, 0, 0, 0];
+
+        Image::new(
+            Extent3d {
+                width: 4,
+                height: 2,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            [red, blue, green, green, red, blue, clear, clear].concat(),
+            TextureFormat::Rgba8UnormSrgb,
+            RenderAssetUsages::default(),
+        )
+    }
+
+    fn tile(px: IVec2, src: IVec2, f: i32) -> TileInstance {
+        TileInstance {
+            px,
+            src,
+            f,
+            a: 1.,
+            ..default()
+        }
+    }
+
+    fn pixel(image: &Image, x: u32, y: u32) -> Srgba {
+        image.get_color_at(x, y).unwrap().to_srgba()
+    }
+
+    #[test]
+    fn tiles_are_flipped_and_stacked_in_order() {
+        let baked = bake_tiles(
+            &tileset(),
+            2,
+            2,
+            Vec2::ZERO,
+            IVec2::new(2, 1),
+            &[
+                tile(IVec2::new(0, 0), IVec2::new(0, 0), 0),
+                tile(IVec2::new(2, 0), IVec2::new(0, 0), 1),
+                tile(IVec2::new(0, 0), IVec2::new(2, 0), 0),
+            ],
+            1.,
+        )
+        .unwrap();
+
+        assert_eq!(baked.top_left, IVec2::ZERO);
+        assert_eq!(baked.image.size(), UVec2::new(4, 2));
+
+        // the second tile covers the top row of the first one
+        assert_eq!(pixel(&baked.image, 0, 0), Srgba::GREEN);
+        assert_eq!(pixel(&baked.image, 0, 1), Srgba::RED);
+        assert_eq!(pixel(&baked.image, 1, 1), Srgba::BLUE);
+
+        // the flipped tile has blue on the left
+        assert_eq!(pixel(&baked.image, 2, 0), Srgba::BLUE);
+        assert_eq!(pixel(&baked.image, 3, 0), Srgba::RED);
+    }
+
+    #[test]
+    fn opacity_and_oversized_tiles_are_accounted_for() {
+        let baked = bake_tiles(
+            &tileset(),
+            2,
+            1,
+            Vec2::new(0., 1.),
+            IVec2::new(1, 1),
+            &[tile(IVec2::ZERO, IVec2::ZERO, 0)],
+            0.5,
+        )
+        .unwrap();
+
+        // tiles one pixel larger than the grid may overflow it by one pixel on any side
+        assert_eq!(baked.top_left, IVec2::splat(-1));
+        assert_eq!(baked.image.size(), UVec2::new(3, 3));
+
+        // pivoted to the bottom, so the tile sticks out above the cell
+        let top_left = pixel(&baked.image, 1, 0);
+        assert_eq!(top_left.red, 1.);
+        assert!((top_left.alpha - 0.5).abs() < 0.01);
+        assert_eq!(pixel(&baked.image, 1, 2).alpha, 0.);
+    }
+}
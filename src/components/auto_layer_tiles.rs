@@ -0,0 +1,38 @@
+use crate::ldtk::{LayerInstance, TileInstance};
+use bevy::prelude::*;
+
+#[allow(unused_imports)]
+use crate::resources::AutoTileUpdates;
+
+/// [Component] storing the current auto-layer tiles of a layer, in drawing order.
+///
+/// Only inserted with [AutoTileUpdates::OnIntGridChange], on the bottom tilemap entity of every
+/// spawned layer with auto-layer rules.
+///
+/// Starts out as the tiles LDtk generated for the layer.
+/// When IntGrid values change, only the tiles of rules matched at cells whose patterns include a
+/// changed value are generated again, so the rest stay the ones LDtk picked.
+#[derive(Clone, PartialEq, Debug, Default, Component, Reflect)]
+#[reflect(Component)]
+pub struct AutoLayerTiles {
+    tiles: Vec<TileInstance>,
+}
+
+impl From<&LayerInstance> for AutoLayerTiles {
+    fn from(layer_instance: &LayerInstance) -> Self {
+        AutoLayerTiles {
+            tiles: layer_instance.auto_layer_tiles.clone(),
+        }
+    }
+}
+
+impl AutoLayerTiles {
+    /// Returns the tiles, in the same order as the `auto_layer_tiles` of a [LayerInstance].
+    pub fn tiles(&self) -> &[TileInstance] {
+        &self.tiles
+    }
+
+    pub(crate) fn tiles_mut(&mut self) -> &mut Vec<TileInstance> {
+        &mut self.tiles
+    }
+}
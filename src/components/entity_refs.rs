@@ -0,0 +1,226 @@
+use crate::{
+    ldtk::{EntityInstance, FieldValue},
+    EntityIid,
+};
+use bevy::prelude::*;
+use std::collections::HashMap;
+
+#[allow(unused_imports)]
+use crate::resources::IidIndex;
+
+/// [`Component`] storing the iids referenced by each `EntityRef` field of an LDtk entity.
+///
+/// Inserted by the plugin on spawned LDtk entities that have at least one `EntityRef` or
+/// `EntityRefs` field.
+/// Null references are omitted.
+/// Once all of these iids are spawned, the plugin resolves them into an [`LdtkEntityRefs`]
+/// component.
+///
+/// [`Component`]: https://docs.rs/bevy/latest/bevy/ecs/prelude/trait.Component.html
+#[derive(Clone, Eq, PartialEq, Debug, Default, Component, Reflect)]
+#[reflect(Component)]
+pub struct LdtkEntityRefIids {
+    fields: HashMap<String, Vec<EntityIid>>,
+}
+
+impl LdtkEntityRefIids {
+    /// Collects the references of every `EntityRef` and `EntityRefs` field of the entity instance.
+    ///
+    /// Returns [`None`] if the entity instance has no such fields.
+    pub fn from_entity_instance(entity_instance: &EntityInstance) -> Option<LdtkEntityRefIids> {
+        let fields: HashMap<_, _> = entity_instance
+            .field_instances
+            .iter()
+            .filter_map(|field_instance| {
+                let iids = match &field_instance.value {
+                    FieldValue::EntityRef(entity_ref) => entity_ref.iter().collect::<Vec<_>>(),
+                    FieldValue::EntityRefs(entity_refs) => entity_refs.iter().flatten().collect(),
+                    _ => return None,
+                }
+                .into_iter()
+                .map(|entity_ref| EntityIid::new(entity_ref.entity_iid.clone()))
+                .collect();
+
+                Some((field_instance.identifier.clone(), iids))
+            })
+            .collect();
+
+        (!fields.is_empty()).then_some(LdtkEntityRefIids { fields })
+    }
+
+    /// Returns the iids referenced by the given field.
+    pub fn get(&self, field_identifier: &str) -> &[EntityIid] {
+        self.fields
+            .get(field_identifier)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Iterates through all fields and the iids they reference.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &[EntityIid])> {
+        self.fields
+            .iter()
+            .map(|(field_identifier, iids)| (field_identifier.as_str(), iids.as_slice()))
+    }
+
+    /// Resolves the iids into bevy [`Entity`]s using the given [`IidIndex`].
+    ///
+    /// Returns [`None`] unless every referenced entity is spawned.
+    pub fn resolve(&self, iid_index: &IidIndex) -> Option<LdtkEntityRefs> {
+        let fields = self
+            .fields
+            .iter()
+            .map(|(field_identifier, iids)| {
+                let entities = iids
+                    .iter()
+                    .map(|iid| iid_index.get_entity(iid))
+                    .collect::<Option<_>>()?;
+
+                Some((field_identifier.clone(), entities))
+            })
+            .collect::<Option<_>>()?;
+
+        Some(LdtkEntityRefs { fields })
+    }
+}
+
+/// [`Component`] storing the bevy [`Entity`]s referenced by each `EntityRef` field of an LDtk
+/// entity.
+///
+/// The plugin inserts this once every entity referenced by an [`LdtkEntityRefIids`] is spawned,
+/// including entities in other levels.
+/// If any of them despawn, like when their level is unloaded, this component is removed until
+/// they spawn again.
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_ecs_ldtk::prelude::*;
+/// #[derive(Component)]
+/// struct Target(Entity);
+///
+/// fn resolve_targets(
+///     mut commands: Commands,
+///     turret_query: Query<(Entity, &LdtkEntityRefs), Changed<LdtkEntityRefs>>,
+/// ) {
+///     for (turret, entity_refs) in &turret_query {
+///         if let Some(target) = entity_refs.get_single("target") {
+///             commands.entity(turret).insert(Target(target));
+///         }
+///     }
+/// }
+/// # bevy::ecs::system::assert_is_system(resolve_targets);
+/// ```
+///
+/// [`Component`]: https://docs.rs/bevy/latest/bevy/ecs/prelude/trait.Component.html
+#[derive(Clone, Eq, PartialEq, Debug, Default, Component, Reflect)]
+#[reflect(Component)]
+pub struct LdtkEntityRefs {
+    fields: HashMap<String, Vec<Entity>>,
+}
+
+impl LdtkEntityRefs {
+    /// Returns the entities referenced by the given field.
+    pub fn get(&self, field_identifier: &str) -> &[Entity] {
+        self.fields
+            .get(field_identifier)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Returns the first entity referenced by the given field.
+    ///
+    /// Intended for non-array `EntityRef` fields.
+    pub fn get_single(&self, field_identifier: &str) -> Option<Entity> {
+        self.get(field_identifier).first().copied()
+    }
+
+    /// Iterates through all fields and the entities they reference.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &[Entity])> {
+        self.fields
+            .iter()
+            .map(|(field_identifier, entities)| (field_identifier.as_str(), entities.as_slice()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ldtk::{FieldInstance, ReferenceToAnEntityInstance};
+
+    fn entity_ref(iid: &str) -> ReferenceToAnEntityInstance {
+        ReferenceToAnEntityInstance {
+            entity_iid: iid.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn field_instance(identifier: &str, value: FieldValue) -> FieldInstance {
+        FieldInstance {
+            identifier: identifier.to_string(),
+            value,
+            field_instance_type: "".to_string(),
+            tile: None,
+            def_uid: 0,
+            real_editor_values: Vec::new(),
+        }
+    }
+
+    fn entity_instance() -> EntityInstance {
+        EntityInstance {
+            field_instances: vec![
+                field_instance("mother", FieldValue::EntityRef(Some(entity_ref("m")))),
+                field_instance("friend", FieldValue::EntityRef(None)),
+                field_instance(
+                    "children",
+                    FieldValue::EntityRefs(vec![
+                        Some(entity_ref("a")),
+                        None,
+                        Some(entity_ref("b")),
+                    ]),
+                ),
+                field_instance("health", FieldValue::Int(Some(1))),
+            ],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn ref_iids_collected_from_entity_ref_fields() {
+        let ref_iids = LdtkEntityRefIids::from_entity_instance(&entity_instance()).unwrap();
+
+        assert_eq!(ref_iids.get("mother"), &[EntityIid::new("m")]);
+        assert!(ref_iids.get("friend").is_empty());
+        assert_eq!(
+            ref_iids.get("children"),
+            &[EntityIid::new("a"), EntityIid::new("b")]
+        );
+        assert_eq!(ref_iids.iter().count(), 3);
+
+        assert_eq!(
+            LdtkEntityRefIids::from_entity_instance(&EntityInstance::default()),
+            None
+        );
+    }
+
+    #[test]
+    fn refs_only_resolve_when_all_entities_are_indexed() {
+        let ref_iids = LdtkEntityRefIids::from_entity_instance(&entity_instance()).unwrap();
+        let mut iid_index = IidIndex::default();
+
+        let m = Entity::from_raw_u32(1).unwrap();
+        let a = Entity::from_raw_u32(2).unwrap();
+        let b = Entity::from_raw_u32(3).unwrap();
+
+        iid_index.insert_entity(EntityIid::new("m"), m);
+        iid_index.insert_entity(EntityIid::new("a"), a);
+        assert_eq!(ref_iids.resolve(&iid_index), None);
+
+        iid_index.insert_entity(EntityIid::new("b"), b);
+        let refs = ref_iids.resolve(&iid_index).unwrap();
+        assert_eq!(refs.get_single("mother"), Some(m));
+        assert_eq!(refs.get_single("friend"), None);
+        assert_eq!(refs.get("children"), &[a, b]);
+
+        iid_index.remove_entity(m);
+        assert_eq!(ref_iids.resolve(&iid_index), None);
+    }
+}
@@ -0,0 +1,189 @@
+use crate::{
+    ldtk::{LayerInstance, Type},
+    utils::merge_int_grid_cells,
+};
+use bevy::prelude::*;
+use std::collections::HashMap;
+
+#[allow(unused_imports)]
+use crate::resources::{IntGridRectMerging, LdtkSettings};
+
+/// [Component] storing the IntGrid cells of a level merged into rectangles, per IntGrid value.
+///
+/// Inserted on level entities when [LdtkSettings::int_grid_rect_merging] is
+/// [IntGridRectMerging::Enabled], and recalculated whenever the level spawns or respawns.
+///
+/// This is intended for spawning colliders without creating one for every single IntGrid cell,
+/// but doesn't depend on any physics library.
+/// The rectangles are in translation space relative to the level entity, and account for layer
+/// offsets.
+/// So, entities spawned as children of the level can use them directly.
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_ecs_ldtk::prelude::*;
+/// # #[derive(Component)]
+/// # struct Collider { size: Vec2 }
+/// fn spawn_wall_colliders(
+///     mut commands: Commands,
+///     level_query: Query<(Entity, &IntGridRects), Changed<IntGridRects>>,
+/// ) {
+///     for (level_entity, int_grid_rects) in &level_query {
+///         commands.entity(level_entity).with_children(|level| {
+///             for rect in int_grid_rects.iter_value(1) {
+///                 level.spawn((
+///                     Collider { size: rect.size() },
+///                     Transform::from_translation(rect.center().extend(0.)),
+///                 ));
+///             }
+///         });
+///     }
+/// }
+/// ```
+///
+/// If you need different groupings of IntGrid values, or to merge cells of a single layer, see
+/// [merge_int_grid_cells].
+#[derive(Clone, PartialEq, Debug, Default, Component, Reflect)]
+#[reflect(Component)]
+pub struct IntGridRects {
+    layers: HashMap<String, HashMap<i32, Vec<Rect>>>,
+}
+
+impl IntGridRects {
+    /// Merges the IntGrid cells of every IntGrid layer in the given list.
+    ///
+    /// Non-IntGrid layers are ignored.
+    pub fn from_layer_instances<'a>(
+        layer_instances: impl IntoIterator<Item = &'a LayerInstance>,
+    ) -> IntGridRects {
+        let layers = layer_instances
+            .into_iter()
+            .filter(|layer_instance| layer_instance.layer_instance_type == Type::IntGrid)
+            .map(|layer_instance| {
+                (
+                    layer_instance.identifier.clone(),
+                    layer_int_grid_rects(layer_instance),
+                )
+            })
+            .collect();
+
+        IntGridRects { layers }
+    }
+
+    /// Returns the merged rectangles of the given IntGrid value on the given layer.
+    pub fn get(&self, layer_identifier: &str, value: i32) -> &[Rect] {
+        self.layers
+            .get(layer_identifier)
+            .and_then(|values| values.get(&value))
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Iterates through the merged rectangles of the given IntGrid value on every layer.
+    pub fn iter_value(&self, value: i32) -> impl Iterator<Item = &Rect> {
+        self.layers
+            .values()
+            .filter_map(move |values| values.get(&value))
+            .flatten()
+    }
+
+    /// Iterates through all merged rectangles, along with their layer identifier and IntGrid value.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, i32, &Rect)> {
+        self.layers.iter().flat_map(|(layer_identifier, values)| {
+            values.iter().flat_map(move |(value, rects)| {
+                rects
+                    .iter()
+                    .map(move |rect| (layer_identifier.as_str(), *value, rect))
+            })
+        })
+    }
+}
+
+fn layer_int_grid_rects(layer_instance: &LayerInstance) -> HashMap<i32, Vec<Rect>> {
+    let grid_size = layer_instance.grid_size as f32;
+    let layer_offset = Vec2::new(
+        layer_instance.px_total_offset_x as f32,
+        -layer_instance.px_total_offset_y as f32,
+    );
+
+    merge_int_grid_cells(
+        &layer_instance.int_grid_csv,
+        layer_instance.c_wid,
+        layer_instance.c_hei,
+        Some,
+    )
+    .into_iter()
+    .map(|(value, grid_rects)| {
+        let rects = grid_rects
+            .into_iter()
+            .map(|grid_rect| {
+                Rect::from_corners(
+                    grid_rect.min.as_vec2() * grid_size + layer_offset,
+                    grid_rect.max.as_vec2() * grid_size + layer_offset,
+                )
+            })
+            .collect();
+
+        (value, rects)
+    })
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn int_grid_layer(identifier: &str, int_grid_csv: Vec<i32>) -> LayerInstance {
+        LayerInstance {
+            identifier: identifier.to_string(),
+            layer_instance_type: Type::IntGrid,
+            c_wid: 3,
+            c_hei: 2,
+            grid_size: 16,
+            int_grid_csv,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn rects_are_level_relative_translations() {
+        let mut layer = int_grid_layer("Walls", vec![1, 1, 0, 1, 1, 2]);
+        layer.px_total_offset_x = 4;
+        layer.px_total_offset_y = 8;
+
+        let int_grid_rects = IntGridRects::from_layer_instances([&layer]);
+
+        assert_eq!(
+            int_grid_rects.get("Walls", 1),
+            &[Rect::new(4., -8., 36., 24.)]
+        );
+        assert_eq!(
+            int_grid_rects.get("Walls", 2),
+            &[Rect::new(36., -8., 52., 8.)]
+        );
+        assert!(int_grid_rects.get("Walls", 3).is_empty());
+        assert!(int_grid_rects.get("Other", 1).is_empty());
+    }
+
+    #[test]
+    fn non_int_grid_layers_are_ignored() {
+        let walls = int_grid_layer("Walls", vec![1, 0, 0, 0, 0, 1]);
+        let water = int_grid_layer("Water", vec![0, 0, 1, 0, 0, 0]);
+        let entities = LayerInstance {
+            identifier: "Entities".to_string(),
+            layer_instance_type: Type::Entities,
+            ..Default::default()
+        };
+
+        let int_grid_rects = IntGridRects::from_layer_instances([&walls, &water, &entities]);
+
+        assert_eq!(int_grid_rects.iter_value(1).count(), 3);
+        assert_eq!(int_grid_rects.iter().count(), 3);
+        assert_eq!(
+            int_grid_rects
+                .iter()
+                .filter(|(layer_identifier, ..)| *layer_identifier == "Water")
+                .count(),
+            1
+        );
+    }
+}
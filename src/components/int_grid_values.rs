@@ -0,0 +1,197 @@
+use crate::{
+    components::GridCoords,
+    ldtk::LayerInstance,
+    utils::{grid_coords_to_ldtk_grid_coords, ldtk_grid_coords_to_grid_coords},
+};
+use bevy::prelude::*;
+
+#[allow(unused_imports)]
+use crate::{app::LdtkIntCell, components::IntGridCell, resources::IntGridStorage, LdtkIntGridMut};
+
+/// [Component] storing the current values of every cell in an IntGrid layer, including empty
+/// ones.
+///
+/// Inserted on the tilemap entity of every spawned IntGrid layer, alongside its
+/// [LayerMetadata](crate::components::LayerMetadata).
+/// If a layer is split into multiple tilemaps to stack its tiles, this is only inserted on the
+/// bottom one, which is also the one that has [IntGridCell] entities.
+///
+/// With [IntGridStorage::Dense], this is the only record of IntGrid cells that don't have an
+/// [LdtkIntCell] registered for them.
+///
+/// Unlike the `int_grid_csv` of the layer's [LayerInstance], this stays up to date when values
+/// are changed with [LdtkIntGridMut].
+#[derive(Clone, Eq, PartialEq, Debug, Default, Component, Reflect)]
+#[reflect(Component)]
+pub struct IntGridValues {
+    values: Vec<i32>,
+    c_wid: i32,
+    c_hei: i32,
+}
+
+impl From<&LayerInstance> for IntGridValues {
+    fn from(layer_instance: &LayerInstance) -> Self {
+        IntGridValues {
+            values: layer_instance.int_grid_csv.clone(),
+            c_wid: layer_instance.c_wid,
+            c_hei: layer_instance.c_hei,
+        }
+    }
+}
+
+impl IntGridValues {
+    /// Grid-based width of the layer.
+    pub fn c_wid(&self) -> i32 {
+        self.c_wid
+    }
+
+    /// Grid-based height of the layer.
+    pub fn c_hei(&self) -> i32 {
+        self.c_hei
+    }
+
+    /// Returns the values of every cell, in the same order as the `int_grid_csv` of a
+    /// [LayerInstance].
+    ///
+    /// So, rows are ordered from top to bottom.
+    pub fn values(&self) -> &[i32] {
+        &self.values
+    }
+
+    /// Returns the value at the given [GridCoords], or [None] if they are outside of the layer.
+    ///
+    /// Empty cells have a value of `0`.
+    pub fn get(&self, grid_coords: GridCoords) -> Option<i32> {
+        self.index(grid_coords)
+            .and_then(|index| self.values.get(index))
+            .copied()
+    }
+
+    /// Iterates through the [GridCoords] and values of every non-empty cell.
+    pub fn iter(&self) -> impl Iterator<Item = (GridCoords, i32)> + '_ {
+        self.values
+            .iter()
+            .enumerate()
+            .filter(|(_, value)| **value != 0)
+            .map(|(index, value)| (self.grid_coords(index), *value))
+    }
+
+    /// Iterates through the [GridCoords] of every cell with the given value.
+    pub fn iter_value(&self, value: i32) -> impl Iterator<Item = GridCoords> + '_ {
+        self.values
+            .iter()
+            .enumerate()
+            .filter(move |(_, v)| **v == value)
+            .map(|(index, _)| self.grid_coords(index))
+    }
+
+    /// Iterates through the [GridCoords] and values of the orthogonal neighbours of the given
+    /// cell that are within the layer.
+    pub fn neighbours(
+        &self,
+        grid_coords: GridCoords,
+    ) -> impl Iterator<Item = (GridCoords, i32)> + '_ {
+        [
+            GridCoords::new(0, 1),
+            GridCoords::new(1, 0),
+            GridCoords::new(0, -1),
+            GridCoords::new(-1, 0),
+        ]
+        .into_iter()
+        .filter_map(move |offset| {
+            let neighbour = grid_coords + offset;
+            self.get(neighbour).map(|value| (neighbour, value))
+        })
+    }
+
+    /// Sets the value at the given [GridCoords], returning the previous value, or [None] if they
+    /// are outside of the layer.
+    pub(crate) fn set(&mut self, grid_coords: GridCoords, value: i32) -> Option<i32> {
+        let index = self.index(grid_coords)?;
+        self.values
+            .get_mut(index)
+            .map(|old_value| std::mem::replace(old_value, value))
+    }
+
+    fn grid_coords(&self, index: usize) -> GridCoords {
+        let ldtk_grid_coords = IVec2::new(index as i32 % self.c_wid, index as i32 / self.c_wid);
+        ldtk_grid_coords_to_grid_coords(ldtk_grid_coords, self.c_hei)
+    }
+
+    fn index(&self, grid_coords: GridCoords) -> Option<usize> {
+        if grid_coords.x < 0
+            || grid_coords.y < 0
+            || grid_coords.x >= self.c_wid
+            || grid_coords.y >= self.c_hei
+        {
+            return None;
+        }
+
+        let ldtk_grid_coords = grid_coords_to_ldtk_grid_coords(grid_coords, self.c_hei);
+
+        Some((ldtk_grid_coords.y * self.c_wid + ldtk_grid_coords.x) as usize)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn int_grid_values() -> IntGridValues {
+        IntGridValues::from(&LayerInstance {
+            c_wid: 3,
+            c_hei: 2,
+            int_grid_csv: vec![1, 0, 2, 3, 4, 0],
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn values_are_accessed_with_flipped_y() {
+        let values = int_grid_values();
+
+        assert_eq!(values.get(GridCoords::new(0, 1)), Some(1));
+        assert_eq!(values.get(GridCoords::new(2, 1)), Some(2));
+        assert_eq!(values.get(GridCoords::new(1, 0)), Some(4));
+        assert_eq!(values.get(GridCoords::new(2, 0)), Some(0));
+        assert_eq!(values.get(GridCoords::new(3, 0)), None);
+        assert_eq!(values.get(GridCoords::new(0, -1)), None);
+
+        assert_eq!(
+            values.iter().collect::<Vec<_>>(),
+            vec![
+                (GridCoords::new(0, 1), 1),
+                (GridCoords::new(2, 1), 2),
+                (GridCoords::new(0, 0), 3),
+                (GridCoords::new(1, 0), 4),
+            ]
+        );
+    }
+
+    #[test]
+    fn cells_can_be_found_by_value_and_neighbours() {
+        let values = int_grid_values();
+
+        assert_eq!(
+            values.iter_value(0).collect::<Vec<_>>(),
+            vec![GridCoords::new(1, 1), GridCoords::new(2, 0)]
+        );
+
+        assert_eq!(
+            values.neighbours(GridCoords::new(0, 0)).collect::<Vec<_>>(),
+            vec![(GridCoords::new(0, 1), 1), (GridCoords::new(1, 0), 4)]
+        );
+        assert_eq!(values.neighbours(GridCoords::new(1, 1)).count(), 3);
+    }
+
+    #[test]
+    fn set_returns_previous_value() {
+        let mut values = int_grid_values();
+
+        assert_eq!(values.set(GridCoords::new(2, 0), 5), Some(0));
+        assert_eq!(values.set(GridCoords::new(2, 0), 6), Some(5));
+        assert_eq!(values.set(GridCoords::new(3, 0), 1), None);
+
+        assert_eq!(values.values(), &[1, 0, 2, 3, 4, 6]);
+    }
+}
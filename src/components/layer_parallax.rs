@@ -0,0 +1,152 @@
+use crate::ldtk::LayerDefinition;
+use bevy::prelude::*;
+
+#[allow(unused_imports)]
+use crate::components::LdtkWorldBundle;
+
+/// [`Component`] that sets the camera whose position drives the parallax of layers in this world.
+///
+/// Insert this on an entity with an [`LdtkWorldBundle`] to enable parallax for its levels.
+/// Layers whose definitions have a non-zero parallax factor are then offset, and optionally
+/// scaled, relative to the camera like they are in the LDtk editor.
+/// Without this component, all layers move rigidly with their level.
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_ecs_ldtk::prelude::*;
+/// fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+///     let camera = commands.spawn(Camera2d).id();
+///
+///     commands.spawn((
+///         LdtkWorldBundle {
+///             ldtk_handle: asset_server.load("my_project.ldtk").into(),
+///             ..Default::default()
+///         },
+///         ParallaxCamera(camera),
+///     ));
+/// }
+/// # bevy::ecs::system::assert_is_system(setup);
+/// ```
+///
+/// [`Component`]: https://docs.rs/bevy/latest/bevy/ecs/prelude/trait.Component.html
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Component, Reflect)]
+#[reflect(Component)]
+pub struct ParallaxCamera(pub Entity);
+
+/// [`Component`] added to layers whose definition has a non-zero parallax factor.
+///
+/// The plugin uses this to update the layer's [`Transform`] according to the [`ParallaxCamera`] of
+/// its world.
+/// Like in LDtk, a layer is aligned normally when the camera is centered on its level.
+/// As the camera moves away from the center, the layer is offset by the camera's distance from
+/// the center multiplied by the parallax factor.
+/// So, a factor of -1 makes the layer static relative to the camera, while a factor of 1 makes it
+/// scroll twice as fast as the level.
+///
+/// [`Component`]: https://docs.rs/bevy/latest/bevy/ecs/prelude/trait.Component.html
+#[derive(Copy, Clone, PartialEq, Debug, Default, Component, Reflect)]
+#[reflect(Component)]
+pub struct LayerParallax {
+    /// Parallax factors of the layer, from -1 to 1.
+    pub factor: Vec2,
+    /// Whether or not the layer is also scaled according to its parallax factor.
+    pub scaling: bool,
+    /// Translation of the layer relative to its level, without parallax.
+    pub base_translation: Vec3,
+    /// Center of the level, relative to the level.
+    pub level_center: Vec2,
+}
+
+impl LayerParallax {
+    /// Creates a [`LayerParallax`] for a layer with the given definition, returning [`None`] if it
+    /// doesn't have a parallax factor.
+    pub fn from_layer_definition(
+        layer_definition: &LayerDefinition,
+        base_translation: Vec3,
+        level_size: Vec2,
+    ) -> Option<LayerParallax> {
+        let factor = Vec2::new(
+            layer_definition.parallax_factor_x,
+            layer_definition.parallax_factor_y,
+        );
+
+        (factor != Vec2::ZERO).then_some(LayerParallax {
+            factor,
+            scaling: layer_definition.parallax_scaling,
+            base_translation,
+            level_center: level_size / 2.,
+        })
+    }
+
+    /// Calculates the layer's [`Transform`] relative to its level, given the translation of the
+    /// camera relative to the level.
+    pub fn transform(&self, camera_translation: Vec2) -> Transform {
+        let scale = if self.scaling {
+            Vec2::ONE + self.factor
+        } else {
+            Vec2::ONE
+        };
+
+        let camera_offset = camera_translation - self.level_center;
+        let translation = self.level_center
+            + (self.base_translation.truncate() - self.level_center) * scale
+            - camera_offset * self.factor;
+
+        Transform::from_translation(translation.extend(self.base_translation.z))
+            .with_scale(scale.extend(1.))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn layer_definition(parallax_factor_x: f32, parallax_factor_y: f32) -> LayerDefinition {
+        LayerDefinition {
+            parallax_factor_x,
+            parallax_factor_y,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn layers_without_parallax_factor_are_skipped() {
+        assert_eq!(
+            LayerParallax::from_layer_definition(
+                &layer_definition(0., 0.),
+                Vec3::ZERO,
+                Vec2::splat(100.)
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn parallax_offsets_layer_relative_to_level_center() {
+        let parallax = LayerParallax::from_layer_definition(
+            &layer_definition(-0.5, 0.5),
+            Vec3::new(0., 0., 2.),
+            Vec2::new(200., 100.),
+        )
+        .unwrap();
+
+        assert_eq!(
+            parallax.transform(Vec2::new(100., 50.)),
+            Transform::from_xyz(0., 0., 2.)
+        );
+
+        assert_eq!(
+            parallax.transform(Vec2::new(140., 30.)),
+            Transform::from_xyz(20., 10., 2.)
+        );
+
+        let scaled = LayerParallax {
+            scaling: true,
+            ..parallax
+        };
+
+        assert_eq!(
+            scaled.transform(Vec2::new(100., 50.)),
+            Transform::from_xyz(50., -25., 2.).with_scale(Vec3::new(0.5, 1.5, 1.))
+        );
+    }
+}
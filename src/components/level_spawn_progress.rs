@@ -0,0 +1,190 @@
+use bevy::prelude::*;
+
+use crate::ldtk::{LayerInstance, TileInstance, Type};
+
+#[allow(unused_imports)]
+use crate::resources::{LdtkSettings, LevelEvent, LevelSpawnBudget};
+
+/// [`Component`] storing how much of a level has spawned so far.
+///
+/// Inserted on level entities when [`LdtkSettings::level_spawn_budget`] is
+/// [`LevelSpawnBudget::PerFrame`], and reset whenever the level starts spawning or respawning.
+/// It remains on the level after the level has finished spawning.
+///
+/// Progress is measured in the tiles and entities of the level's layers, so it is suitable for
+/// displaying a loading bar.
+/// Big layers may spawn over multiple frames, a few rows of tiles or entities at a time.
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_ecs_ldtk::prelude::*;
+/// fn log_loading_progress(
+///     level_query: Query<(&LevelIid, &LevelSpawnProgress), Changed<LevelSpawnProgress>>,
+/// ) {
+///     for (level_iid, progress) in &level_query {
+///         info!("level {level_iid} is {:.0}% spawned", progress.fraction() * 100.);
+///     }
+/// }
+/// # bevy::ecs::system::assert_is_system(log_loading_progress);
+/// ```
+///
+/// [`Component`]: https://docs.rs/bevy/latest/bevy/ecs/prelude/trait.Component.html
+#[derive(Clone, PartialEq, Debug, Default, Component, Reflect)]
+#[reflect(Component)]
+pub struct LevelSpawnProgress {
+    spawned: usize,
+    total: usize,
+    background_spawned: bool,
+    next_layer: usize,
+    layer_z: i32,
+    #[reflect(ignore)]
+    layer_cursor: LayerSpawnCursor,
+}
+
+/// Where spawning left off within the layer currently being spawned.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub(crate) struct LayerSpawnCursor {
+    /// The layer's entities spawned so far, one per tilemap for tile layers.
+    pub(crate) layer_entities: Vec<Entity>,
+    /// The tiles of each of the layer's tilemaps, for tile layers.
+    pub(crate) grid_tiles: Vec<Vec<TileInstance>>,
+    /// The amount of entity instances, or rows of tiles, spawned so far.
+    pub(crate) next: usize,
+    /// Amount of tiles and entities of the layer spawned so far.
+    pub(crate) spawned: usize,
+}
+
+impl LayerSpawnCursor {
+    /// Returns true if none of the layer has been spawned yet.
+    pub(crate) fn is_unstarted(&self) -> bool {
+        self.layer_entities.is_empty()
+    }
+}
+
+impl LevelSpawnProgress {
+    pub(crate) fn new(total: usize) -> Self {
+        LevelSpawnProgress { total, ..default() }
+    }
+
+    /// Amount of tiles and entities spawned so far.
+    pub fn spawned(&self) -> usize {
+        self.spawned + self.layer_cursor.spawned
+    }
+
+    /// Total amount of tiles and entities in the level.
+    pub fn total(&self) -> usize {
+        self.total
+    }
+
+    /// Portion of the level spawned so far, from `0.` to `1.`.
+    pub fn fraction(&self) -> f32 {
+        if self.total == 0 {
+            1.
+        } else {
+            self.spawned() as f32 / self.total as f32
+        }
+    }
+
+    /// Returns true if the level has finished spawning.
+    pub fn is_finished(&self) -> bool {
+        self.spawned() >= self.total
+    }
+
+    pub(crate) fn background_spawned(&self) -> bool {
+        self.background_spawned
+    }
+
+    pub(crate) fn finish_background(&mut self, layer_z: i32) {
+        self.background_spawned = true;
+        self.layer_z = layer_z;
+    }
+
+    pub(crate) fn next_layer(&self) -> usize {
+        self.next_layer
+    }
+
+    pub(crate) fn layer_z(&self) -> i32 {
+        self.layer_z
+    }
+
+    pub(crate) fn layer_cursor_mut(&mut self) -> &mut LayerSpawnCursor {
+        &mut self.layer_cursor
+    }
+
+    pub(crate) fn finish_layer(&mut self, layer_instance: &LayerInstance, layer_z: i32) {
+        self.spawned += layer_spawn_cost(layer_instance);
+        self.next_layer += 1;
+        self.layer_z = layer_z;
+        self.layer_cursor = LayerSpawnCursor::default();
+    }
+}
+
+/// Approximate amount of tiles or entities spawned for the given layer.
+///
+/// Always at least 1, so that every layer contributes to a level's progress.
+pub(crate) fn layer_spawn_cost(layer_instance: &LayerInstance) -> usize {
+    let cost = match layer_instance.layer_instance_type {
+        Type::Entities => layer_instance.entity_instances.len(),
+        _ => (layer_instance.c_wid * layer_instance.c_hei).max(0) as usize,
+    };
+
+    cost.max(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ldtk::EntityInstance;
+
+    #[test]
+    fn progress_is_measured_in_layer_costs() {
+        let tiles = LayerInstance {
+            layer_instance_type: Type::Tiles,
+            c_wid: 4,
+            c_hei: 3,
+            ..Default::default()
+        };
+        let entities = LayerInstance {
+            layer_instance_type: Type::Entities,
+            entity_instances: vec![EntityInstance::default(); 3],
+            ..Default::default()
+        };
+        let empty_entities = LayerInstance {
+            layer_instance_type: Type::Entities,
+            ..Default::default()
+        };
+
+        let layers = [tiles, entities, empty_entities];
+        let mut progress = LevelSpawnProgress::new(layers.iter().map(layer_spawn_cost).sum());
+
+        assert_eq!(progress.total(), 16);
+        assert_eq!(progress.fraction(), 0.);
+        assert!(!progress.is_finished());
+
+        progress.finish_background(2);
+        progress.layer_cursor_mut().spawned = 8;
+
+        assert_eq!(progress.spawned(), 8);
+        assert!(!progress.is_finished());
+
+        progress.finish_layer(&layers[0], 3);
+
+        assert_eq!(progress.spawned(), 12);
+        assert_eq!(progress.next_layer(), 1);
+        assert_eq!(progress.layer_z(), 3);
+        assert_eq!(progress.fraction(), 0.75);
+
+        progress.finish_layer(&layers[1], 4);
+        progress.finish_layer(&layers[2], 5);
+
+        assert!(progress.is_finished());
+        assert_eq!(progress.fraction(), 1.);
+    }
+
+    #[test]
+    fn empty_levels_are_finished() {
+        let progress = LevelSpawnProgress::new(0);
+
+        assert!(progress.is_finished());
+        assert_eq!(progress.fraction(), 1.);
+    }
+}
@@ -0,0 +1,208 @@
+use bevy::prelude::*;
+
+use crate::{ldtk::Level, LevelIid, LevelSet};
+
+#[allow(unused_imports)]
+use crate::resources::{LevelSelection, LevelSpawnBehavior};
+
+/// [`Component`] that streams levels in and out of every [`LevelSet`] based on this entity's
+/// position.
+///
+/// Levels within [`LevelStreamingFocus::load_distance`] of the entity are added to the
+/// [`LevelSet`], and are only removed again once they are farther than
+/// [`LevelStreamingFocus::unload_distance`].
+/// Keeping the unload distance larger than the load distance prevents levels from rapidly
+/// spawning and despawning while the entity moves back and forth along the boundary.
+/// Distances are measured in pixels from the entity to the nearest edge of each level, so a level
+/// the entity is inside of is always at a distance of `0`.
+///
+/// Only levels with a matching [`LevelStreamingFocus::world_depth`] are streamed.
+/// Multiple focus entities are supported, such as one for each player in split-screen.
+/// The resulting level set is the union of the levels streamed by every focus.
+///
+/// Level bounds are calculated from the levels' world coordinates, so this is intended to be used
+/// with [`LevelSpawnBehavior::UseWorldTranslation`].
+/// Like [`LevelSet`] itself, this should not be used while a [`LevelSelection`] resource exists.
+///
+/// For more explanation and comparison of options for selecting levels to spawn, see the
+/// [*Level Selection*](https://trouv.github.io/bevy_ecs_ldtk/v0.15.0/explanation/level-selection.html) <!-- x-release-please-version -->
+/// chapter of the `bevy_ecs_ldtk` book.
+///
+/// [`Component`]: https://docs.rs/bevy/latest/bevy/ecs/prelude/trait.Component.html
+#[derive(Copy, Clone, PartialEq, Debug, Component, Reflect)]
+#[reflect(Component)]
+pub struct LevelStreamingFocus {
+    /// Distance, in pixels, within which levels will be spawned.
+    pub load_distance: f32,
+    /// Distance, in pixels, beyond which spawned levels will be despawned.
+    ///
+    /// If this is smaller than [`LevelStreamingFocus::load_distance`], the load distance is used
+    /// instead.
+    pub unload_distance: f32,
+    /// The `world_depth` of the levels to stream.
+    pub world_depth: i32,
+}
+
+impl LevelStreamingFocus {
+    /// Construct a new [`LevelStreamingFocus`] streaming levels at a `world_depth` of `0`.
+    pub fn new(load_distance: f32, unload_distance: f32) -> Self {
+        LevelStreamingFocus {
+            load_distance,
+            unload_distance,
+            world_depth: 0,
+        }
+    }
+
+    /// Returns this [`LevelStreamingFocus`] with the given `world_depth`.
+    pub fn with_world_depth(self, world_depth: i32) -> Self {
+        LevelStreamingFocus {
+            world_depth,
+            ..self
+        }
+    }
+}
+
+/// Returns the bounds of the level relative to the world entity, assuming
+/// [`LevelSpawnBehavior::UseWorldTranslation`].
+fn level_world_rect(level: &Level) -> Rect {
+    Rect::new(
+        level.world_x as f32,
+        -(level.world_y + level.px_hei) as f32,
+        (level.world_x + level.px_wid) as f32,
+        -level.world_y as f32,
+    )
+}
+
+fn distance_to_rect(rect: Rect, point: Vec2) -> f32 {
+    (point.clamp(rect.min, rect.max) - point).length()
+}
+
+/// Calculates the [`LevelSet`] desired by the given foci, and their positions relative to the
+/// world entity.
+///
+/// Levels in the `current` set are kept as long as they are within the unload distance of a focus.
+pub(crate) fn streamed_level_set<'a>(
+    levels: impl IntoIterator<Item = &'a Level>,
+    current: &LevelSet,
+    foci: &[(LevelStreamingFocus, Vec2)],
+) -> LevelSet {
+    levels
+        .into_iter()
+        .filter(|level| {
+            let level_iid = LevelIid::new(level.iid.clone());
+            let rect = level_world_rect(level);
+
+            foci.iter()
+                .filter(|(focus, _)| focus.world_depth == level.world_depth)
+                .any(|(focus, position)| {
+                    let distance = distance_to_rect(rect, *position);
+
+                    distance <= focus.load_distance
+                        || (current.iids.contains(&level_iid)
+                            && distance <= focus.unload_distance.max(focus.load_distance))
+                })
+        })
+        .map(|level| LevelIid::new(level.iid.clone()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn level(iid: &str, world_x: i32, world_y: i32, world_depth: i32) -> Level {
+        Level {
+            iid: iid.to_string(),
+            world_x,
+            world_y,
+            world_depth,
+            px_wid: 100,
+            px_hei: 100,
+            ..Default::default()
+        }
+    }
+
+    fn levels() -> Vec<Level> {
+        vec![
+            level("a", 0, 0, 0),
+            level("b", 100, 0, 0),
+            level("c", 300, 0, 0),
+            level("d", 0, 0, 1),
+        ]
+    }
+
+    #[test]
+    fn levels_within_load_distance_are_streamed_in() {
+        let levels = levels();
+        let focus = LevelStreamingFocus::new(50., 150.);
+
+        assert_eq!(
+            streamed_level_set(
+                &levels,
+                &LevelSet::default(),
+                &[(focus, Vec2::new(50., -50.))]
+            ),
+            LevelSet::from_iids(["a", "b"])
+        );
+        assert_eq!(
+            streamed_level_set(
+                &levels,
+                &LevelSet::default(),
+                &[(focus, Vec2::new(250., -50.))]
+            ),
+            LevelSet::from_iids(["b", "c"])
+        );
+        assert_eq!(
+            streamed_level_set(
+                &levels,
+                &LevelSet::default(),
+                &[(focus, Vec2::new(50., 100.))]
+            ),
+            LevelSet::default()
+        );
+    }
+
+    #[test]
+    fn levels_are_kept_until_unload_distance() {
+        let levels = levels();
+        let focus = LevelStreamingFocus::new(50., 150.);
+        let current = LevelSet::from_iids(["a", "b"]);
+
+        assert_eq!(
+            streamed_level_set(&levels, &current, &[(focus, Vec2::new(300., -50.))]),
+            LevelSet::from_iids(["b", "c"])
+        );
+        assert_eq!(
+            streamed_level_set(&levels, &current, &[(focus, Vec2::new(240., -50.))]),
+            LevelSet::from_iids(["a", "b"])
+        );
+
+        let focus_without_hysteresis = LevelStreamingFocus::new(50., 0.);
+        assert_eq!(
+            streamed_level_set(
+                &levels,
+                &current,
+                &[(focus_without_hysteresis, Vec2::new(240., -50.))]
+            ),
+            LevelSet::from_iids(["b"])
+        );
+    }
+
+    #[test]
+    fn multiple_foci_and_world_depths() {
+        let levels = levels();
+        let foci = [
+            (LevelStreamingFocus::new(0., 0.), Vec2::new(50., -50.)),
+            (LevelStreamingFocus::new(0., 0.), Vec2::new(350., -50.)),
+            (
+                LevelStreamingFocus::new(0., 0.).with_world_depth(1),
+                Vec2::new(50., -50.),
+            ),
+        ];
+
+        assert_eq!(
+            streamed_level_set(&levels, &LevelSet::default(), &foci),
+            LevelSet::from_iids(["a", "c", "d"])
+        );
+    }
+}
@@ -8,6 +8,29 @@ pub use level_iid::LevelIid;
 mod level_set;
 pub use level_set::LevelSet;
 
+mod level_streaming_focus;
+pub(crate) use level_streaming_focus::streamed_level_set;
+pub use level_streaming_focus::LevelStreamingFocus;
+
+mod level_spawn_progress;
+pub use level_spawn_progress::LevelSpawnProgress;
+pub(crate) use level_spawn_progress::{layer_spawn_cost, LayerSpawnCursor};
+
+mod entity_refs;
+pub use entity_refs::{LdtkEntityRefIids, LdtkEntityRefs};
+
+mod auto_layer_tiles;
+pub use auto_layer_tiles::AutoLayerTiles;
+
+mod int_grid_rects;
+pub use int_grid_rects::IntGridRects;
+
+mod int_grid_values;
+pub use int_grid_values::IntGridValues;
+
+mod layer_parallax;
+pub use layer_parallax::{LayerParallax, ParallaxCamera};
+
 pub use crate::ldtk::EntityInstance;
 use crate::{
     ldtk::{LayerInstance, Type},
@@ -17,7 +40,10 @@ use crate::{
 use bevy::asset::UntypedAssetId;
 use bevy::prelude::*;
 
-use std::ops::{Add, AddAssign, Mul, MulAssign, Sub, SubAssign};
+use std::{
+    collections::{HashMap, HashSet},
+    ops::{Add, AddAssign, Mul, MulAssign, Range, Sub, SubAssign},
+};
 
 #[allow(unused_imports)]
 use crate::{
@@ -188,6 +214,9 @@ impl GridCoords {
 /// definition.
 ///
 /// Automatically inserted on any tiles with metadata.
+///
+/// To parse this data into your own types, see
+/// [LdtkTileAppExt::register_tile_metadata](crate::app::LdtkTileAppExt::register_tile_metadata).
 #[derive(Clone, Eq, PartialEq, Debug, Default, Hash, Component, Reflect)]
 #[reflect(Component)]
 pub struct TileMetadata {
@@ -309,6 +338,84 @@ impl From<&LayerInstance> for LayerMetadata {
 #[reflect(Component)]
 pub struct Respawn;
 
+/// [Component] that indicates that an LDtk entity should be patched in place when its level is
+/// hot reloaded, rather than respawned.
+///
+/// This only applies with [`HotReloadBehavior::RespawnChangedLevels`].
+/// When a level containing this entity changes, the entity isn't despawned.
+/// Instead, if the level still contains an entity instance with the same iid, the entity's
+/// [`LdtkEntity`] bundle is evaluated and inserted again, along with its [`Transform`] and
+/// [`EntityInstance`].
+/// So, components derived from LDtk are updated, while components added at runtime are
+/// preserved.
+/// If the entity instance was removed from the level, the entity is despawned.
+///
+/// Opt in to this behavior by adding it to your [`LdtkEntity`] bundle.
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_ecs_ldtk::prelude::*;
+/// # #[derive(Component, Default)]
+/// # struct Enemy;
+/// #[derive(Bundle, LdtkEntity, Default)]
+/// pub struct EnemyBundle {
+///     enemy: Enemy,
+///     #[sprite_sheet]
+///     sprite_sheet: Sprite,
+///     #[from_entity_instance]
+///     entity_instance: EntityInstance,
+///     patch_on_hot_reload: PatchOnHotReload,
+/// }
+/// ```
+///
+/// [`HotReloadBehavior::RespawnChangedLevels`]: crate::resources::HotReloadBehavior::RespawnChangedLevels
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default, Hash, Component, Reflect)]
+#[reflect(Component)]
+pub struct PatchOnHotReload;
+
+/// [Component] inserted alongside [Respawn] on levels that are respawned due to hot reloading.
+///
+/// Indicates that entities with [PatchOnHotReload] should be preserved for patching.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default, Hash, Component)]
+pub(crate) struct HotReloadRespawn;
+
+/// [Component] inserted on levels whose changes due to hot reloading are limited to some of their
+/// layers, so that only those layers are respawned.
+///
+/// This only applies with [`HotReloadBehavior::RespawnChangedLevels`].
+/// It is removed once the layers have respawned.
+/// Entities with [PatchOnHotReload] in those layers are patched in place.
+///
+/// [`HotReloadBehavior::RespawnChangedLevels`]: crate::resources::HotReloadBehavior::RespawnChangedLevels
+#[derive(Clone, Eq, PartialEq, Debug, Component, Reflect)]
+#[reflect(Component)]
+pub struct RespawnLayers {
+    layer_iids: HashSet<String>,
+    layer_z: HashMap<String, Range<i32>>,
+}
+
+impl RespawnLayers {
+    pub(crate) fn new(layer_iids: HashSet<String>) -> Self {
+        RespawnLayers {
+            layer_iids,
+            layer_z: HashMap::new(),
+        }
+    }
+
+    /// Iids of the layers to respawn.
+    pub fn layer_iids(&self) -> &HashSet<String> {
+        &self.layer_iids
+    }
+
+    /// The z values spanned by the entities of the given layer before it was despawned.
+    pub(crate) fn layer_z(&self, layer_iid: &str) -> Option<&Range<i32>> {
+        self.layer_z.get(layer_iid)
+    }
+
+    pub(crate) fn set_layer_z(&mut self, layer_z: HashMap<String, Range<i32>>) {
+        self.layer_z = layer_z;
+    }
+}
+
 #[derive(Clone, Debug, Default, Bundle)]
 pub(crate) struct TileGridBundle {
     pub tile_bundle: TileBundle,
@@ -0,0 +1,859 @@
+//! Contains [`LdtkIntGridQuery`] and [`LdtkIntGridMut`] for looking up and changing the IntGrid
+//! values of spawned levels.
+use crate::{
+    app::{LdtkIntCellIdentifierMap, LdtkIntCellMap, PhantomLdtkIntCell, PhantomLdtkIntCellTrait},
+    assets::{LdtkProject, LdtkProjectData},
+    components::{
+        GridCoords, IntGridCell, IntGridCellBundle, IntGridValues, LayerMetadata,
+        LdtkProjectHandle, LevelIid, TileEnumTags, TileMetadata,
+    },
+    resources::{IntGridChanged, IntGridRendering, IntGridStorage, LdtkSettings},
+    utils::{
+        grid_coords_to_translation_relative_to_tile_layer, int_cell_registered,
+        ldtk_int_cell_map_get,
+    },
+};
+use bevy::{ecs::system::SystemParam, prelude::*};
+use bevy_ecs_tilemap::{
+    map::TilemapId,
+    tiles::{TileBundle, TileColor, TileFlip, TilePos, TileStorage, TileTextureIndex, TileVisible},
+};
+
+#[cfg(feature = "external_levels")]
+use crate::assets::LdtkExternalLevel;
+
+#[allow(unused_imports)]
+use crate::{ldtk::LayerInstance, resources::LevelSpawnBehavior};
+
+/// [`SystemParam`] for looking up the IntGrid values of spawned levels.
+///
+/// Values are read from the [`IntGridValues`] of the spawned layers, so no [`IntGridCell`]
+/// entities are required, and values changed with [`LdtkIntGridMut`] are accounted for.
+/// This makes it a cheaper alternative to iterating through `IntGridCell` entities for gameplay
+/// checks like "is the tile under the cursor a wall?".
+///
+/// Translations are converted to the level's space using its [`GlobalTransform`], so results
+/// account for [`LevelSpawnBehavior::UseWorldTranslation`] and any transform applied to the world.
+/// Layer offsets are accounted for as well.
+///
+/// Lookups return [`None`] if the level isn't spawned, it has no IntGrid layer with the given
+/// identifier, or the position is outside of the layer.
+/// Empty cells within the layer have a value of `0`.
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_ecs_ldtk::prelude::*;
+/// # #[derive(Component)]
+/// # struct Player;
+/// fn detect_water(player_query: Query<&GlobalTransform, With<Player>>, int_grid: LdtkIntGridQuery) {
+///     for player_transform in &player_query {
+///         if int_grid.get_at_translation("Terrain", player_transform.translation().truncate())
+///             == Some(2)
+///         {
+///             info!("splash!");
+///         }
+///     }
+/// }
+/// # bevy::ecs::system::assert_is_system(detect_water);
+/// ```
+#[derive(SystemParam)]
+pub struct LdtkIntGridQuery<'w, 's> {
+    level_query: Query<
+        'w,
+        's,
+        (
+            &'static LevelIid,
+            &'static GlobalTransform,
+            &'static Children,
+        ),
+    >,
+    layer_query: Query<'w, 's, (&'static LayerMetadata, &'static IntGridValues)>,
+}
+
+impl LdtkIntGridQuery<'_, '_> {
+    /// Returns the IntGrid value at the given [`GridCoords`] of a spawned level's layer.
+    pub fn get_at_grid_coords(
+        &self,
+        level_iid: &LevelIid,
+        layer_identifier: &str,
+        grid_coords: GridCoords,
+    ) -> Option<i32> {
+        self.level_query
+            .iter()
+            .filter(|(iid, ..)| *iid == level_iid)
+            .find_map(|(_, _, children)| {
+                let (_, int_grid_values) = self.find_int_grid_layer(children, layer_identifier)?;
+                int_grid_values.get(grid_coords)
+            })
+    }
+
+    /// Returns the IntGrid value at the given global translation.
+    ///
+    /// If multiple spawned levels have an IntGrid layer with the given identifier at this
+    /// translation, the value of the first one found is returned.
+    pub fn get_at_translation(&self, layer_identifier: &str, translation: Vec2) -> Option<i32> {
+        self.level_query
+            .iter()
+            .find_map(|(_, level_transform, children)| {
+                let (layer_metadata, int_grid_values) =
+                    self.find_int_grid_layer(children, layer_identifier)?;
+
+                let level_translation = level_transform
+                    .affine()
+                    .inverse()
+                    .transform_point3(translation.extend(0.))
+                    .truncate();
+
+                int_grid_value_at_level_translation(
+                    layer_metadata,
+                    int_grid_values,
+                    level_translation,
+                )
+            })
+    }
+
+    fn find_int_grid_layer(
+        &self,
+        children: &Children,
+        layer_identifier: &str,
+    ) -> Option<(&LayerMetadata, &IntGridValues)> {
+        self.layer_query
+            .iter_many(children)
+            .find(|(layer_metadata, _)| layer_metadata.identifier == layer_identifier)
+    }
+}
+
+/// [`SystemParam`] for changing the IntGrid values of spawned levels.
+///
+/// Changing a value updates the layer's [`IntGridValues`], and the cell's entity accordingly:
+/// - the cell's entity is given an [`IntGridCell`] with the new value, and the [`LdtkIntCell`]
+///   bundle registered for it,
+/// - cells that become empty have their entity despawned,
+/// - cells that stop being empty have an entity spawned for them,
+/// - with [`IntGridStorage::Dense`], cells only have an entity if an [`LdtkIntCell`] is
+///   registered for their value,
+/// - the cell's color is updated if the layer is rendered with [`IntGridRendering::Colorful`].
+///
+/// If the new value's registered [`LdtkIntCell`] differs from the previous value's, the cell's
+/// entity is despawned and respawned with just its tile components, so that it only has the new
+/// bundle's components.
+///
+/// An [`IntGridChanged`] message is written for every value that actually changes.
+/// These are also what the plugin uses to update auto-layers with
+/// [`AutoTileUpdates::OnIntGridChange`].
+///
+/// Note that the level's [`IntGridRects`] and the `int_grid_csv` of its [`LayerInstance`]s are not
+/// updated.
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_ecs_ldtk::prelude::*;
+/// fn dig(mut int_grid: LdtkIntGridMut, level_query: Query<&LevelIid>) {
+///     for level_iid in &level_query {
+///         int_grid.set(level_iid, "Terrain", GridCoords::new(3, 4), 0);
+///     }
+/// }
+/// # bevy::ecs::system::assert_is_system(dig);
+/// ```
+///
+/// [`LdtkIntCell`]: crate::app::LdtkIntCell
+/// [`AutoTileUpdates::OnIntGridChange`]: crate::resources::AutoTileUpdates::OnIntGridChange
+/// [`IntGridRects`]: crate::components::IntGridRects
+#[allow(clippy::type_complexity)]
+#[derive(SystemParam)]
+pub struct LdtkIntGridMut<'w, 's> {
+    commands: Commands<'w, 's>,
+    ldtk_int_cell_map: NonSend<'w, LdtkIntCellMap>,
+    ldtk_int_cell_identifier_map: NonSend<'w, LdtkIntCellIdentifierMap>,
+    ldtk_settings: Res<'w, LdtkSettings>,
+    ldtk_project_assets: Res<'w, Assets<LdtkProject>>,
+    #[cfg(feature = "external_levels")]
+    level_assets: Res<'w, Assets<LdtkExternalLevel>>,
+    ldtk_world_query: Query<'w, 's, &'static LdtkProjectHandle>,
+    level_query: Query<'w, 's, (&'static LevelIid, &'static ChildOf, &'static Children)>,
+    layer_query: Query<
+        'w,
+        's,
+        (
+            &'static LayerMetadata,
+            &'static mut IntGridValues,
+            &'static mut TileStorage,
+        ),
+    >,
+    tile_query: Query<
+        'w,
+        's,
+        (
+            &'static TileTextureIndex,
+            &'static TileFlip,
+            &'static TileColor,
+            &'static TileVisible,
+            Option<&'static TileMetadata>,
+            Option<&'static TileEnumTags>,
+        ),
+    >,
+    int_grid_changed: MessageWriter<'w, IntGridChanged>,
+}
+
+impl LdtkIntGridMut<'_, '_> {
+    /// Sets the IntGrid value at the given [`GridCoords`] of a spawned level's layer.
+    ///
+    /// Returns the previous value, or [`None`] if the level isn't spawned, it has no IntGrid layer
+    /// with the given identifier, or the coordinates are outside of the layer.
+    pub fn set(
+        &mut self,
+        level_iid: &LevelIid,
+        layer_identifier: &str,
+        grid_coords: GridCoords,
+        value: i32,
+    ) -> Option<i32> {
+        let (world_entity, layer_entity) = self
+            .level_query
+            .iter()
+            .filter(|(iid, ..)| *iid == level_iid)
+            .find_map(|(_, child_of, children)| {
+                let layer_entity = children.iter().find(|child| {
+                    self.layer_query
+                        .get(*child)
+                        .is_ok_and(|(layer_metadata, ..)| {
+                            layer_metadata.identifier == layer_identifier
+                        })
+                })?;
+
+                Some((child_of.parent(), layer_entity))
+            })?;
+
+        let (layer_metadata, mut int_grid_values, mut storage) =
+            self.layer_query.get_mut(layer_entity).ok()?;
+
+        let old_value = int_grid_values.set(grid_coords, value)?;
+        if old_value == value {
+            return Some(old_value);
+        }
+
+        let project = self
+            .ldtk_world_query
+            .get(world_entity)
+            .ok()
+            .and_then(|handle| self.ldtk_project_assets.get(handle));
+
+        let level = project.and_then(|project| match project.data() {
+            #[cfg(feature = "internal_levels")]
+            LdtkProjectData::Standalone(project) => {
+                project.get_loaded_level_by_iid(level_iid.get())
+            }
+            #[cfg(feature = "external_levels")]
+            LdtkProjectData::Parent(project) => {
+                project.get_external_level_by_iid(&self.level_assets, level_iid.get())
+            }
+        });
+        let layer_instance = level.as_ref().and_then(|level| {
+            level
+                .layer_instances()
+                .iter()
+                .find(|layer_instance| layer_instance.iid == layer_metadata.iid)
+        });
+
+        let layer_definition = project.and_then(|project| {
+            project
+                .json_data()
+                .defs
+                .layers
+                .iter()
+                .find(|layer_definition| layer_definition.uid == layer_metadata.layer_def_uid)
+        });
+
+        // Colorful IntGrid layers have a tile for every non-empty cell, colored by its value
+        let colorful_color = (layer_metadata.tileset_def_uid.is_none()
+            && self.ldtk_settings.int_grid_rendering == IntGridRendering::Colorful)
+            .then(|| {
+                layer_definition
+                    .and_then(|layer_definition| {
+                        layer_definition
+                            .int_grid_values
+                            .iter()
+                            .find(|value_definition| value_definition.value == value)
+                    })
+                    .map(|value_definition| {
+                        let color = value_definition.color;
+                        color.with_alpha(color.alpha() * layer_metadata.opacity)
+                    })
+                    .unwrap_or(Color::NONE)
+            });
+
+        let tile_pos = TilePos::from(grid_coords);
+        let tile_entity = storage.checked_get(&tile_pos);
+
+        let registration = |value| {
+            ldtk_int_cell_map_get(
+                layer_identifier,
+                value,
+                layer_definition,
+                &self.ldtk_int_cell_map,
+                &self.ldtk_int_cell_identifier_map,
+            )
+        };
+
+        // Values without a registration share the default IntGridCellBundle
+        let bundle_changed = old_value != 0
+            && match (registration(old_value), registration(value)) {
+                (Some(old), Some(new)) => !std::ptr::eq(old, new),
+                (old, new) => old.is_some() != new.is_some(),
+            };
+
+        let is_cell_entity = value != 0
+            && (self.ldtk_settings.int_grid_storage == IntGridStorage::CellEntities
+                || int_cell_registered(
+                    &self.ldtk_int_cell_map,
+                    &self.ldtk_int_cell_identifier_map,
+                    layer_identifier,
+                    layer_definition,
+                    value,
+                ));
+
+        if !is_cell_entity {
+            if let Some(tile_entity) = tile_entity {
+                self.commands.entity(tile_entity).despawn();
+                storage.remove(&tile_pos);
+
+                // Cells of layers with a tileset can also display a tile, which should be kept
+                if let Ok((texture_index, flip, color, visible, metadata, enum_tags)) =
+                    self.tile_query.get(tile_entity)
+                {
+                    if visible.0 && layer_metadata.tileset_def_uid.is_some() {
+                        let replacement = spawn_cell_tile(
+                            &mut self.commands,
+                            layer_entity,
+                            tile_pos,
+                            layer_metadata.grid_size,
+                            TileBundle {
+                                texture_index: *texture_index,
+                                flip: *flip,
+                                color: *color,
+                                ..default()
+                            },
+                        );
+
+                        let mut entity_commands = self.commands.entity(replacement);
+                        if let Some(metadata) = metadata {
+                            entity_commands.insert(metadata.clone());
+                        }
+                        if let Some(enum_tags) = enum_tags {
+                            entity_commands.insert(enum_tags.clone());
+                        }
+                        storage.set(&tile_pos, replacement);
+                    }
+                }
+            }
+
+            // Dense storage still needs a tile for the cell to be visible
+            if let Some(color) = colorful_color.filter(|_| value != 0) {
+                let tile_entity = spawn_cell_tile(
+                    &mut self.commands,
+                    layer_entity,
+                    tile_pos,
+                    layer_metadata.grid_size,
+                    TileBundle {
+                        color: TileColor(color),
+                        ..default()
+                    },
+                );
+                storage.set(&tile_pos, tile_entity);
+            }
+        } else {
+            let tile_entity = match tile_entity {
+                // The previous bundle's components can't be removed without knowing its type
+                Some(tile_entity) if bundle_changed => {
+                    let (tile_bundle, metadata, enum_tags) = self
+                        .tile_query
+                        .get(tile_entity)
+                        .map(
+                            |(texture_index, flip, color, visible, metadata, enum_tags)| {
+                                (
+                                    TileBundle {
+                                        texture_index: *texture_index,
+                                        flip: *flip,
+                                        color: *color,
+                                        visible: *visible,
+                                        ..default()
+                                    },
+                                    metadata.cloned(),
+                                    enum_tags.cloned(),
+                                )
+                            },
+                        )
+                        .unwrap_or_default();
+
+                    let replacement = spawn_cell_tile(
+                        &mut self.commands,
+                        layer_entity,
+                        tile_pos,
+                        layer_metadata.grid_size,
+                        TileBundle {
+                            color: colorful_color.map_or(tile_bundle.color, TileColor),
+                            ..tile_bundle
+                        },
+                    );
+
+                    let mut entity_commands = self.commands.entity(replacement);
+                    if let Some(metadata) = metadata {
+                        entity_commands.insert(metadata);
+                    }
+                    if let Some(enum_tags) = enum_tags {
+                        entity_commands.insert(enum_tags);
+                    }
+
+                    self.commands.entity(tile_entity).despawn();
+                    storage.set(&tile_pos, replacement);
+                    replacement
+                }
+                Some(tile_entity) => {
+                    if let Some(color) = colorful_color {
+                        self.commands.entity(tile_entity).insert(TileColor(color));
+                    }
+                    tile_entity
+                }
+                None => {
+                    let tile_entity = spawn_cell_tile(
+                        &mut self.commands,
+                        layer_entity,
+                        tile_pos,
+                        layer_metadata.grid_size,
+                        match colorful_color {
+                            Some(color) => TileBundle {
+                                color: TileColor(color),
+                                ..default()
+                            },
+                            None => TileBundle {
+                                visible: TileVisible(false),
+                                ..default()
+                            },
+                        },
+                    );
+                    storage.set(&tile_pos, tile_entity);
+                    tile_entity
+                }
+            };
+
+            let mut entity_commands = self.commands.entity(tile_entity);
+            entity_commands.insert(IntGridCell { value });
+
+            if let Some(layer_instance) = layer_instance {
+                let default_ldtk_int_cell: Box<dyn PhantomLdtkIntCellTrait> =
+                    Box::new(PhantomLdtkIntCell::<IntGridCellBundle>::new());
+
+                registration(value)
+                    .unwrap_or(&default_ldtk_int_cell)
+                    .evaluate(&mut entity_commands, IntGridCell { value }, layer_instance);
+            }
+
+            // like when spawning, the spatial components are inserted after the bundle
+            entity_commands.insert((
+                Transform::from_translation(
+                    grid_coords_to_translation_relative_to_tile_layer(
+                        grid_coords,
+                        IVec2::splat(layer_metadata.grid_size),
+                    )
+                    .extend(0.),
+                ),
+                ChildOf(layer_entity),
+            ));
+        }
+
+        self.int_grid_changed.write(IntGridChanged {
+            level_iid: level_iid.clone(),
+            layer_entity,
+            layer_identifier: layer_identifier.to_string(),
+            grid_coords,
+            old_value,
+            new_value: value,
+        });
+
+        Some(old_value)
+    }
+}
+
+/// Spawns a tile entity as a child of an IntGrid layer, without any IntGrid components.
+fn spawn_cell_tile(
+    commands: &mut Commands,
+    layer_entity: Entity,
+    tile_pos: TilePos,
+    grid_size: i32,
+    tile_bundle: TileBundle,
+) -> Entity {
+    commands
+        .spawn((
+            TileBundle {
+                position: tile_pos,
+                tilemap_id: TilemapId(layer_entity),
+                ..tile_bundle
+            },
+            GridCoords::from(tile_pos),
+            Transform::from_translation(
+                grid_coords_to_translation_relative_to_tile_layer(
+                    tile_pos.into(),
+                    IVec2::splat(grid_size),
+                )
+                .extend(0.),
+            ),
+            ChildOf(layer_entity),
+        ))
+        .id()
+}
+
+fn int_grid_value_at_level_translation(
+    layer_metadata: &LayerMetadata,
+    int_grid_values: &IntGridValues,
+    level_translation: Vec2,
+) -> Option<i32> {
+    let layer_offset = Vec2::new(
+        layer_metadata.px_total_offset_x as f32,
+        -layer_metadata.px_total_offset_y as f32,
+    );
+
+    // Flooring rather than truncating, so that positions just left of or below the layer aren't
+    // rounded into it.
+    let grid_coords = ((level_translation - layer_offset) / layer_metadata.grid_size as f32)
+        .floor()
+        .as_ivec2()
+        .into();
+
+    int_grid_values.get(grid_coords)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn int_grid_layer() -> (LayerMetadata, IntGridValues) {
+        let layer_instance = LayerInstance {
+            identifier: "Terrain".to_string(),
+            c_wid: 3,
+            c_hei: 2,
+            grid_size: 16,
+            int_grid_csv: vec![1, 0, 2, 3, 4, 0],
+            ..Default::default()
+        };
+
+        (
+            LayerMetadata::from(&layer_instance),
+            IntGridValues::from(&layer_instance),
+        )
+    }
+
+    fn set_system(
+        In((grid_coords, value)): In<(GridCoords, i32)>,
+        mut int_grid: LdtkIntGridMut,
+    ) -> Option<i32> {
+        int_grid.set(&LevelIid::new("level"), "Terrain", grid_coords, value)
+    }
+
+    /// Creates an app with a single spawned IntGrid layer, returning the layer entity.
+    fn int_grid_app(ldtk_settings: LdtkSettings) -> (App, Entity) {
+        let mut app = App::new();
+        app.insert_resource(ldtk_settings)
+            .init_resource::<Assets<LdtkProject>>()
+            .init_non_send::<LdtkIntCellMap>()
+            .init_non_send::<LdtkIntCellIdentifierMap>()
+            .add_message::<IntGridChanged>();
+
+        #[cfg(feature = "external_levels")]
+        app.init_resource::<Assets<LdtkExternalLevel>>();
+
+        let (layer_metadata, int_grid_values) = int_grid_layer();
+        let world_entity = app.world_mut().spawn_empty().id();
+        let level_entity = app
+            .world_mut()
+            .spawn((LevelIid::new("level"), ChildOf(world_entity)))
+            .id();
+        let layer_entity = app
+            .world_mut()
+            .spawn((
+                layer_metadata,
+                int_grid_values,
+                TileStorage::empty(bevy_ecs_tilemap::map::TilemapSize { x: 3, y: 2 }),
+                ChildOf(level_entity),
+            ))
+            .id();
+
+        (app, layer_entity)
+    }
+
+    #[test]
+    fn set_updates_values_and_cell_entities() {
+        let (mut app, layer_entity) = int_grid_app(LdtkSettings::default());
+
+        // empty to non-empty
+        assert_eq!(
+            app.world_mut()
+                .run_system_cached_with(set_system, (GridCoords::new(1, 1), 5))
+                .unwrap(),
+            Some(0)
+        );
+
+        let tile_pos = TilePos::new(1, 1);
+        let cell = app
+            .world()
+            .get::<TileStorage>(layer_entity)
+            .unwrap()
+            .get(&tile_pos)
+            .unwrap();
+        assert_eq!(
+            app.world().get::<IntGridCell>(cell),
+            Some(&IntGridCell { value: 5 })
+        );
+        assert_eq!(
+            app.world().get::<ChildOf>(cell).map(ChildOf::parent),
+            Some(layer_entity)
+        );
+
+        // non-empty to non-empty keeps the entity
+        app.world_mut()
+            .run_system_cached_with(set_system, (GridCoords::new(1, 1), 6))
+            .unwrap();
+        assert_eq!(
+            app.world().get::<IntGridCell>(cell),
+            Some(&IntGridCell { value: 6 })
+        );
+
+        // unchanged values and out of bounds coordinates don't write messages
+        app.world_mut()
+            .run_system_cached_with(set_system, (GridCoords::new(1, 1), 6))
+            .unwrap();
+        assert_eq!(
+            app.world_mut()
+                .run_system_cached_with(set_system, (GridCoords::new(3, 0), 1))
+                .unwrap(),
+            None
+        );
+
+        // non-empty to empty
+        app.world_mut()
+            .run_system_cached_with(set_system, (GridCoords::new(1, 1), 0))
+            .unwrap();
+        assert!(app.world().get_entity(cell).is_err());
+        assert_eq!(
+            app.world()
+                .get::<TileStorage>(layer_entity)
+                .unwrap()
+                .get(&tile_pos),
+            None
+        );
+        assert_eq!(
+            app.world()
+                .get::<IntGridValues>(layer_entity)
+                .unwrap()
+                .get(GridCoords::new(1, 1)),
+            Some(0)
+        );
+
+        let changes = app
+            .world_mut()
+            .resource_mut::<Messages<IntGridChanged>>()
+            .drain()
+            .map(|changed| (changed.old_value, changed.new_value))
+            .collect::<Vec<_>>();
+        assert_eq!(changes, vec![(0, 5), (5, 6), (6, 0)]);
+    }
+
+    #[cfg(feature = "internal_levels")]
+    #[test]
+    fn changing_registered_bundle_respawns_cell() {
+        use crate::{
+            app::LdtkIntCell,
+            assets::{LdtkJsonWithMetadata, LevelIndices, LevelMetadata},
+            ldtk::{LdtkJson, Level},
+        };
+        use std::collections::HashMap;
+
+        #[derive(Component, Default)]
+        struct Water;
+
+        #[derive(Bundle, Default)]
+        struct WaterBundle {
+            water: Water,
+            int_grid_cell: IntGridCell,
+        }
+
+        impl LdtkIntCell for WaterBundle {
+            fn bundle_int_cell(int_grid_cell: IntGridCell, _: &LayerInstance) -> Self {
+                WaterBundle {
+                    int_grid_cell,
+                    ..default()
+                }
+            }
+        }
+
+        let (mut app, layer_entity) = int_grid_app(LdtkSettings::default());
+
+        let layer_instance = LayerInstance {
+            identifier: "Terrain".to_string(),
+            iid: app
+                .world()
+                .get::<LayerMetadata>(layer_entity)
+                .unwrap()
+                .iid
+                .clone(),
+            ..Default::default()
+        };
+        let project = LdtkProject::new(
+            LdtkProjectData::Standalone(LdtkJsonWithMetadata::new(
+                LdtkJson {
+                    levels: vec![Level {
+                        iid: "level".to_string(),
+                        layer_instances: Some(vec![layer_instance]),
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                },
+                HashMap::from([(
+                    "level".to_string(),
+                    LevelMetadata::new(None, LevelIndices::in_root(0)),
+                )]),
+            )),
+            HashMap::new(),
+            None,
+            false,
+        );
+        let handle = app
+            .world_mut()
+            .resource_mut::<Assets<LdtkProject>>()
+            .add(project);
+        let world_entity = app
+            .world()
+            .get::<ChildOf>(app.world().get::<ChildOf>(layer_entity).unwrap().parent())
+            .unwrap()
+            .parent();
+        app.world_mut()
+            .entity_mut(world_entity)
+            .insert(LdtkProjectHandle::from(handle));
+
+        app.world_mut().non_send_mut::<LdtkIntCellMap>().insert(
+            (Some("Terrain".to_string()), Some(5)),
+            Box::new(PhantomLdtkIntCell::<WaterBundle>::new()),
+        );
+
+        let cell_at = |app: &App| {
+            app.world()
+                .get::<TileStorage>(layer_entity)
+                .unwrap()
+                .get(&TilePos::new(1, 1))
+                .unwrap()
+        };
+
+        app.world_mut()
+            .run_system_cached_with(set_system, (GridCoords::new(1, 1), 5))
+            .unwrap();
+        let water_cell = cell_at(&app);
+        app.world_mut().entity_mut(water_cell).insert(TileMetadata {
+            data: "wet".to_string(),
+        });
+        assert!(app.world().get::<Water>(water_cell).is_some());
+
+        // the default bundle replaces WaterBundle
+        app.world_mut()
+            .run_system_cached_with(set_system, (GridCoords::new(1, 1), 6))
+            .unwrap();
+        let cell = cell_at(&app);
+        assert_ne!(cell, water_cell);
+        assert!(app.world().get_entity(water_cell).is_err());
+        assert!(app.world().get::<Water>(cell).is_none());
+        assert_eq!(
+            app.world().get::<IntGridCell>(cell),
+            Some(&IntGridCell { value: 6 })
+        );
+        assert_eq!(
+            app.world()
+                .get::<TileMetadata>(cell)
+                .map(|m| m.data.as_str()),
+            Some("wet")
+        );
+        assert_eq!(
+            app.world().get::<ChildOf>(cell).map(ChildOf::parent),
+            Some(layer_entity)
+        );
+
+        // values sharing a registration keep the entity
+        app.world_mut()
+            .run_system_cached_with(set_system, (GridCoords::new(1, 1), 7))
+            .unwrap();
+        assert_eq!(cell_at(&app), cell);
+    }
+
+    #[test]
+    fn dense_storage_only_spawns_registered_cells() {
+        let (mut app, layer_entity) = int_grid_app(LdtkSettings {
+            int_grid_rendering: IntGridRendering::Invisible,
+            int_grid_storage: IntGridStorage::Dense,
+            ..default()
+        });
+
+        app.world_mut().non_send_mut::<LdtkIntCellMap>().insert(
+            (Some("Terrain".to_string()), Some(7)),
+            Box::new(PhantomLdtkIntCell::<IntGridCellBundle>::new()),
+        );
+
+        app.world_mut()
+            .run_system_cached_with(set_system, (GridCoords::new(1, 1), 5))
+            .unwrap();
+        app.world_mut()
+            .run_system_cached_with(set_system, (GridCoords::new(2, 0), 7))
+            .unwrap();
+
+        let storage = app.world().get::<TileStorage>(layer_entity).unwrap();
+        assert_eq!(storage.get(&TilePos::new(1, 1)), None);
+        let registered_cell = storage.get(&TilePos::new(2, 0)).unwrap();
+        assert_eq!(
+            app.world().get::<IntGridCell>(registered_cell),
+            Some(&IntGridCell { value: 7 })
+        );
+
+        assert_eq!(
+            app.world()
+                .get::<IntGridValues>(layer_entity)
+                .unwrap()
+                .get(GridCoords::new(1, 1)),
+            Some(5)
+        );
+    }
+
+    #[test]
+    fn translation_lookup_accounts_for_layer_offset() {
+        let (mut layer_metadata, int_grid_values) = int_grid_layer();
+
+        assert_eq!(
+            int_grid_value_at_level_translation(
+                &layer_metadata,
+                &int_grid_values,
+                Vec2::new(20., 8.)
+            ),
+            Some(4)
+        );
+        assert_eq!(
+            int_grid_value_at_level_translation(
+                &layer_metadata,
+                &int_grid_values,
+                Vec2::new(-0.5, 8.)
+            ),
+            None
+        );
+
+        layer_metadata.px_total_offset_x = 16;
+        layer_metadata.px_total_offset_y = -16;
+
+        assert_eq!(
+            int_grid_value_at_level_translation(
+                &layer_metadata,
+                &int_grid_values,
+                Vec2::new(20., 8.)
+            ),
+            None
+        );
+        assert_eq!(
+            int_grid_value_at_level_translation(
+                &layer_metadata,
+                &int_grid_values,
+                Vec2::new(20., 24.)
+            ),
+            Some(3)
+        );
+    }
+}
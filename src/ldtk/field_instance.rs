@@ -1,7 +1,10 @@
 use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 
 #[allow(unused_imports)]
-use super::{EntityInstance, GridPoint, Level, ReferenceToAnEntityInstance, TilesetRectangle};
+use super::{
+    EntityInstance, FieldDefinition, GridPoint, Level, ReferenceToAnEntityInstance,
+    TilesetRectangle,
+};
 use bevy::prelude::*;
 use regex::Regex;
 
@@ -45,6 +48,24 @@ pub struct FieldInstance {
     pub real_editor_values: Vec<Option<serde_json::Value>>,
 }
 
+impl FieldInstance {
+    /// Construct a [`FieldInstance`] of the given [`FieldDefinition`], typing the json `value`
+    /// according to the definition.
+    pub(crate) fn from_definition_value(
+        field_definition: &FieldDefinition,
+        value: serde_json::Value,
+    ) -> Result<FieldInstance, serde_json::Error> {
+        serde_json::from_value(serde_json::to_value(FieldInstanceHelper {
+            identifier: field_definition.identifier.clone(),
+            tile: None,
+            field_instance_type: field_definition.field_definition_type.clone(),
+            value,
+            def_uid: field_definition.uid,
+            real_editor_values: Vec::new(),
+        })?)
+    }
+}
+
 #[derive(Serialize, Deserialize)]
 struct FieldInstanceHelper {
     #[serde(rename = "__identifier")]
@@ -0,0 +1,448 @@
+//! Contains [`FieldInstancesDeserializer`], for deserializing field instances into typed structs.
+//!
+//! Most users will want to use [`LdtkFields::deserialize_fields`] rather than using this module
+//! directly.
+use crate::ldtk::{
+    ldtk_fields::LdtkFieldsError, FieldInstance, FieldValue, ReferenceToAnEntityInstance,
+    TilesetRectangle,
+};
+use bevy::prelude::*;
+use serde::{
+    de::{
+        self,
+        value::{BorrowedStrDeserializer, SeqDeserializer},
+        IntoDeserializer, Visitor,
+    },
+    forward_to_deserialize_any, Deserialize, Deserializer,
+};
+use std::fmt;
+
+#[allow(unused_imports)]
+use crate::ldtk::ldtk_fields::LdtkFields;
+
+impl de::Error for LdtkFieldsError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        LdtkFieldsError::Deserialize {
+            message: msg.to_string(),
+        }
+    }
+
+    fn missing_field(field: &'static str) -> Self {
+        LdtkFieldsError::FieldNotFound {
+            identifier: field.to_string(),
+        }
+    }
+}
+
+/// [`Deserializer`] over a list of [`FieldInstance`]s, presenting them as a map from field
+/// identifiers to field values.
+///
+/// Field values are presented to the deserialized type as follows:
+/// - `Int`, `Float`, and `Bool` fields as numbers and booleans.
+/// - `String`, `Multilines`, and `FilePath` fields as strings.
+/// - `Enum` fields as strings or unit enum variants, so they can be deserialized into Rust enums
+///   with matching variant names.
+/// - `Color` fields as hex strings, see [`deserialize_color`].
+/// - `Point` fields as `(x, y)` sequences, see [`deserialize_point`].
+/// - `Tile` and `EntityRef` fields in the same shape as [`TilesetRectangle`] and
+///   [`ReferenceToAnEntityInstance`].
+/// - Array fields as sequences of the above.
+///
+/// Null values can only be deserialized into [`Option`]s.
+///
+/// Errors are reported as [`LdtkFieldsError`]s, specifying the identifier of the field that
+/// failed to deserialize when possible.
+#[derive(Copy, Clone, Debug)]
+pub struct FieldInstancesDeserializer<'de> {
+    field_instances: &'de [FieldInstance],
+}
+
+impl<'de> FieldInstancesDeserializer<'de> {
+    /// Construct a new [`FieldInstancesDeserializer`] over the given field instances.
+    pub fn new(field_instances: &'de [FieldInstance]) -> Self {
+        FieldInstancesDeserializer { field_instances }
+    }
+}
+
+impl<'de> Deserializer<'de> for FieldInstancesDeserializer<'de> {
+    type Error = LdtkFieldsError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_map(FieldInstancesMapAccess {
+            field_instances: self.field_instances.iter(),
+            current: None,
+        })
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct enum identifier ignored_any
+    }
+}
+
+struct FieldInstancesMapAccess<'de> {
+    field_instances: std::slice::Iter<'de, FieldInstance>,
+    current: Option<&'de FieldInstance>,
+}
+
+impl<'de> de::MapAccess<'de> for FieldInstancesMapAccess<'de> {
+    type Error = LdtkFieldsError;
+
+    fn next_key_seed<K: de::DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Self::Error> {
+        let Some(field_instance) = self.field_instances.next() else {
+            return Ok(None);
+        };
+        self.current = Some(field_instance);
+
+        seed.deserialize(BorrowedStrDeserializer::new(&field_instance.identifier))
+            .map(Some)
+    }
+
+    fn next_value_seed<V: de::DeserializeSeed<'de>>(
+        &mut self,
+        seed: V,
+    ) -> Result<V::Value, Self::Error> {
+        let field_instance = self
+            .current
+            .take()
+            .expect("next_value_seed should only be called after next_key_seed");
+
+        seed.deserialize(Value::from(&field_instance.value))
+            .map_err(|e| e.into_fields_error(&field_instance.identifier))
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.field_instances.len())
+    }
+}
+
+/// Error produced when deserializing a single field value, before it is associated with the
+/// field's identifier.
+#[derive(Debug)]
+enum ValueError {
+    WrongType,
+    UnexpectedNull,
+    Custom(String),
+}
+
+impl ValueError {
+    fn into_fields_error(self, identifier: &str) -> LdtkFieldsError {
+        let identifier = identifier.to_string();
+        match self {
+            ValueError::WrongType => LdtkFieldsError::WrongFieldType { identifier },
+            ValueError::UnexpectedNull => LdtkFieldsError::UnexpectedNull { identifier },
+            ValueError::Custom(message) => LdtkFieldsError::InvalidFieldValue {
+                identifier,
+                message,
+            },
+        }
+    }
+}
+
+impl fmt::Display for ValueError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValueError::WrongType => write!(f, "wrong field type"),
+            ValueError::UnexpectedNull => write!(f, "unexpected null"),
+            ValueError::Custom(message) => write!(f, "{message}"),
+        }
+    }
+}
+
+impl std::error::Error for ValueError {}
+
+impl de::Error for ValueError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        ValueError::Custom(msg.to_string())
+    }
+
+    fn invalid_type(_: de::Unexpected, _: &dyn de::Expected) -> Self {
+        ValueError::WrongType
+    }
+}
+
+/// Borrowed, deserializer-friendly representation of a [`FieldValue`] or one of its elements.
+enum Value<'de> {
+    Null,
+    Int(i32),
+    Float(f32),
+    Bool(bool),
+    String(&'de str),
+    Color(Color),
+    Tile(&'de TilesetRectangle),
+    EntityRef(&'de ReferenceToAnEntityInstance),
+    Point(IVec2),
+    Seq(Vec<Value<'de>>),
+}
+
+fn nullable<'de, T>(value: &'de Option<T>, f: impl FnOnce(&'de T) -> Value<'de>) -> Value<'de> {
+    value.as_ref().map(f).unwrap_or(Value::Null)
+}
+
+fn seq<'de, T>(values: &'de [T], f: impl Fn(&'de T) -> Value<'de>) -> Value<'de> {
+    Value::Seq(values.iter().map(f).collect())
+}
+
+impl<'de> From<&'de FieldValue> for Value<'de> {
+    fn from(field_value: &'de FieldValue) -> Self {
+        match field_value {
+            FieldValue::Int(value) => nullable(value, |v| Value::Int(*v)),
+            FieldValue::Float(value) => nullable(value, |v| Value::Float(*v)),
+            FieldValue::Bool(value) => Value::Bool(*value),
+            FieldValue::String(value) | FieldValue::FilePath(value) | FieldValue::Enum(value) => {
+                nullable(value, |v| Value::String(v))
+            }
+            FieldValue::Color(value) => Value::Color(*value),
+            FieldValue::Tile(value) => nullable(value, Value::Tile),
+            FieldValue::EntityRef(value) => nullable(value, Value::EntityRef),
+            FieldValue::Point(value) => nullable(value, |v| Value::Point(*v)),
+            FieldValue::Ints(values) => seq(values, |v| nullable(v, |v| Value::Int(*v))),
+            FieldValue::Floats(values) => seq(values, |v| nullable(v, |v| Value::Float(*v))),
+            FieldValue::Bools(values) => seq(values, |v| Value::Bool(*v)),
+            FieldValue::Strings(values)
+            | FieldValue::FilePaths(values)
+            | FieldValue::Enums(values) => seq(values, |v| nullable(v, |v| Value::String(v))),
+            FieldValue::Colors(values) => seq(values, |v| Value::Color(*v)),
+            FieldValue::Tiles(values) => seq(values, |v| nullable(v, Value::Tile)),
+            FieldValue::EntityRefs(values) => seq(values, |v| nullable(v, Value::EntityRef)),
+            FieldValue::Points(values) => seq(values, |v| nullable(v, |v| Value::Point(*v))),
+        }
+    }
+}
+
+impl<'de> IntoDeserializer<'de, ValueError> for Value<'de> {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self::Deserializer {
+        self
+    }
+}
+
+impl<'de> Deserializer<'de> for Value<'de> {
+    type Error = ValueError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self {
+            Value::Null => Err(ValueError::UnexpectedNull),
+            Value::Int(value) => visitor.visit_i32(value),
+            Value::Float(value) => visitor.visit_f32(value),
+            Value::Bool(value) => visitor.visit_bool(value),
+            Value::String(value) => visitor.visit_borrowed_str(value),
+            Value::Color(value) => visitor.visit_string(value.to_srgba().to_hex()),
+            Value::Tile(value) => serde_json::to_value(value)
+                .and_then(|json| json.deserialize_any(visitor))
+                .map_err(de::Error::custom),
+            Value::EntityRef(value) => serde_json::to_value(value)
+                .and_then(|json| json.deserialize_any(visitor))
+                .map_err(de::Error::custom),
+            Value::Point(value) => {
+                SeqDeserializer::new([value.x, value.y].into_iter()).deserialize_any(visitor)
+            }
+            Value::Seq(values) => SeqDeserializer::new(values.into_iter()).deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self {
+            Value::Null => visitor.visit_none(),
+            value => visitor.visit_some(value),
+        }
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        match self {
+            Value::String(value) => {
+                visitor.visit_enum(BorrowedStrDeserializer::<ValueError>::new(value))
+            }
+            value => value.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple
+        tuple_struct map struct identifier ignored_any
+    }
+}
+
+/// Deserializes a [`Color`] from the hex string that `Color` fields are presented as.
+///
+/// Intended for use with serde's `deserialize_with` attribute, since [`Color`] doesn't implement
+/// [`Deserialize`] without bevy's `serialize` feature.
+/// ```
+/// use bevy::prelude::*;
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct Tint {
+///     #[serde(deserialize_with = "bevy_ecs_ldtk::ldtk::fields_deserializer::deserialize_color")]
+///     color: Color,
+/// }
+/// ```
+pub fn deserialize_color<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Color, D::Error> {
+    super::color::deserialize(deserializer)
+}
+
+/// Deserializes an [`IVec2`] from the `(x, y)` sequence that `Point` fields are presented as.
+///
+/// Intended for use with serde's `deserialize_with` attribute, since [`IVec2`] doesn't implement
+/// [`Deserialize`] without bevy's `serialize` feature.
+pub fn deserialize_point<'de, D: Deserializer<'de>>(deserializer: D) -> Result<IVec2, D::Error> {
+    let (x, y) = <(i32, i32)>::deserialize(deserializer)?;
+    Ok(IVec2::new(x, y))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ldtk::ldtk_fields::LdtkFields;
+
+    fn field_instance(identifier: &str, value: FieldValue) -> FieldInstance {
+        FieldInstance {
+            identifier: identifier.to_string(),
+            value,
+            field_instance_type: "".to_string(),
+            tile: None,
+            def_uid: 0,
+            real_editor_values: Vec::new(),
+        }
+    }
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    enum Item {
+        Sword,
+        Shield,
+    }
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    #[serde(rename_all = "PascalCase")]
+    struct Enemy {
+        health: i32,
+        speed: f32,
+        boss: bool,
+        name: String,
+        title: Option<String>,
+        drop: Item,
+        inventory: Vec<Option<Item>>,
+        #[serde(deserialize_with = "deserialize_color")]
+        color: Color,
+        #[serde(deserialize_with = "deserialize_point")]
+        spawn: IVec2,
+        tile: TilesetRectangle,
+        friend: Option<ReferenceToAnEntityInstance>,
+    }
+
+    fn enemy_entity() -> crate::ldtk::EntityInstance {
+        crate::ldtk::EntityInstance {
+            field_instances: vec![
+                field_instance("Health", FieldValue::Int(Some(10))),
+                field_instance("Speed", FieldValue::Float(Some(1.5))),
+                field_instance("Boss", FieldValue::Bool(true)),
+                field_instance("Name", FieldValue::String(Some("Gorb".to_string()))),
+                field_instance("Title", FieldValue::String(None)),
+                field_instance("Drop", FieldValue::Enum(Some("Shield".to_string()))),
+                field_instance(
+                    "Inventory",
+                    FieldValue::Enums(vec![Some("Sword".to_string()), None]),
+                ),
+                field_instance("Color", FieldValue::Color(Color::srgb(1., 0., 0.))),
+                field_instance("Spawn", FieldValue::Point(Some(IVec2::new(3, 4)))),
+                field_instance(
+                    "Tile",
+                    FieldValue::Tile(Some(TilesetRectangle {
+                        x: 16,
+                        y: 32,
+                        w: 16,
+                        h: 16,
+                        tileset_uid: 7,
+                    })),
+                ),
+                field_instance("Friend", FieldValue::EntityRef(None)),
+                field_instance("Unused", FieldValue::Int(Some(0))),
+            ],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn deserialize_fields_into_struct() {
+        let enemy: Enemy = enemy_entity().deserialize_fields().unwrap();
+
+        assert_eq!(
+            enemy,
+            Enemy {
+                health: 10,
+                speed: 1.5,
+                boss: true,
+                name: "Gorb".to_string(),
+                title: None,
+                drop: Item::Shield,
+                inventory: vec![Some(Item::Sword), None],
+                color: Color::srgb(1., 0., 0.),
+                spawn: IVec2::new(3, 4),
+                tile: TilesetRectangle {
+                    x: 16,
+                    y: 32,
+                    w: 16,
+                    h: 16,
+                    tileset_uid: 7,
+                },
+                friend: None,
+            }
+        );
+    }
+
+    #[test]
+    fn errors_report_failing_field() {
+        let mut entity = enemy_entity();
+        entity.field_instances.retain(|f| f.identifier != "Speed");
+        assert_eq!(
+            entity.deserialize_fields::<Enemy>(),
+            Err(LdtkFieldsError::FieldNotFound {
+                identifier: "Speed".to_string()
+            })
+        );
+
+        let mut entity = enemy_entity();
+        entity.field_instances[0].value = FieldValue::Int(None);
+        assert_eq!(
+            entity.deserialize_fields::<Enemy>(),
+            Err(LdtkFieldsError::UnexpectedNull {
+                identifier: "Health".to_string()
+            })
+        );
+
+        let mut entity = enemy_entity();
+        entity.field_instances[2].value = FieldValue::Int(Some(1));
+        assert_eq!(
+            entity.deserialize_fields::<Enemy>(),
+            Err(LdtkFieldsError::WrongFieldType {
+                identifier: "Boss".to_string()
+            })
+        );
+
+        let mut entity = enemy_entity();
+        entity.field_instances[5].value = FieldValue::Enum(Some("Bow".to_string()));
+        assert!(matches!(
+            entity.deserialize_fields::<Enemy>(),
+            Err(LdtkFieldsError::InvalidFieldValue { identifier, .. }) if identifier == "Drop"
+        ));
+    }
+}
@@ -0,0 +1,66 @@
+//! Contains [`LdtkEnum`] trait, implemented by Rust enums generated from LDtk enum definitions.
+use crate::ldtk::TilesetRectangle;
+use bevy::prelude::*;
+use std::str::FromStr;
+use thiserror::Error;
+
+#[allow(unused_imports)]
+use crate::ldtk::{ldtk_fields::LdtkFields, EnumDefinition, FieldValue};
+
+/// Error returned when parsing a string that isn't a value of an [`LdtkEnum`].
+#[derive(Clone, Debug, PartialEq, Eq, Error)]
+#[error("{value:?} is not a value of the {enum_identifier} enum")]
+pub struct ParseLdtkEnumError {
+    /// Identifier of the enum in LDtk.
+    pub enum_identifier: &'static str,
+    /// The string that failed to parse.
+    pub value: String,
+}
+
+/// Trait for Rust enums mirroring an [`EnumDefinition`] of an LDtk project.
+///
+/// Rather than implementing this manually, you can generate these enums with the `ldtk_enums!`
+/// macro, provided the `derive` feature is enabled.
+/// Given the path to an LDtk project relative to your crate's `Cargo.toml`, it generates one enum
+/// for every enum definition in the project.
+/// Each enum has a variant for every value in its definition with exactly the same name, and
+/// derives [`Copy`], [`Eq`], [`Hash`], [`Debug`], and [`Reflect`].
+/// They also implement [`FromStr`] and [`Display`](std::fmt::Display), so [`FieldValue::Enum`]
+/// values can easily be parsed into them.
+/// ```
+/// use bevy_ecs_ldtk::{ldtk::ldtk_enum::LdtkEnum, prelude::*};
+///
+/// // The field_instances.ldtk project defines an "Equipment" enum.
+/// bevy_ecs_ldtk::ldtk_enums!("assets/field_instances.ldtk");
+///
+/// fn equipment_drops(entity_instance: &EntityInstance) -> Vec<Equipment> {
+///     entity_instance
+///         .iter_enums_field("equipment_drops")
+///         .expect("expected entity to have non-nullable equipment_drops enums field")
+///         .map(|value| value.parse().expect("value should be valid Equipment"))
+///         .collect()
+/// }
+///
+/// assert_eq!("Sword".parse(), Ok(Equipment::Sword));
+/// assert_eq!(Equipment::IDENTIFIER, "Equipment");
+/// assert!(Equipment::Sword.tile_rect().is_some());
+/// ```
+///
+/// The project file is tracked by the compiler, so your crate is rebuilt whenever it changes.
+/// If an enum or value your code depends on is renamed or removed in LDtk, compilation fails.
+pub trait LdtkEnum: Copy + FromStr<Err = ParseLdtkEnumError> + 'static {
+    /// Identifier of the enum in LDtk.
+    const IDENTIFIER: &'static str;
+
+    /// All values of the enum, in the same order as in LDtk.
+    const VALUES: &'static [Self];
+
+    /// Identifier of this value in LDtk.
+    fn as_str(&self) -> &'static str;
+
+    /// Color assigned to this value in LDtk.
+    fn color(&self) -> Color;
+
+    /// Tile assigned to this value in LDtk, if any.
+    fn tile_rect(&self) -> Option<TilesetRectangle>;
+}
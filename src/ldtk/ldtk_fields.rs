@@ -1,10 +1,11 @@
 //! Contains [`LdtkFields`] trait, providing convenience methods for accessing field instances.
 use crate::ldtk::{
-    all_some_iter::AllSomeIter, EntityInstance, FieldInstance, FieldValue, Level,
-    ReferenceToAnEntityInstance, TilesetRectangle,
+    all_some_iter::AllSomeIter, fields_deserializer::FieldInstancesDeserializer, EntityInstance,
+    FieldInstance, FieldValue, Level, ReferenceToAnEntityInstance, TilesetRectangle,
 };
 use bevy::prelude::*;
 use paste::paste;
+use serde::Deserialize;
 use thiserror::Error;
 
 /// Errors related to the [`LdtkFields`] trait.
@@ -19,6 +20,17 @@ pub enum LdtkFieldsError {
     /// The field instance exists and is the correct variant, but the value is null.
     #[error("found {identifier} field of the correct type, but the value is null")]
     UnexpectedNull { identifier: String },
+    /// The field instance exists, but its value could not be deserialized into the requested type.
+    ///
+    /// Only returned by [`LdtkFields::deserialize_fields`].
+    #[error("found {identifier} field, but could not deserialize it: {message}")]
+    InvalidFieldValue { identifier: String, message: String },
+    /// The field instances could not be deserialized into the requested type, for reasons not
+    /// specific to any one field.
+    ///
+    /// Only returned by [`LdtkFields::deserialize_fields`].
+    #[error("could not deserialize fields: {message}")]
+    Deserialize { message: String },
 }
 
 /// Base macro for generating a method that accesses a field instance and unwraps its [FieldValue]
@@ -204,6 +216,58 @@ pub trait LdtkFields {
     create_plural_fields_methods!(Tiles, TilesetRectangle);
     create_plural_fields_methods!(EntityRefs, ReferenceToAnEntityInstance);
     create_plural_fields_methods!(Points, IVec2);
+
+    /// Deserialize all of this item's field instances into a type implementing [`Deserialize`].
+    ///
+    /// Field identifiers are used as keys, so struct fields need to match them exactly.
+    /// LDtk identifiers are usually capitalized, so `#[serde(rename_all = "PascalCase")]` is often
+    /// helpful.
+    /// Field instances without a corresponding struct field are ignored, unless the struct uses
+    /// `#[serde(deny_unknown_fields)]`.
+    ///
+    /// Null values can only be deserialized into [`Option`]s, arrays can be deserialized into
+    /// [`Vec`]s, and `Enum` fields can be deserialized into Rust enums with matching variant names.
+    /// See [`FieldInstancesDeserializer`] for more details on how each field type is presented.
+    /// ```
+    /// use bevy_ecs_ldtk::prelude::*;
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Deserialize)]
+    /// enum Equipment {
+    ///     Sword,
+    ///     Shield,
+    /// }
+    ///
+    /// #[derive(Deserialize)]
+    /// #[serde(rename_all = "PascalCase")]
+    /// struct PlayerFields {
+    ///     health: i32,
+    ///     nickname: Option<String>,
+    ///     equipment: Vec<Equipment>,
+    /// }
+    ///
+    /// fn player_fields(entity_instance: &EntityInstance) -> PlayerFields {
+    ///     entity_instance
+    ///         .deserialize_fields()
+    ///         .expect("player should have valid fields")
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    /// - returns [`LdtkFieldsError::FieldNotFound`] if a required field doesn't exist.
+    /// - returns [`LdtkFieldsError::WrongFieldType`] if a field's type doesn't match the type
+    ///   it's deserialized into.
+    /// - returns [`LdtkFieldsError::UnexpectedNull`] if a null value is deserialized into a
+    ///   non-optional type.
+    /// - returns [`LdtkFieldsError::InvalidFieldValue`] if a field fails to deserialize for any
+    ///   other reason, like an unknown enum variant.
+    /// - returns [`LdtkFieldsError::Deserialize`] for errors not associated with a single field.
+    fn deserialize_fields<'de, T: Deserialize<'de>>(&'de self) -> Result<T, LdtkFieldsError>
+    where
+        Self: Sized,
+    {
+        T::deserialize(FieldInstancesDeserializer::new(self.field_instances()))
+    }
 }
 
 impl LdtkFields for EntityInstance {
@@ -41,10 +41,13 @@ mod color;
 #[cfg(test)]
 pub mod fake;
 mod field_instance;
+pub mod fields_deserializer;
 mod impl_definitions;
+pub mod ldtk_enum;
 pub mod ldtk_fields;
 pub mod loaded_level;
 pub mod raw_level_accessor;
+pub mod toc_instance;
 
 pub use field_instance::*;
 
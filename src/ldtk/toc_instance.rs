@@ -0,0 +1,184 @@
+//! Contains [`TocInstance`] and related types/implementations.
+use crate::{
+    ldtk::{
+        ldtk_fields::LdtkFields, EntityDefinition, FieldInstance, LdtkTocInstanceData,
+        ReferenceToAnEntityInstance,
+    },
+    prelude::{EntityIid, LevelIid},
+};
+use bevy::prelude::*;
+use serde_json::Value;
+
+/// Wrapper around a borrowed entry of the project's table of contents.
+///
+/// LDtk lists every instance of entities with the "Add to table of contents" option in the
+/// project's [`toc`], along with their positions and any fields marked with "Export to table of
+/// contents".
+/// This data is available without loading or spawning any levels, making it useful for things
+/// like fast-travel maps or objective markers.
+///
+/// Can be accessed via [`LdtkProject::iter_toc_instances`] and related methods.
+///
+/// The exported fields are stored in the table of contents without type information.
+/// When constructed with the instance's [`EntityDefinition`], they are typed using its field
+/// definitions, so they can be read with the [`LdtkFields`] accessors.
+/// Fields with no matching definition, or whose value doesn't match its definition's type, are
+/// omitted.
+///
+/// [`toc`]: crate::ldtk::LdtkJson::toc
+/// [`LdtkProject::iter_toc_instances`]: crate::assets::LdtkProject::iter_toc_instances
+#[derive(Clone, Debug, PartialEq)]
+pub struct TocInstance<'a> {
+    identifier: &'a str,
+    data: &'a LdtkTocInstanceData,
+    field_instances: Vec<FieldInstance>,
+}
+
+impl<'a> TocInstance<'a> {
+    /// Construct a new [`TocInstance`] for an instance of the entity with the given identifier.
+    ///
+    /// The `entity_definition` is used for typing the instance's exported fields.
+    pub fn new(
+        identifier: &'a str,
+        data: &'a LdtkTocInstanceData,
+        entity_definition: Option<&EntityDefinition>,
+    ) -> Self {
+        let field_instances = match (&data.fields, entity_definition) {
+            (Some(Value::Object(fields)), Some(entity_definition)) => entity_definition
+                .field_defs
+                .iter()
+                .filter_map(|field_def| {
+                    let value = fields.get(&field_def.identifier)?;
+
+                    FieldInstance::from_definition_value(field_def, value.clone()).ok()
+                })
+                .collect(),
+            _ => Vec::new(),
+        };
+
+        TocInstance {
+            identifier,
+            data,
+            field_instances,
+        }
+    }
+
+    /// The raw table of contents data borrowed by this instance.
+    pub fn raw(&self) -> &'a LdtkTocInstanceData {
+        self.data
+    }
+
+    /// Identifier of this instance's entity definition.
+    pub fn identifier(&self) -> &'a str {
+        self.identifier
+    }
+
+    /// IID information of this instance.
+    pub fn iids(&self) -> &'a ReferenceToAnEntityInstance {
+        &self.data.iids
+    }
+
+    /// IID of this entity instance, matching the [`EntityIid`] it is spawned with.
+    pub fn entity_iid(&self) -> EntityIid {
+        EntityIid::new(self.data.iids.entity_iid.clone())
+    }
+
+    /// IID of the level containing this entity instance.
+    pub fn level_iid(&self) -> LevelIid {
+        LevelIid::new(self.data.iids.level_iid.clone())
+    }
+
+    /// Position of this entity instance in the world, in pixels.
+    ///
+    /// Like other LDtk coordinates, this uses a y-down coordinate system.
+    /// Use [`ldtk_pixel_coords_to_translation`] to convert it to bevy's coordinate system.
+    ///
+    /// [`ldtk_pixel_coords_to_translation`]: crate::utils::ldtk_pixel_coords_to_translation
+    pub fn world_position(&self) -> IVec2 {
+        IVec2::new(self.data.world_x, self.data.world_y)
+    }
+
+    /// Width and height of this entity instance, in pixels.
+    pub fn size(&self) -> IVec2 {
+        IVec2::new(self.data.wid_px, self.data.hei_px)
+    }
+}
+
+impl LdtkFields for TocInstance<'_> {
+    fn field_instances(&self) -> &[FieldInstance] {
+        &self.field_instances
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ldtk::{FieldDefinition, FieldValue};
+    use serde_json::json;
+
+    fn field_def(identifier: &str, uid: i32, field_definition_type: &str) -> FieldDefinition {
+        FieldDefinition {
+            identifier: identifier.to_string(),
+            uid,
+            field_definition_type: field_definition_type.to_string(),
+            export_to_toc: true,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn fields_are_typed_by_definition() {
+        let entity_definition = EntityDefinition {
+            identifier: "Door".to_string(),
+            field_defs: vec![
+                field_def("Locked", 1, "Bool"),
+                field_def("Keys", 2, "Array<Int>"),
+                field_def("Destination", 3, "String"),
+                field_def("Unexported", 4, "Float"),
+            ],
+            ..Default::default()
+        };
+
+        let data = LdtkTocInstanceData {
+            fields: Some(json!({
+                "Locked": true,
+                "Keys": [1, 2],
+                "Destination": 5,
+                "Unknown": "ignored",
+            })),
+            hei_px: 32,
+            iids: ReferenceToAnEntityInstance {
+                entity_iid: "entity".to_string(),
+                level_iid: "level".to_string(),
+                ..Default::default()
+            },
+            wid_px: 16,
+            world_x: 64,
+            world_y: 128,
+        };
+
+        let instance = TocInstance::new("Door", &data, Some(&entity_definition));
+
+        assert_eq!(instance.identifier(), "Door");
+        assert_eq!(instance.entity_iid(), EntityIid::new("entity"));
+        assert_eq!(instance.level_iid(), LevelIid::new("level"));
+        assert_eq!(instance.world_position(), IVec2::new(64, 128));
+        assert_eq!(instance.size(), IVec2::new(16, 32));
+
+        assert_eq!(instance.get_bool_field("Locked"), Ok(&true));
+        assert_eq!(
+            instance.get_maybe_ints_field("Keys"),
+            Ok(&[Some(1), Some(2)][..])
+        );
+        assert!(instance.get_string_field("Destination").is_err());
+        assert!(instance.get_float_field("Unexported").is_err());
+        assert_eq!(instance.field_instances().len(), 2);
+        assert!(matches!(
+            instance.field_instances()[0].value,
+            FieldValue::Bool(true)
+        ));
+
+        let untyped = TocInstance::new("Door", &data, None);
+        assert!(untyped.field_instances().is_empty());
+    }
+}
@@ -2,15 +2,21 @@
 
 use crate::{
     app::{
-        LdtkEntity, LdtkEntityMap, LdtkIntCellMap, PhantomLdtkEntity, PhantomLdtkEntityTrait,
-        PhantomLdtkIntCell, PhantomLdtkIntCellTrait,
+        LdtkEntity, LdtkEntityMap, LdtkEntityTagMap, LdtkIntCellIdentifierMap, LdtkIntCellMap,
+        LdtkTileMap, PhantomLdtkEntity, PhantomLdtkEntityTrait, PhantomLdtkIntCell,
+        PhantomLdtkIntCellTrait,
     },
+    assets::{ExportedImages, LevelMetadata, ParsedTileMetadata},
+    baked_tiles::{bake_tiles, BakedTiles},
     components::*,
     ldtk::{
         loaded_level::LoadedLevel, EntityDefinition, EnumTagValue, LayerDefinition, LayerInstance,
         LevelBackgroundPosition, TileCustomMetadata, TileInstance, TilesetDefinition, Type,
     },
-    resources::{IntGridRendering, LdtkSettings, LevelBackground},
+    resources::{
+        AutoTileUpdates, IntGridRectMerging, IntGridRendering, IntGridStorage, LdtkSettings,
+        LevelBackground, TileLayerRendering,
+    },
     tile_makers::*,
     utils::*,
 };
@@ -22,7 +28,11 @@ use bevy_ecs_tilemap::{
     },
     tiles::{TilePos, TileStorage},
 };
-use std::collections::{HashMap, HashSet};
+use std::{
+    borrow::Cow,
+    collections::{HashMap, HashSet},
+    ops::Range,
+};
 
 #[cfg(feature = "render")]
 use bevy_ecs_tilemap::TilemapBundle;
@@ -108,12 +118,56 @@ pub(crate) fn tile_to_grid_coords(
     )
 }
 
+/// Maps the tile ids of a tileset to the [TileMetadata] and [TileEnumTags] of those tiles.
+pub(crate) fn tile_metadata_maps(
+    tileset_definition: Option<&TilesetDefinition>,
+) -> (HashMap<i32, TileMetadata>, HashMap<i32, TileEnumTags>) {
+    let metadata_map: HashMap<i32, TileMetadata> = tileset_definition
+        .map(|tileset_definition| {
+            tileset_definition
+                .custom_data
+                .iter()
+                .map(|TileCustomMetadata { data, tile_id }| {
+                    (*tile_id, TileMetadata { data: data.clone() })
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let mut enum_tags_map: HashMap<i32, TileEnumTags> = HashMap::new();
+
+    if let Some(tileset_definition) = tileset_definition {
+        for EnumTagValue {
+            enum_value_id,
+            tile_ids,
+        } in tileset_definition.enum_tags.iter()
+        {
+            for tile_id in tile_ids {
+                enum_tags_map
+                    .entry(*tile_id)
+                    .or_insert_with(|| TileEnumTags {
+                        tags: Vec::new(),
+                        source_enum_uid: tileset_definition.tags_source_enum_uid,
+                    })
+                    .tags
+                    .push(enum_value_id.clone());
+            }
+        }
+    }
+
+    (metadata_map, enum_tags_map)
+}
+
+#[allow(clippy::too_many_arguments)]
 fn insert_metadata_to_tile(
     commands: &mut Commands,
     tile_instance: &TileInstance,
     tile_entity: Entity,
     metadata_map: &HashMap<i32, TileMetadata>,
     enum_tags_map: &HashMap<i32, TileEnumTags>,
+    tileset_definition: Option<&TilesetDefinition>,
+    ldtk_tile_map: &LdtkTileMap,
+    parsed_tile_metadata: &ParsedTileMetadata,
 ) -> bool {
     let mut entity_commands = commands.entity(tile_entity);
 
@@ -122,16 +176,54 @@ fn insert_metadata_to_tile(
     if let Some(tile_metadata) = metadata_map.get(&tile_instance.t) {
         entity_commands.insert(tile_metadata.clone());
         metadata_inserted = true;
+
+        if let Some(tileset_definition) = tileset_definition {
+            parsed_tile_metadata.insert_for_tile(
+                &tileset_definition.identifier,
+                tile_instance.t,
+                &mut entity_commands,
+            );
+        }
     }
 
-    if let Some(enum_tags) = enum_tags_map.get(&tile_instance.t) {
+    let enum_tags = enum_tags_map.get(&tile_instance.t);
+
+    if let Some(enum_tags) = enum_tags {
         entity_commands.insert(enum_tags.clone());
         metadata_inserted = true;
     }
 
+    if let Some(tileset_definition) = tileset_definition {
+        let enum_tag_values = enum_tags
+            .map(|enum_tags| enum_tags.tags.as_slice())
+            .unwrap_or_default();
+
+        if let Some(ldtk_tile) = ldtk_tile_map_get(
+            &tileset_definition.identifier,
+            tile_instance.t,
+            enum_tag_values,
+            ldtk_tile_map,
+        ) {
+            ldtk_tile.evaluate(&mut entity_commands, tile_instance, tileset_definition);
+        }
+    }
+
     metadata_inserted
 }
 
+/// Returns true if an [LdtkTile](crate::app::LdtkTile) is registered for any tile of the given
+/// tileset.
+fn ldtk_tiles_registered(
+    tileset_definition: Option<&TilesetDefinition>,
+    ldtk_tile_map: &LdtkTileMap,
+) -> bool {
+    tileset_definition.is_some_and(|tileset_definition| {
+        ldtk_tile_map
+            .keys()
+            .any(|(tileset_identifier, _)| *tileset_identifier == tileset_definition.identifier)
+    })
+}
+
 fn spatial_bundle_for_tiles(grid_coords: GridCoords, grid_size: i32) -> Transform {
     let translation =
         grid_coords_to_translation_relative_to_tile_layer(grid_coords, IVec2::splat(grid_size))
@@ -144,11 +236,12 @@ fn insert_spatial_bundle_for_layer_tiles(
     commands: &mut Commands,
     storage: &TileStorage,
     size: &TilemapSize,
+    rows: Range<u32>,
     grid_size: i32,
     tilemap_id: TilemapId,
 ) {
-    for x in 0..size.x {
-        for y in 0..size.y {
+    for y in rows {
+        for x in 0..size.x {
             let tile_pos = TilePos { x, y };
             let tile_entity = storage.get(&tile_pos);
 
@@ -172,17 +265,34 @@ fn insert_spatial_bundle_for_layer_tiles(
 fn insert_tile_metadata_for_layer(
     commands: &mut Commands,
     tile_storage: &TileStorage,
+    rows: Range<u32>,
     grid_tiles: &[TileInstance],
     layer_instance: &LayerInstance,
     metadata_map: &HashMap<i32, TileMetadata>,
     enum_tags_map: &HashMap<i32, TileEnumTags>,
+    tileset_definition: Option<&TilesetDefinition>,
+    ldtk_tile_map: &LdtkTileMap,
+    parsed_tile_metadata: &ParsedTileMetadata,
 ) {
     for tile in grid_tiles {
         let grid_coords = tile_to_grid_coords(tile, layer_instance.c_hei, layer_instance.grid_size);
 
+        if !rows.contains(&(grid_coords.y as u32)) {
+            continue;
+        }
+
         let tile_entity = tile_storage.get(&grid_coords.into()).unwrap();
 
-        insert_metadata_to_tile(commands, tile, tile_entity, metadata_map, enum_tags_map);
+        insert_metadata_to_tile(
+            commands,
+            tile,
+            tile_entity,
+            metadata_map,
+            enum_tags_map,
+            tileset_definition,
+            ldtk_tile_map,
+            parsed_tile_metadata,
+        );
     }
 }
 
@@ -212,16 +322,25 @@ fn tile_in_layer_bounds(tile: &TileInstance, layer_instance: &LayerInstance) ->
         && tile.px.y < (layer_instance.c_hei * layer_instance.grid_size)
 }
 
+/// Spawns a level, continuing from where the given [`LevelSpawnProgress`] left off.
+///
+/// Layers are spawned until the `budget` of tiles and entities runs out, so a level may take
+/// multiple calls to finish spawning.
+/// Big layers are spawned a few rows of tiles, or a few entities, at a time.
 #[allow(clippy::too_many_arguments)]
 pub fn spawn_level(
     level: LoadedLevel,
-    background_image: &Option<Handle<Image>>,
+    level_metadata: &LevelMetadata,
     commands: &mut Commands,
     asset_server: &AssetServer,
-    images: &Assets<Image>,
+    images: &mut Assets<Image>,
     texture_atlases: &mut Assets<TextureAtlasLayout>,
     ldtk_entity_map: &LdtkEntityMap,
+    ldtk_entity_tag_map: &LdtkEntityTagMap,
     ldtk_int_cell_map: &LdtkIntCellMap,
+    ldtk_int_cell_identifier_map: &LdtkIntCellIdentifierMap,
+    ldtk_tile_map: &LdtkTileMap,
+    parsed_tile_metadata: &ParsedTileMetadata,
     entity_definition_map: &HashMap<i32, &EntityDefinition>,
     layer_definition_map: &HashMap<i32, &LayerDefinition>,
     tileset_map: &HashMap<i32, Handle<Image>>,
@@ -230,14 +349,193 @@ pub fn spawn_level(
     worldly_set: &HashSet<Worldly>,
     ldtk_entity: Entity,
     ldtk_settings: &LdtkSettings,
+    progress: &mut LevelSpawnProgress,
+    budget: &mut usize,
+    patch_targets: &mut HashMap<EntityIid, Entity>,
 ) {
-    let layer_instances = level.layer_instances();
+    if !progress.background_spawned() {
+        let layer_z = spawn_level_background(
+            level,
+            level_metadata,
+            commands,
+            images,
+            texture_atlases,
+            ldtk_entity,
+            ldtk_settings,
+        );
+        progress.finish_background(layer_z);
+    }
+
+    for layer_instance in
+        spawned_layer_instances(level.layer_instances(), ldtk_settings).skip(progress.next_layer())
+    {
+        if *budget == 0 {
+            break;
+        }
+
+        let layer_z = spawn_layer(
+            layer_instance,
+            progress.layer_z(),
+            *level.px_wid(),
+            *level.px_hei(),
+            commands,
+            asset_server,
+            images,
+            texture_atlases,
+            ldtk_entity_map,
+            ldtk_entity_tag_map,
+            ldtk_int_cell_map,
+            ldtk_int_cell_identifier_map,
+            ldtk_tile_map,
+            parsed_tile_metadata,
+            entity_definition_map,
+            layer_definition_map,
+            tileset_map,
+            tileset_definition_map,
+            int_grid_image_handle,
+            level_metadata.exported_images(),
+            worldly_set,
+            ldtk_entity,
+            ldtk_settings,
+            patch_targets,
+            progress.layer_cursor_mut(),
+            budget,
+        );
+
+        match layer_z {
+            Some(layer_z) => progress.finish_layer(layer_instance, layer_z),
+            None => break,
+        }
+    }
+}
+
+/// Respawns the given layers of a spawned level at the z values their previous entities spanned.
+///
+/// Returns false if a layer doesn't fit in those z values anymore, in which case the entire level
+/// should be respawned.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn respawn_layers(
+    level: LoadedLevel,
+    level_metadata: &LevelMetadata,
+    commands: &mut Commands,
+    asset_server: &AssetServer,
+    images: &mut Assets<Image>,
+    texture_atlases: &mut Assets<TextureAtlasLayout>,
+    ldtk_entity_map: &LdtkEntityMap,
+    ldtk_entity_tag_map: &LdtkEntityTagMap,
+    ldtk_int_cell_map: &LdtkIntCellMap,
+    ldtk_int_cell_identifier_map: &LdtkIntCellIdentifierMap,
+    ldtk_tile_map: &LdtkTileMap,
+    parsed_tile_metadata: &ParsedTileMetadata,
+    entity_definition_map: &HashMap<i32, &EntityDefinition>,
+    layer_definition_map: &HashMap<i32, &LayerDefinition>,
+    tileset_map: &HashMap<i32, Handle<Image>>,
+    tileset_definition_map: &HashMap<i32, &TilesetDefinition>,
+    int_grid_image_handle: &Option<Handle<Image>>,
+    worldly_set: &HashSet<Worldly>,
+    ldtk_entity: Entity,
+    ldtk_settings: &LdtkSettings,
+    layers: &RespawnLayers,
+    patch_targets: &mut HashMap<EntityIid, Entity>,
+) -> bool {
+    if ldtk_settings.int_grid_rect_merging == IntGridRectMerging::Enabled {
+        let int_grid_rects = IntGridRects::from_layer_instances(spawned_layer_instances(
+            level.layer_instances(),
+            ldtk_settings,
+        ));
+
+        commands.entity(ldtk_entity).insert(int_grid_rects);
+    }
+
+    // Layers are respawned all at once
+    let mut budget = usize::MAX;
+
+    for layer_instance in spawned_layer_instances(level.layer_instances(), ldtk_settings)
+        .filter(|layer_instance| layers.layer_iids().contains(&layer_instance.iid))
+    {
+        let Some(layer_z) = layers.layer_z(&layer_instance.iid) else {
+            return false;
+        };
+
+        let next_layer_z = spawn_layer(
+            layer_instance,
+            layer_z.start,
+            *level.px_wid(),
+            *level.px_hei(),
+            commands,
+            asset_server,
+            images,
+            texture_atlases,
+            ldtk_entity_map,
+            ldtk_entity_tag_map,
+            ldtk_int_cell_map,
+            ldtk_int_cell_identifier_map,
+            ldtk_tile_map,
+            parsed_tile_metadata,
+            entity_definition_map,
+            layer_definition_map,
+            tileset_map,
+            tileset_definition_map,
+            int_grid_image_handle,
+            level_metadata.exported_images(),
+            worldly_set,
+            ldtk_entity,
+            ldtk_settings,
+            patch_targets,
+            &mut LayerSpawnCursor::default(),
+            &mut budget,
+        );
+
+        if next_layer_z.is_none_or(|next_layer_z| next_layer_z > layer_z.end) {
+            return false;
+        }
+    }
+
+    true
+}
 
+/// Iterates through the layers of a level that should be spawned, in spawning order.
+pub(crate) fn spawned_layer_instances<'a>(
+    layer_instances: &'a [LayerInstance],
+    ldtk_settings: &'a LdtkSettings,
+) -> impl Iterator<Item = &'a LayerInstance> {
+    layer_instances
+        .iter()
+        .filter(|layer| {
+            !ldtk_settings
+                .exclusions
+                .layer_identifiers
+                .contains(&layer.identifier)
+        })
+        .rev()
+}
+
+/// Spawns the level's background and [`IntGridRects`], returning the z value of the next layer.
+///
+/// This includes the image LDtk exported for the entire level, if it's used for rendering.
+fn spawn_level_background(
+    level: LoadedLevel,
+    level_metadata: &LevelMetadata,
+    commands: &mut Commands,
+    images: &Assets<Image>,
+    texture_atlases: &mut Assets<TextureAtlasLayout>,
+    ldtk_entity: Entity,
+    ldtk_settings: &LdtkSettings,
+) -> i32 {
     let mut layer_z = 0;
 
-    if ldtk_settings.level_background == LevelBackground::Rendered {
-        let translation = Vec3::new(*level.px_wid() as f32, *level.px_hei() as f32, 0.) / 2.;
+    if ldtk_settings.int_grid_rect_merging == IntGridRectMerging::Enabled {
+        let int_grid_rects = IntGridRects::from_layer_instances(spawned_layer_instances(
+            level.layer_instances(),
+            ldtk_settings,
+        ));
+
+        commands.entity(ldtk_entity).insert(int_grid_rects);
+    }
+
+    let translation = Vec3::new(*level.px_wid() as f32, *level.px_hei() as f32, 0.) / 2.;
 
+    if ldtk_settings.level_background == LevelBackground::Rendered {
         let background_entity = commands
             .spawn((
                 Sprite {
@@ -255,7 +553,7 @@ pub fn spawn_level(
 
         // Spawn background image
         if let (Some(background_image_handle), Some(background_position)) =
-            (background_image, level.bg_pos())
+            (level_metadata.bg_image(), level.bg_pos())
         {
             match background_image_sprite_sheet(
                 images,
@@ -277,195 +575,404 @@ pub fn spawn_level(
         }
     }
 
-    for layer_instance in layer_instances
-        .iter()
-        .filter(|layer| {
-            !ldtk_settings
-                .exclusions
-                .layer_identifiers
-                .contains(&layer.identifier)
-        })
-        .rev()
-    {
-        let layer_offset = Vec2::new(
-            layer_instance.px_total_offset_x as f32,
-            -layer_instance.px_total_offset_y as f32,
-        );
+    if let (TileLayerRendering::ExportedImages, Some(level_image)) = (
+        ldtk_settings.tile_layer_rendering,
+        level_metadata.exported_images().level(),
+    ) {
+        commands.spawn((
+            Sprite {
+                image: level_image.clone(),
+                custom_size: Some(Vec2::new(*level.px_wid() as f32, *level.px_hei() as f32)),
+                ..default()
+            },
+            Transform::from_translation(translation.with_z(layer_z as f32)),
+            Name::new("Exported Level Image"),
+            ChildOf(ldtk_entity),
+        ));
 
-        match layer_instance.layer_instance_type {
-            Type::Entities => {
-                let layer_entity = commands
-                    .spawn((
-                        Transform::from_translation(layer_offset.extend(layer_z as f32)),
-                        Visibility::default(),
-                        LayerMetadata::from(layer_instance),
-                        Name::new(layer_instance.identifier.to_owned()),
-                    ))
-                    .with_children(|commands| {
-                        for entity_instance in &layer_instance.entity_instances {
-                            let transform = calculate_transform_from_entity_instance(
-                                entity_instance,
-                                entity_definition_map,
-                                *level.px_hei(),
-                            );
-                            // Note: entities do not seem to be affected visually by layer offsets in
-                            // the editor, so no layer offset is added to the transform here.
+        layer_z += 1;
+    }
 
-                            let (tileset, tileset_definition) = match &entity_instance.tile {
-                                Some(t) => (
-                                    tileset_map.get(&t.tileset_uid),
-                                    tileset_definition_map.get(&t.tileset_uid).copied(),
-                                ),
-                                None => (None, None),
-                            };
-
-                            let predicted_worldly = Worldly::bundle_entity(
-                                entity_instance,
-                                layer_instance,
-                                tileset,
-                                tileset_definition,
-                                asset_server,
-                                texture_atlases,
-                            );
+    layer_z
+}
 
-                            if !worldly_set.contains(&predicted_worldly) {
-                                let default_ldtk_entity: Box<dyn PhantomLdtkEntityTrait> =
-                                    Box::new(PhantomLdtkEntity::<EntityInstanceBundle>::new());
-                                // insert Name before evaluating LdtkEntitys so that user-provided
-                                // names aren't overwritten
-                                let mut entity_commands = commands.spawn((
-                                    EntityIid::new(entity_instance.iid.to_owned()),
-                                    Name::new(entity_instance.identifier.to_owned()),
-                                ));
-
-                                ldtk_map_get_or_default(
-                                    layer_instance.identifier.clone(),
-                                    entity_instance.identifier.clone(),
-                                    &default_ldtk_entity,
-                                    ldtk_entity_map,
-                                )
-                                .evaluate(
-                                    &mut entity_commands,
-                                    entity_instance,
-                                    layer_instance,
-                                    tileset,
-                                    tileset_definition,
-                                    asset_server,
-                                    texture_atlases,
-                                );
+/// Spawns a layer of a level at the given z value, continuing from where the given
+/// [`LayerSpawnCursor`] left off.
+///
+/// Returns the z value of the next layer once the layer has finished spawning, or [`None`] if the
+/// `budget` ran out first.
+#[allow(clippy::too_many_arguments)]
+fn spawn_layer(
+    layer_instance: &LayerInstance,
+    mut layer_z: i32,
+    level_px_wid: i32,
+    level_px_hei: i32,
+    commands: &mut Commands,
+    asset_server: &AssetServer,
+    images: &mut Assets<Image>,
+    texture_atlases: &mut Assets<TextureAtlasLayout>,
+    ldtk_entity_map: &LdtkEntityMap,
+    ldtk_entity_tag_map: &LdtkEntityTagMap,
+    ldtk_int_cell_map: &LdtkIntCellMap,
+    ldtk_int_cell_identifier_map: &LdtkIntCellIdentifierMap,
+    ldtk_tile_map: &LdtkTileMap,
+    parsed_tile_metadata: &ParsedTileMetadata,
+    entity_definition_map: &HashMap<i32, &EntityDefinition>,
+    layer_definition_map: &HashMap<i32, &LayerDefinition>,
+    tileset_map: &HashMap<i32, Handle<Image>>,
+    tileset_definition_map: &HashMap<i32, &TilesetDefinition>,
+    int_grid_image_handle: &Option<Handle<Image>>,
+    exported_images: &ExportedImages,
+    worldly_set: &HashSet<Worldly>,
+    ldtk_entity: Entity,
+    ldtk_settings: &LdtkSettings,
+    patch_targets: &mut HashMap<EntityIid, Entity>,
+    cursor: &mut LayerSpawnCursor,
+    budget: &mut usize,
+) -> Option<i32> {
+    let layer_offset = Vec2::new(
+        layer_instance.px_total_offset_x as f32,
+        -layer_instance.px_total_offset_y as f32,
+    );
+
+    let layer_definition = layer_definition_map
+        .get(&layer_instance.layer_def_uid)
+        .copied();
+
+    let layer_parallax = |base_translation: Vec3| {
+        layer_definition.and_then(|layer_definition| {
+            LayerParallax::from_layer_definition(
+                layer_definition,
+                base_translation,
+                IVec2::new(level_px_wid, level_px_hei).as_vec2(),
+            )
+        })
+    };
+
+    match layer_instance.layer_instance_type {
+        Type::Entities => {
+            let layer_entity = match cursor.layer_entities.first() {
+                Some(layer_entity) => *layer_entity,
+                None => {
+                    let layer_translation = layer_offset.extend(layer_z as f32);
+                    let layer_entity = commands
+                        .spawn((
+                            Transform::from_translation(layer_translation),
+                            Visibility::default(),
+                            LayerMetadata::from(layer_instance),
+                            Name::new(layer_instance.identifier.to_owned()),
+                        ))
+                        .id();
+
+                    if let Some(layer_parallax) = layer_parallax(layer_translation) {
+                        commands.entity(layer_entity).insert(layer_parallax);
+                    }
 
-                                entity_commands.insert(transform);
-                            }
-                        }
-                    })
-                    .id();
+                    commands.entity(ldtk_entity).add_child(layer_entity);
+                    cursor.layer_entities.push(layer_entity);
+                    layer_entity
+                }
+            };
+
+            let entity_instances = layer_instance
+                .entity_instances
+                .get(cursor.next..)
+                .unwrap_or_default();
+            let count = entity_instances.len().min(*budget);
+
+            commands.entity(layer_entity).with_children(|commands| {
+                for entity_instance in &entity_instances[..count] {
+                    let transform = calculate_transform_from_entity_instance(
+                        entity_instance,
+                        entity_definition_map,
+                        level_px_hei,
+                    );
+                    // Note: entities do not seem to be affected visually by layer offsets in
+                    // the editor, so no layer offset is added to the transform here.
+
+                    let (tileset, tileset_definition) = match &entity_instance.tile {
+                        Some(t) => (
+                            tileset_map.get(&t.tileset_uid),
+                            tileset_definition_map.get(&t.tileset_uid).copied(),
+                        ),
+                        None => (None, None),
+                    };
 
-                commands.entity(ldtk_entity).add_child(layer_entity);
-                layer_z += 1;
-            }
-            _ => {
-                // The remaining layers have a lot of shared code.
-                // This is because:
-                // 1. There is virtually no difference between AutoTile and Tile layers
-                // 2. IntGrid layers can sometimes have AutoTile functionality
-
-                let size = TilemapSize {
-                    x: layer_instance.c_wid as u32,
-                    y: layer_instance.c_hei as u32,
-                };
+                    let predicted_worldly = Worldly::bundle_entity(
+                        entity_instance,
+                        layer_instance,
+                        tileset,
+                        tileset_definition,
+                        asset_server,
+                        texture_atlases,
+                    );
 
-                let tileset_definition = layer_instance
-                    .tileset_def_uid
-                    .map(|u| tileset_definition_map.get(&u).unwrap());
+                    if !worldly_set.contains(&predicted_worldly) {
+                        let default_ldtk_entity: Box<dyn PhantomLdtkEntityTrait> =
+                            Box::new(PhantomLdtkEntity::<EntityInstanceBundle>::new());
+                        let entity_iid = EntityIid::new(entity_instance.iid.to_owned());
+                        let name = Name::new(entity_instance.identifier.to_owned());
+
+                        // insert Name before evaluating LdtkEntitys so that user-provided
+                        // names aren't overwritten
+                        let mut entity_commands = match patch_targets.remove(&entity_iid) {
+                            Some(entity) => {
+                                let layer_entity = commands.target_entity();
+                                let mut entity_commands = commands.commands_mut().entity(entity);
+                                entity_commands.insert((ChildOf(layer_entity), name));
+                                entity_commands
+                            }
+                            None => commands.spawn((entity_iid, name)),
+                        };
+
+                        ldtk_entity_map_get_or_default(
+                            &layer_instance.identifier,
+                            entity_instance,
+                            &default_ldtk_entity,
+                            ldtk_entity_map,
+                            ldtk_entity_tag_map,
+                        )
+                        .evaluate(
+                            &mut entity_commands,
+                            entity_instance,
+                            layer_instance,
+                            tileset,
+                            tileset_definition,
+                            asset_server,
+                            texture_atlases,
+                        );
 
-                let tile_size = tileset_definition
-                    .map(|TilesetDefinition { tile_grid_size, .. }| *tile_grid_size)
-                    .unwrap_or(layer_instance.grid_size) as f32;
+                        entity_commands.insert(transform);
 
-                let tilemap_tile_size = TilemapTileSize {
-                    x: tile_size,
-                    y: tile_size,
-                };
+                        match LdtkEntityRefIids::from_entity_instance(entity_instance) {
+                            Some(ref_iids) => {
+                                entity_commands.insert(ref_iids);
+                            }
+                            // patched entities may have lost their references
+                            None => {
+                                entity_commands.remove::<(LdtkEntityRefIids, LdtkEntityRefs)>();
+                            }
+                        }
+                    }
+                }
+            });
 
-                let grid_size = layer_instance.grid_size as f32;
+            cursor.next += count;
+            cursor.spawned += count;
+            *budget = budget.saturating_sub(count.max(1));
 
-                let tilemap_grid_size = TilemapGridSize {
-                    x: grid_size,
-                    y: grid_size,
+            (count == entity_instances.len()).then_some(layer_z + 1)
+        }
+        _ => {
+            // The remaining layers have a lot of shared code.
+            // This is because:
+            // 1. There is virtually no difference between AutoTile and Tile layers
+            // 2. IntGrid layers can sometimes have AutoTile functionality
+
+            // Layers already drawn by an image LDtk exported, or whose tileset wasn't loaded, are
+            // only spawned for their data.
+            let mut hide_tilemap = false;
+
+            if ldtk_settings.tile_layer_rendering == TileLayerRendering::ExportedImages {
+                let layer_image = match exported_images.level() {
+                    Some(_) => None,
+                    None => exported_images.layers().get(&layer_instance.layer_def_uid),
                 };
 
-                let spacing = match tileset_definition {
-                    Some(tileset_definition) if tileset_definition.spacing != 0 => {
-                        // TODO: Check that this is still an issue with upcoming
-                        // bevy_ecs_tilemap releases
-                        #[cfg(not(feature = "atlas"))]
-                        {
-                            warn!(
-                                    "Tile spacing on Tile and AutoTile layers requires the \"atlas\" feature"
-                                );
+                hide_tilemap = exported_images.level().is_some() || layer_image.is_some();
 
-                            TilemapSpacing::default()
+                if layer_image.is_some()
+                    || (hide_tilemap && layer_instance.layer_instance_type != Type::IntGrid)
+                {
+                    // IntGrid layers spawned over multiple frames only spawn their image once
+                    if cursor.is_unstarted() {
+                        let level_size = IVec2::new(level_px_wid, level_px_hei).as_vec2();
+                        let translation = (level_size / 2.).extend(layer_z as f32);
+
+                        let layer_entity = commands
+                            .spawn((
+                                Transform::from_translation(translation),
+                                Visibility::default(),
+                                Name::new(layer_instance.identifier.to_owned()),
+                                ChildOf(ldtk_entity),
+                            ))
+                            .id();
+
+                        if let Some(layer_image) = layer_image {
+                            commands.entity(layer_entity).insert(Sprite {
+                                image: layer_image.clone(),
+                                custom_size: Some(level_size),
+                                ..default()
+                            });
+
+                            if let Some(layer_parallax) = layer_parallax(translation) {
+                                commands.entity(layer_entity).insert(layer_parallax);
+                            }
                         }
 
-                        #[cfg(feature = "atlas")]
-                        {
-                            TilemapSpacing {
-                                x: tileset_definition.spacing as f32,
-                                y: tileset_definition.spacing as f32,
-                            }
+                        // IntGrid layers still spawn their tilemap for the cells, which is the
+                        // entity that gets the LayerMetadata
+                        if layer_instance.layer_instance_type != Type::IntGrid {
+                            commands
+                                .entity(layer_entity)
+                                .insert(LayerMetadata::from(layer_instance));
+                            *budget = budget.saturating_sub(layer_spawn_cost(layer_instance));
+                            return Some(layer_z + 1);
                         }
                     }
-                    _ => TilemapSpacing::default(),
-                };
 
-                let texture = match (tileset_definition, int_grid_image_handle) {
-                    (Some(tileset_definition), _) => TilemapTexture::Single(
-                        tileset_map.get(&tileset_definition.uid).unwrap().clone(),
-                    ),
-                    (None, Some(handle)) => TilemapTexture::Single(handle.clone()),
-                    _ => {
-                        warn!("unable to render tilemap layer, it has no tileset and no intgrid layers were expected");
-                        continue;
-                    }
-                };
+                    layer_z += 1;
+                }
+            }
 
-                let metadata_map: HashMap<i32, TileMetadata> = tileset_definition
-                    .map(|tileset_definition| {
-                        tileset_definition
-                            .custom_data
-                            .iter()
-                            .map(|TileCustomMetadata { data, tile_id }| {
-                                (*tile_id, TileMetadata { data: data.clone() })
-                            })
-                            .collect()
-                    })
-                    .unwrap_or_default();
+            let size = TilemapSize {
+                x: layer_instance.c_wid as u32,
+                y: layer_instance.c_hei as u32,
+            };
+
+            let tileset_definition = layer_instance
+                .tileset_def_uid
+                .map(|u| tileset_definition_map.get(&u).unwrap());
+
+            let tile_size = tileset_definition
+                .map(|TilesetDefinition { tile_grid_size, .. }| *tile_grid_size)
+                .unwrap_or(layer_instance.grid_size) as f32;
 
-                let mut enum_tags_map: HashMap<i32, TileEnumTags> = HashMap::new();
+            let tilemap_tile_size = TilemapTileSize {
+                x: tile_size,
+                y: tile_size,
+            };
 
-                if let Some(tileset_definition) = tileset_definition {
-                    for EnumTagValue {
-                        enum_value_id,
-                        tile_ids,
-                    } in tileset_definition.enum_tags.iter()
+            let grid_size = layer_instance.grid_size as f32;
+
+            let tilemap_grid_size = TilemapGridSize {
+                x: grid_size,
+                y: grid_size,
+            };
+
+            let spacing = match tileset_definition {
+                Some(tileset_definition) if tileset_definition.spacing != 0 => {
+                    // TODO: Check that this is still an issue with upcoming
+                    // bevy_ecs_tilemap releases
+                    #[cfg(not(feature = "atlas"))]
                     {
-                        for tile_id in tile_ids {
-                            enum_tags_map
-                                .entry(*tile_id)
-                                .or_insert_with(|| TileEnumTags {
-                                    tags: Vec::new(),
-                                    source_enum_uid: tileset_definition.tags_source_enum_uid,
-                                })
-                                .tags
-                                .push(enum_value_id.clone());
+                        warn!(
+                                "Tile spacing on Tile and AutoTile layers requires the \"atlas\" feature"
+                            );
+
+                        TilemapSpacing::default()
+                    }
+
+                    #[cfg(feature = "atlas")]
+                    {
+                        TilemapSpacing {
+                            x: tileset_definition.spacing as f32,
+                            y: tileset_definition.spacing as f32,
                         }
                     }
                 }
+                _ => TilemapSpacing::default(),
+            };
+
+            let texture = match (tileset_definition, int_grid_image_handle) {
+                (Some(tileset_definition), _) => match tileset_map.get(&tileset_definition.uid) {
+                    Some(handle) => TilemapTexture::Single(handle.clone()),
+                    None => {
+                        // The tileset wasn't loaded, so the layer is only spawned for its data
+                        hide_tilemap = true;
+                        TilemapTexture::Single(Handle::default())
+                    }
+                },
+                (None, Some(handle)) => TilemapTexture::Single(handle.clone()),
+                _ => {
+                    warn!("unable to render tilemap layer, it has no tileset and no intgrid layers were expected");
+                    *budget = budget.saturating_sub(layer_spawn_cost(layer_instance));
+                    return Some(layer_z);
+                }
+            };
+
+            let (metadata_map, enum_tags_map) = tile_metadata_maps(tileset_definition.copied());
+            let ldtk_tiles_registered =
+                ldtk_tiles_registered(tileset_definition.copied(), ldtk_tile_map);
+
+            let LayerDefinition {
+                tile_pivot_x,
+                tile_pivot_y,
+                ..
+            } = &layer_definition_map
+                .get(&layer_instance.layer_def_uid)
+                .expect("Encountered layer without definition");
+
+            // Layers that couldn't be baked continue spawning as tilemaps
+            if ldtk_settings.tile_layer_rendering == TileLayerRendering::Baked
+                && layer_instance.layer_instance_type != Type::IntGrid
+                && cursor.is_unstarted()
+            {
+                let baked_tiles = tileset_definition
+                    .and_then(|tileset_definition| tileset_map.get(&tileset_definition.uid))
+                    .and_then(|handle| images.get(handle))
+                    .and_then(|tileset| {
+                        let grid_tiles = layer_instance
+                            .grid_tiles
+                            .iter()
+                            .chain(&layer_instance.auto_layer_tiles)
+                            .filter(|tile| tile_in_layer_bounds(tile, layer_instance))
+                            .cloned()
+                            .collect::<Vec<_>>();
+
+                        bake_tiles(
+                            tileset,
+                            tile_size as i32,
+                            layer_instance.grid_size,
+                            Vec2::new(*tile_pivot_x, *tile_pivot_y),
+                            IVec2::new(layer_instance.c_wid, layer_instance.c_hei),
+                            &grid_tiles,
+                            layer_instance.opacity,
+                        )
+                        .inspect_err(|e| {
+                            warn!(
+                                "unable to bake layer \"{}\", spawning it as a tilemap instead: {e}",
+                                layer_instance.identifier
+                            )
+                        })
+                        .ok()
+                    });
+
+                if let Some(BakedTiles { image, top_left }) = baked_tiles {
+                    // The image's top left corner, in bevy space relative to the layer
+                    let image_size = image.size().as_vec2();
+                    let top_left = Vec2::new(
+                        top_left.x as f32,
+                        (layer_instance.c_hei * layer_instance.grid_size - top_left.y) as f32,
+                    );
+                    let translation =
+                        (top_left + Vec2::new(image_size.x, -image_size.y) / 2. + layer_offset)
+                            .extend(layer_z as f32);
+
+                    let layer_entity = commands
+                        .spawn((
+                            Sprite::from_image(images.add(image)),
+                            Transform::from_translation(translation),
+                            LayerMetadata::from(layer_instance),
+                            Name::new(layer_instance.identifier.to_owned()),
+                            ChildOf(ldtk_entity),
+                        ))
+                        .id();
+
+                    if let Some(layer_parallax) = layer_parallax(translation) {
+                        commands.entity(layer_entity).insert(layer_parallax);
+                    }
+
+                    *budget = budget.saturating_sub(layer_spawn_cost(layer_instance));
+                    return Some(layer_z + 1);
+                }
+            }
 
+            if cursor.is_unstarted() {
                 let mut grid_tiles = layer_instance.grid_tiles.clone();
                 grid_tiles.extend(layer_instance.auto_layer_tiles.clone());
 
-                for (i, grid_tiles) in layer_grid_tiles(grid_tiles)
+                cursor.grid_tiles = layer_grid_tiles(grid_tiles)
                     .into_iter()
                     // filter out tiles that are out of bounds
                     .map(|grid_tiles| {
@@ -474,240 +981,299 @@ pub fn spawn_level(
                             .filter(|tile| tile_in_layer_bounds(tile, layer_instance))
                             .collect::<Vec<_>>()
                     })
-                    .enumerate()
-                {
-                    let layer_entity = commands.spawn_empty().id();
-
-                    let LayerDefinition {
-                        tile_pivot_x,
-                        tile_pivot_y,
-                        ..
-                    } = &layer_definition_map
-                        .get(&layer_instance.layer_def_uid)
-                        .expect("Encountered layer without definition");
-
-                    // The math for determining the x/y of a tilemap layer depends heavily on
-                    // both the layer's grid size and the tileset's tile size.
-                    // In particular, we care about their difference for properly reversing y
-                    // direction and for tile pivot calculations.
-                    let grid_tile_size_difference = grid_size - tile_size;
-
-                    // It is useful to determine what we should treat as the desired "origin" of
-                    // the tilemap in bevy space.
-                    // This will be the bottom left pixel of the tilemap.
-                    // The y value is affected when there is a difference between the grid size and
-                    // tile size - it sinks below 0 when the grid size is greater.
-                    let bottom_left_pixel = Vec2::new(0., grid_tile_size_difference);
-
-                    // Tiles in bevy_ecs_tilemap are anchored to the center of the tile.
-                    // We need to cancel out this anchoring so that layers of different sizes will
-                    // stack on top of eachother as they do in LDtk.
-                    let centering_adjustment = Vec2::splat(tile_size / 2.);
-
-                    // Layers in LDtk can have a pivot value that acts like an anchor.
-                    // The amount that a tile is translated by this pivot is simply the difference
-                    // between grid_size and tile_size again.
-                    let pivot_adjustment = Vec2::new(
-                        grid_tile_size_difference * tile_pivot_x,
-                        -grid_tile_size_difference * tile_pivot_y,
-                    );
+                    .collect();
 
-                    let tilemap_transform = Transform::from_translation(
-                        (bottom_left_pixel
-                            + centering_adjustment
-                            + pivot_adjustment
-                            + layer_offset)
-                            .extend(layer_z as f32),
-                    );
+                cursor.layer_entities = cursor
+                    .grid_tiles
+                    .iter()
+                    .map(|_| commands.spawn_empty().id())
+                    .collect();
+            }
 
-                    let tilemap_bundle = if layer_instance.layer_instance_type == Type::IntGrid {
-                        // The current spawning of IntGrid layers doesn't allow using
-                        // LayerBuilder::new_batch().
-                        // So, the actual LayerBuilder usage diverges greatly here
-                        let mut storage = TileStorage::empty(size);
-
-                        match tileset_definition {
-                            Some(_) => {
-                                set_all_tiles_with_func(
-                                    commands,
-                                    &mut storage,
-                                    size,
-                                    TilemapId(layer_entity),
-                                    tile_pos_to_tile_grid_bundle_maker(
-                                        tile_pos_to_transparent_tile_maker(
-                                            tile_pos_to_int_grid_with_grid_tiles_tile_maker(
-                                                &grid_tiles,
-                                                &layer_instance.int_grid_csv,
-                                                layer_instance.c_wid,
-                                                layer_instance.c_hei,
-                                                layer_instance.grid_size,
-                                                i,
-                                            ),
-                                            layer_instance.opacity,
+            // Rows are spawned from the top of the layer, as many as the budget allows.
+            let first_part = cursor.next == 0;
+            let rows_remaining = size.y.saturating_sub(cursor.next as u32);
+            let row_count = (*budget / size.x.max(1) as usize)
+                .max(1)
+                .min(rows_remaining as usize) as u32;
+            let rows = (rows_remaining - row_count)..rows_remaining;
+
+            // The indices of the int_grid_csv cells in those rows
+            let csv_start = cursor.next * size.x as usize;
+            let csv_indices = csv_start..(csv_start + (row_count * size.x) as usize);
+
+            for (i, (grid_tiles, layer_entity)) in cursor
+                .grid_tiles
+                .iter()
+                .zip(cursor.layer_entities.iter().copied())
+                .enumerate()
+            {
+                let layer_z = layer_z + i as i32;
+
+                // The math for determining the x/y of a tilemap layer depends heavily on
+                // both the layer's grid size and the tileset's tile size.
+                // In particular, we care about their difference for properly reversing y
+                // direction and for tile pivot calculations.
+                let grid_tile_size_difference = grid_size - tile_size;
+
+                // It is useful to determine what we should treat as the desired "origin" of
+                // the tilemap in bevy space.
+                // This will be the bottom left pixel of the tilemap.
+                // The y value is affected when there is a difference between the grid size and
+                // tile size - it sinks below 0 when the grid size is greater.
+                let bottom_left_pixel = Vec2::new(0., grid_tile_size_difference);
+
+                // Tiles in bevy_ecs_tilemap are anchored to the center of the tile.
+                // We need to cancel out this anchoring so that layers of different sizes will
+                // stack on top of eachother as they do in LDtk.
+                let centering_adjustment = Vec2::splat(tile_size / 2.);
+
+                // Layers in LDtk can have a pivot value that acts like an anchor.
+                // The amount that a tile is translated by this pivot is simply the difference
+                // between grid_size and tile_size again.
+                let pivot_adjustment = Vec2::new(
+                    grid_tile_size_difference * tile_pivot_x,
+                    -grid_tile_size_difference * tile_pivot_y,
+                );
+
+                let tilemap_transform = Transform::from_translation(
+                    (bottom_left_pixel + centering_adjustment + pivot_adjustment + layer_offset)
+                        .extend(layer_z as f32),
+                );
+
+                // Only holds the tiles spawned in this part of the layer
+                let mut storage = TileStorage::empty(size);
+
+                if layer_instance.layer_instance_type == Type::IntGrid {
+                    // The current spawning of IntGrid layers doesn't allow using
+                    // LayerBuilder::new_batch().
+                    // So, the actual LayerBuilder usage diverges greatly here
+
+                    // Values of the cells that should be spawned as entities
+                    let cell_int_grid_csv: Cow<[i32]> = match ldtk_settings.int_grid_storage {
+                        IntGridStorage::CellEntities => Cow::Borrowed(&layer_instance.int_grid_csv),
+                        IntGridStorage::Dense => Cow::Owned(
+                            layer_instance
+                                .int_grid_csv
+                                .iter()
+                                .enumerate()
+                                .map(|(index, value)| {
+                                    // only the cells being spawned are looked up
+                                    if csv_indices.contains(&index)
+                                        && int_cell_registered(
+                                            ldtk_int_cell_map,
+                                            ldtk_int_cell_identifier_map,
+                                            &layer_instance.identifier,
+                                            layer_definition,
+                                            *value,
+                                        )
+                                    {
+                                        *value
+                                    } else {
+                                        0
+                                    }
+                                })
+                                .collect(),
+                        ),
+                    };
+
+                    match tileset_definition {
+                        Some(_) => {
+                            set_tiles_with_func(
+                                commands,
+                                &mut storage,
+                                size,
+                                rows.clone(),
+                                TilemapId(layer_entity),
+                                tile_pos_to_tile_grid_bundle_maker(
+                                    tile_pos_to_transparent_tile_maker(
+                                        tile_pos_to_int_grid_with_grid_tiles_tile_maker(
+                                            grid_tiles,
+                                            &cell_int_grid_csv,
+                                            layer_instance.c_wid,
+                                            layer_instance.c_hei,
+                                            layer_instance.grid_size,
+                                            i,
                                         ),
+                                        layer_instance.opacity,
                                     ),
-                                );
-                            }
-                            None => {
-                                let int_grid_value_defs = &layer_definition_map
-                                    .get(&layer_instance.layer_def_uid)
-                                    .expect("Encountered layer without definition")
-                                    .int_grid_values;
-
-                                match ldtk_settings.int_grid_rendering {
-                                    IntGridRendering::Colorful => {
-                                        set_all_tiles_with_func(
-                                            commands,
-                                            &mut storage,
-                                            size,
-                                            TilemapId(layer_entity),
-                                            tile_pos_to_tile_grid_bundle_maker(
-                                                tile_pos_to_transparent_tile_maker(
-                                                    tile_pos_to_int_grid_colored_tile_maker(
-                                                        &layer_instance.int_grid_csv,
-                                                        int_grid_value_defs,
-                                                        layer_instance.c_wid,
-                                                        layer_instance.c_hei,
-                                                    ),
-                                                    layer_instance.opacity,
+                                ),
+                            );
+                        }
+                        None => {
+                            let int_grid_value_defs = &layer_definition_map
+                                .get(&layer_instance.layer_def_uid)
+                                .expect("Encountered layer without definition")
+                                .int_grid_values;
+
+                            match ldtk_settings.int_grid_rendering {
+                                IntGridRendering::Colorful => {
+                                    set_tiles_with_func(
+                                        commands,
+                                        &mut storage,
+                                        size,
+                                        rows.clone(),
+                                        TilemapId(layer_entity),
+                                        tile_pos_to_tile_grid_bundle_maker(
+                                            tile_pos_to_transparent_tile_maker(
+                                                tile_pos_to_int_grid_colored_tile_maker(
+                                                    &layer_instance.int_grid_csv,
+                                                    int_grid_value_defs,
+                                                    layer_instance.c_wid,
+                                                    layer_instance.c_hei,
                                                 ),
+                                                layer_instance.opacity,
                                             ),
-                                        );
-                                    }
-                                    IntGridRendering::Invisible => {
-                                        set_all_tiles_with_func(
-                                            commands,
-                                            &mut storage,
-                                            size,
-                                            TilemapId(layer_entity),
-                                            tile_pos_to_tile_grid_bundle_maker(
-                                                tile_pos_to_transparent_tile_maker(
-                                                    tile_pos_to_tile_if_int_grid_nonzero_maker(
-                                                        tile_pos_to_invisible_tile,
-                                                        &layer_instance.int_grid_csv,
-                                                        layer_instance.c_wid,
-                                                        layer_instance.c_hei,
-                                                    ),
-                                                    layer_instance.opacity,
+                                        ),
+                                    );
+                                }
+                                IntGridRendering::Invisible => {
+                                    set_tiles_with_func(
+                                        commands,
+                                        &mut storage,
+                                        size,
+                                        rows.clone(),
+                                        TilemapId(layer_entity),
+                                        tile_pos_to_tile_grid_bundle_maker(
+                                            tile_pos_to_transparent_tile_maker(
+                                                tile_pos_to_tile_if_int_grid_nonzero_maker(
+                                                    tile_pos_to_invisible_tile,
+                                                    &cell_int_grid_csv,
+                                                    layer_instance.c_wid,
+                                                    layer_instance.c_hei,
                                                 ),
+                                                layer_instance.opacity,
                                             ),
-                                        );
-                                    }
+                                        ),
+                                    );
                                 }
                             }
                         }
+                    }
 
-                        if i == 0 {
-                            for (i, value) in layer_instance
-                                .int_grid_csv
-                                .iter()
-                                .enumerate()
-                                .filter(|(_, v)| **v != 0)
-                            {
-                                let grid_coords = int_grid_index_to_grid_coords(
-                                    i,
-                                    layer_instance.c_wid as u32,
-                                    layer_instance.c_hei as u32,
-                                ).expect("int_grid_csv indices should be within the bounds of 0..(layer_width * layer_height)");
-
-                                if let Some(tile_entity) = storage.get(&grid_coords.into()) {
-                                    let mut entity_commands = commands.entity(tile_entity);
-
-                                    let default_ldtk_int_cell: Box<dyn PhantomLdtkIntCellTrait> =
-                                        Box::new(PhantomLdtkIntCell::<IntGridCellBundle>::new());
-
-                                    ldtk_map_get_or_default(
-                                        layer_instance.identifier.clone(),
-                                        *value,
-                                        &default_ldtk_int_cell,
-                                        ldtk_int_cell_map,
-                                    )
-                                    .evaluate(
-                                        &mut entity_commands,
-                                        IntGridCell { value: *value },
-                                        layer_instance,
-                                    );
-                                }
+                    if i == 0 {
+                        for (i, value) in cell_int_grid_csv
+                            .iter()
+                            .enumerate()
+                            .skip(csv_indices.start)
+                            .take(csv_indices.len())
+                            .filter(|(_, v)| **v != 0)
+                        {
+                            let grid_coords = int_grid_index_to_grid_coords(
+                                i,
+                                layer_instance.c_wid as u32,
+                                layer_instance.c_hei as u32,
+                            ).expect("int_grid_csv indices should be within the bounds of 0..(layer_width * layer_height)");
+
+                            if let Some(tile_entity) = storage.get(&grid_coords.into()) {
+                                let mut entity_commands = commands.entity(tile_entity);
+
+                                let default_ldtk_int_cell: Box<dyn PhantomLdtkIntCellTrait> =
+                                    Box::new(PhantomLdtkIntCell::<IntGridCellBundle>::new());
+
+                                ldtk_int_cell_map_get(
+                                    &layer_instance.identifier,
+                                    *value,
+                                    layer_definition,
+                                    ldtk_int_cell_map,
+                                    ldtk_int_cell_identifier_map,
+                                )
+                                .unwrap_or(&default_ldtk_int_cell)
+                                .evaluate(
+                                    &mut entity_commands,
+                                    IntGridCell { value: *value },
+                                    layer_instance,
+                                );
                             }
                         }
+                    }
+                } else {
+                    let tile_bundle_maker =
+                        tile_pos_to_tile_grid_bundle_maker(tile_pos_to_transparent_tile_maker(
+                            tile_pos_to_tile_maker(
+                                grid_tiles,
+                                layer_instance.c_hei,
+                                layer_instance.grid_size,
+                            ),
+                            layer_instance.opacity,
+                        ));
+
+                    // When we add metadata to tiles, we need to add additional
+                    // components to them.
+                    // This can't be accomplished using LayerBuilder::new_batch,
+                    // so the logic for building layers with metadata is slower.
+
+                    set_tiles_with_func(
+                        commands,
+                        &mut storage,
+                        size,
+                        rows.clone(),
+                        TilemapId(layer_entity),
+                        tile_bundle_maker,
+                    );
+                }
 
-                        if !(metadata_map.is_empty() && enum_tags_map.is_empty()) {
-                            insert_tile_metadata_for_layer(
-                                commands,
-                                &storage,
-                                &grid_tiles,
-                                layer_instance,
-                                &metadata_map,
-                                &enum_tags_map,
-                            );
-                        }
+                if ldtk_tiles_registered || !(metadata_map.is_empty() && enum_tags_map.is_empty()) {
+                    insert_tile_metadata_for_layer(
+                        commands,
+                        &storage,
+                        rows.clone(),
+                        grid_tiles,
+                        layer_instance,
+                        &metadata_map,
+                        &enum_tags_map,
+                        tileset_definition.copied(),
+                        ldtk_tile_map,
+                        parsed_tile_metadata,
+                    );
+                }
 
-                        TilemapBundle {
-                            transform: tilemap_transform,
-                            grid_size: tilemap_grid_size,
-                            size,
-                            spacing,
-                            storage,
-                            texture: texture.clone(),
-                            tile_size: tilemap_tile_size,
-                            ..default()
-                        }
-                    } else {
-                        let tile_bundle_maker =
-                            tile_pos_to_tile_grid_bundle_maker(tile_pos_to_transparent_tile_maker(
-                                tile_pos_to_tile_maker(
-                                    &grid_tiles,
-                                    layer_instance.c_hei,
-                                    layer_instance.grid_size,
-                                ),
-                                layer_instance.opacity,
-                            ));
-
-                        // When we add metadata to tiles, we need to add additional
-                        // components to them.
-                        // This can't be accomplished using LayerBuilder::new_batch,
-                        // so the logic for building layers with metadata is slower.
-
-                        let mut storage = TileStorage::empty(size);
-
-                        set_all_tiles_with_func(
-                            commands,
-                            &mut storage,
-                            size,
-                            TilemapId(layer_entity),
-                            tile_bundle_maker,
-                        );
+                insert_spatial_bundle_for_layer_tiles(
+                    commands,
+                    &storage,
+                    &size,
+                    rows.clone(),
+                    layer_instance.grid_size,
+                    TilemapId(layer_entity),
+                );
+
+                if first_part {
+                    let mut tilemap_bundle = TilemapBundle {
+                        transform: tilemap_transform,
+                        grid_size: tilemap_grid_size,
+                        size,
+                        spacing,
+                        storage,
+                        texture: texture.clone(),
+                        tile_size: tilemap_tile_size,
+                        ..default()
+                    };
 
-                        if !(metadata_map.is_empty() && enum_tags_map.is_empty()) {
-                            insert_tile_metadata_for_layer(
-                                commands,
-                                &storage,
-                                &grid_tiles,
-                                layer_instance,
-                                &metadata_map,
-                                &enum_tags_map,
-                            );
-                        }
+                    if hide_tilemap {
+                        tilemap_bundle.visibility = Visibility::Hidden;
+                    }
 
-                        TilemapBundle {
-                            transform: tilemap_transform,
-                            grid_size: tilemap_grid_size,
-                            size,
-                            spacing,
-                            storage,
-                            texture: texture.clone(),
-                            tile_size: tilemap_tile_size,
-                            ..default()
-                        }
-                    };
+                    if let Some(layer_parallax) =
+                        layer_parallax(tilemap_bundle.transform.translation)
+                    {
+                        commands.entity(layer_entity).insert(layer_parallax);
+                    }
 
-                    insert_spatial_bundle_for_layer_tiles(
-                        commands,
-                        &tilemap_bundle.storage,
-                        &tilemap_bundle.size,
-                        layer_instance.grid_size,
-                        TilemapId(layer_entity),
-                    );
+                    if layer_instance.layer_instance_type == Type::IntGrid && i == 0 {
+                        commands
+                            .entity(layer_entity)
+                            .insert(IntGridValues::from(layer_instance));
+                    }
+
+                    if ldtk_settings.auto_tile_updates == AutoTileUpdates::OnIntGridChange
+                        && layer_definition
+                            .is_some_and(|definition| !definition.auto_rule_groups.is_empty())
+                        && i == 0
+                    {
+                        commands
+                            .entity(layer_entity)
+                            .insert(AutoLayerTiles::from(layer_instance));
+                    }
 
                     commands.entity(layer_entity).insert((
                         tilemap_bundle,
@@ -715,10 +1281,32 @@ pub fn spawn_level(
                         Name::new(layer_instance.identifier.to_owned()),
                         ChildOf(ldtk_entity),
                     ));
-
-                    layer_z += 1;
+                } else {
+                    // Later parts of the layer add their tiles to the tilemap's storage
+                    let tiles = rows
+                        .clone()
+                        .flat_map(|y| (0..size.x).map(move |x| TilePos { x, y }))
+                        .filter_map(|tile_pos| Some((tile_pos, storage.get(&tile_pos)?)))
+                        .collect::<Vec<_>>();
+
+                    commands
+                        .entity(layer_entity)
+                        .queue(move |mut entity: EntityWorldMut| {
+                            if let Some(mut layer_storage) = entity.get_mut::<TileStorage>() {
+                                for (tile_pos, tile_entity) in tiles {
+                                    layer_storage.set(&tile_pos, tile_entity);
+                                }
+                            }
+                        });
                 }
             }
+
+            let cost = (row_count * size.x) as usize;
+            cursor.next += row_count as usize;
+            cursor.spawned += cost;
+            *budget = budget.saturating_sub(cost.max(1));
+
+            (row_count == rows_remaining).then_some(layer_z + cursor.layer_entities.len() as i32)
         }
     }
 }
@@ -21,7 +21,8 @@
 //! I.e., projects that store level data within the main project file.
 //! - `external_levels`: Enable support for projects that store levels externally.
 //! I.e., projects that store data for each level in files separate from the main project file.
-//! - `derive`: Enables the derive macros for [LdtkEntity] and [LdtkIntCell].
+//! - `derive`: Enables the derive macros for [LdtkEntity], [LdtkIntCell], and [LdtkTile], and the
+//! `ldtk_enums!` macro for generating [LdtkEnum]s.
 //! - `render`: Enables rendering via [bevy_ecs_tilemap]'s `render` feature. Disable it if you want
 //! to run in headless mode.
 //! - `atlas`: Enables the `atlas` feature of [bevy_ecs_tilemap]. This is required for WASM support
@@ -33,11 +34,16 @@
 //! [feature flags]: https://doc.rust-lang.org/cargo/reference/features.html#the-features-section
 //! [LdtkEntity]: app::LdtkEntity
 //! [LdtkIntCell]: app::LdtkEntity
+//! [LdtkTile]: app::LdtkTile
+//! [LdtkEnum]: ldtk::ldtk_enum::LdtkEnum
 //! [bevy_ecs_tilemap]: https://docs.rs/bevy_ecs_tilemap
 
 pub mod app;
 pub mod assets;
+mod auto_layer;
+mod baked_tiles;
 mod components;
+mod int_grid_query;
 pub mod ldtk;
 mod level;
 mod plugin;
@@ -47,6 +53,7 @@ mod tile_makers;
 pub mod utils;
 
 pub use components::*;
+pub use int_grid_query::{LdtkIntGridMut, LdtkIntGridQuery};
 pub use plugin::*;
 pub use resources::*;
 
@@ -57,25 +64,34 @@ pub mod prelude {
     //! `use bevy_ecs_ldtk::prelude::*;` to import commonly used items.
 
     pub use crate::{
-        app::{LdtkEntity, LdtkEntityAppExt, LdtkIntCell, LdtkIntCellAppExt},
+        app::{
+            LdtkEntity, LdtkEntityAppExt, LdtkEntityCommandsExt, LdtkIntCell, LdtkIntCellAppExt,
+            LdtkTile, LdtkTileAppExt,
+        },
         assets::{LdtkProject, LevelIndices, LevelMetadataAccessor},
         components::{
-            EntityIid, EntityInstance, GridCoords, IntGridCell, LayerMetadata, LdtkProjectHandle,
-            LdtkWorldBundle, LevelIid, LevelSet, Respawn, TileEnumTags, TileMetadata, Worldly,
+            AutoLayerTiles, EntityIid, EntityInstance, GridCoords, IntGridCell, IntGridRects,
+            IntGridValues, LayerMetadata, LayerParallax, LdtkEntityRefIids, LdtkEntityRefs,
+            LdtkProjectHandle, LdtkWorldBundle, LevelIid, LevelSet, LevelSpawnProgress,
+            LevelStreamingFocus, ParallaxCamera, PatchOnHotReload, Respawn, TileEnumTags,
+            TileMetadata, Worldly,
         },
+        int_grid_query::{LdtkIntGridMut, LdtkIntGridQuery},
         ldtk::{
             self, ldtk_fields::LdtkFields, raw_level_accessor::RawLevelAccessor, FieldValue,
             LayerInstance, TilesetDefinition,
         },
         plugin::{LdtkPlugin, ProcessLdtkApi},
         resources::{
-            IntGridRendering, LdtkSettings, LevelBackground, LevelEvent, LevelSelection,
-            LevelSpawnBehavior, SetClearColor, SpawnExclusions,
+            AutoTileUpdates, HotReloadBehavior, IidIndex, IntGridChanged, IntGridRectMerging,
+            IntGridRendering, IntGridStorage, LdtkSettings, LevelBackground, LevelEvent,
+            LevelSelection, LevelSpawnBehavior, LevelSpawnBudget, SetClearColor, SpawnExclusions,
+            TileLayerRendering,
         },
     };
 
     #[cfg(feature = "derive")]
-    pub use crate::{LdtkEntity, LdtkIntCell};
+    pub use crate::{LdtkEntity, LdtkIntCell, LdtkTile};
 
     #[cfg(feature = "external_levels")]
     pub use crate::assets::LdtkExternalLevel;
@@ -7,6 +7,7 @@ use bevy::{app::MainScheduleOrder, ecs::schedule::ScheduleLabel, prelude::*};
 /// In particular, this set processes..
 /// - [resources::LevelSelection]
 /// - [components::LevelSet]
+/// - [components::LevelStreamingFocus]
 /// - [components::Worldly]
 /// - [components::Respawn]
 ///
@@ -45,22 +46,44 @@ impl Plugin for LdtkPlugin {
                 (ProcessApiSet::PreClean, ProcessApiSet::Clean).chain(),
             )
             .init_non_send::<app::LdtkEntityMap>()
+            .init_non_send::<app::LdtkEntityTagMap>()
             .init_non_send::<app::LdtkIntCellMap>()
+            .init_non_send::<app::LdtkIntCellIdentifierMap>()
+            .init_non_send::<app::LdtkTileMap>()
             .init_resource::<resources::LdtkSettings>()
+            .init_resource::<resources::IidIndex>()
             .add_message::<resources::LevelEvent>()
+            .add_message::<resources::IntGridChanged>()
             .add_systems(
                 PreUpdate,
-                (systems::process_ldtk_assets, systems::process_ldtk_levels),
+                (
+                    systems::process_ldtk_assets,
+                    systems::process_ldtk_levels,
+                    (systems::update_iid_index, systems::resolve_entity_refs)
+                        .chain()
+                        .after(systems::process_ldtk_levels),
+                ),
             )
             .add_systems(
                 ProcessLdtkApi,
-                (systems::apply_level_selection, systems::apply_level_set)
+                (
+                    systems::apply_level_selection,
+                    systems::apply_level_streaming,
+                    #[cfg(feature = "external_levels")]
+                    systems::load_lazy_external_levels,
+                    systems::apply_level_set,
+                )
                     .chain()
                     .in_set(ProcessApiSet::PreClean),
             )
             .add_systems(
                 ProcessLdtkApi,
-                (ApplyDeferred, systems::clean_respawn_entities)
+                (
+                    ApplyDeferred,
+                    systems::clean_respawn_entities,
+                    systems::update_iid_index,
+                    systems::resolve_entity_refs,
+                )
                     .chain()
                     .in_set(ProcessApiSet::Clean),
             )
@@ -70,6 +93,8 @@ impl Plugin for LdtkPlugin {
                     systems::detect_level_spawned_events
                         .pipe(systems::fire_level_transformed_events),
                     systems::worldly_adoption.after(TransformSystems::Propagate),
+                    systems::apply_layer_parallax.before(TransformSystems::Propagate),
+                    systems::update_auto_layer_tiles,
                 ),
             )
             .register_type::<components::LevelIid>()
@@ -77,6 +102,20 @@ impl Plugin for LdtkPlugin {
             .register_type::<components::GridCoords>()
             .register_type::<components::TileMetadata>()
             .register_type::<components::TileEnumTags>()
-            .register_type::<components::LayerMetadata>();
+            .register_type::<components::LayerMetadata>()
+            .register_type::<components::IntGridRects>()
+            .register_type::<components::IntGridValues>()
+            .register_type::<components::AutoLayerTiles>()
+            .register_type::<components::LevelStreamingFocus>()
+            .register_type::<components::LevelSpawnProgress>()
+            .register_type::<components::LdtkEntityRefIids>()
+            .register_type::<components::LdtkEntityRefs>()
+            .register_type::<components::PatchOnHotReload>()
+            .register_type::<components::RespawnLayers>()
+            .register_type::<components::ParallaxCamera>()
+            .register_type::<components::LayerParallax>();
+
+        #[cfg(feature = "external_levels")]
+        app.init_resource::<resources::LazyExternalLevels>();
     }
 }
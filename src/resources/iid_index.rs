@@ -0,0 +1,189 @@
+use crate::{EntityIid, LevelIid};
+use bevy::prelude::*;
+use std::{collections::HashMap, hash::Hash};
+
+#[allow(unused_imports)]
+use crate::components::{LdtkEntityRefs, Worldly};
+
+/// Two-way map between iids and the entities they belong to.
+#[derive(Clone, Eq, PartialEq, Debug)]
+struct IidMap<I: Eq + Hash> {
+    entities: HashMap<I, Entity>,
+    iids: HashMap<Entity, I>,
+}
+
+impl<I: Eq + Hash> Default for IidMap<I> {
+    fn default() -> Self {
+        IidMap {
+            entities: HashMap::default(),
+            iids: HashMap::default(),
+        }
+    }
+}
+
+impl<I: Clone + Eq + Hash> IidMap<I> {
+    fn insert(&mut self, iid: I, entity: Entity) {
+        if let Some(previous_entity) = self.entities.insert(iid.clone(), entity) {
+            self.iids.remove(&previous_entity);
+        }
+        self.iids.insert(entity, iid);
+    }
+
+    fn remove(&mut self, entity: Entity) -> bool {
+        let Some(iid) = self.iids.remove(&entity) else {
+            return false;
+        };
+
+        if self.entities.get(&iid) == Some(&entity) {
+            self.entities.remove(&iid);
+        }
+
+        true
+    }
+}
+
+/// [`Resource`] mapping the iids of spawned LDtk entities and levels to their bevy [`Entity`].
+///
+/// This is maintained by the plugin as entities with an [`EntityIid`] or [`LevelIid`] spawn and
+/// despawn, so it can be used to look them up in constant time rather than scanning through a
+/// query.
+/// This includes [`Worldly`] entities, which stay indexed as long as they aren't despawned.
+/// It is also what the plugin uses to resolve [`LdtkEntityRefs`].
+///
+/// Updates occur in [`PreUpdate`] after levels are spawned, and in [`ProcessLdtkApi`] after levels
+/// are despawned or cleaned up for respawning.
+/// So, the index is consistent with the plugin's spawning and despawning during [`Update`] and
+/// [`PostUpdate`], including through respawns and hot reloads.
+/// Entities that you despawn yourself are removed from the index during the next update.
+///
+/// If multiple spawned entities share the same iid, like when the same project is spawned in
+/// multiple worlds, only the most recently spawned one is indexed.
+///
+/// [`Resource`]: https://docs.rs/bevy/latest/bevy/ecs/prelude/trait.Resource.html
+/// [`PreUpdate`]: https://docs.rs/bevy/latest/bevy/app/struct.PreUpdate.html
+/// [`Update`]: https://docs.rs/bevy/latest/bevy/app/struct.Update.html
+/// [`PostUpdate`]: https://docs.rs/bevy/latest/bevy/app/struct.PostUpdate.html
+/// [`ProcessLdtkApi`]: crate::prelude::ProcessLdtkApi
+#[derive(Clone, Eq, PartialEq, Debug, Default, Resource)]
+pub struct IidIndex {
+    entities: IidMap<EntityIid>,
+    levels: IidMap<LevelIid>,
+}
+
+impl IidIndex {
+    /// Returns the bevy [`Entity`] of the spawned LDtk entity with the given iid.
+    pub fn get_entity(&self, iid: &EntityIid) -> Option<Entity> {
+        self.entities.entities.get(iid).copied()
+    }
+
+    /// Returns true if an LDtk entity with the given iid is spawned.
+    pub fn contains_entity(&self, iid: &EntityIid) -> bool {
+        self.entities.entities.contains_key(iid)
+    }
+
+    /// Iterates through the iids and bevy [`Entity`]s of all spawned LDtk entities.
+    pub fn iter_entities(&self) -> impl Iterator<Item = (&EntityIid, Entity)> {
+        self.entities
+            .entities
+            .iter()
+            .map(|(iid, entity)| (iid, *entity))
+    }
+
+    /// Returns the bevy [`Entity`] of the spawned level with the given iid.
+    pub fn get_level(&self, iid: &LevelIid) -> Option<Entity> {
+        self.levels.entities.get(iid).copied()
+    }
+
+    /// Returns true if a level with the given iid is spawned.
+    pub fn contains_level(&self, iid: &LevelIid) -> bool {
+        self.levels.entities.contains_key(iid)
+    }
+
+    /// Iterates through the iids and bevy [`Entity`]s of all spawned levels.
+    pub fn iter_levels(&self) -> impl Iterator<Item = (&LevelIid, Entity)> {
+        self.levels
+            .entities
+            .iter()
+            .map(|(iid, entity)| (iid, *entity))
+    }
+
+    pub(crate) fn insert_entity(&mut self, iid: EntityIid, entity: Entity) {
+        self.entities.insert(iid, entity);
+    }
+
+    /// Removes the given LDtk entity from the index, returning true if it was indexed.
+    pub(crate) fn remove_entity(&mut self, entity: Entity) -> bool {
+        self.entities.remove(entity)
+    }
+
+    pub(crate) fn insert_level(&mut self, iid: LevelIid, entity: Entity) {
+        self.levels.insert(iid, entity);
+    }
+
+    /// Removes the given level from the index, returning true if it was indexed.
+    pub(crate) fn remove_level(&mut self, entity: Entity) -> bool {
+        self.levels.remove(entity)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn entities_can_be_inserted_and_removed() {
+        let mut index = IidIndex::default();
+        let a = Entity::from_raw_u32(1).unwrap();
+        let b = Entity::from_raw_u32(2).unwrap();
+
+        index.insert_entity(EntityIid::new("a"), a);
+        index.insert_entity(EntityIid::new("b"), b);
+
+        assert_eq!(index.get_entity(&EntityIid::new("a")), Some(a));
+        assert_eq!(index.get_entity(&EntityIid::new("b")), Some(b));
+        assert_eq!(index.iter_entities().count(), 2);
+
+        assert!(index.remove_entity(a));
+        assert!(!index.remove_entity(a));
+
+        assert_eq!(index.get_entity(&EntityIid::new("a")), None);
+        assert!(index.contains_entity(&EntityIid::new("b")));
+    }
+
+    #[test]
+    fn respawned_entities_replace_old_ones() {
+        let mut index = IidIndex::default();
+        let old = Entity::from_raw_u32(1).unwrap();
+        let new = Entity::from_raw_u32(2).unwrap();
+
+        index.insert_entity(EntityIid::new("a"), old);
+        index.insert_entity(EntityIid::new("a"), new);
+
+        // despawning the old entity after the new one spawned shouldn't remove the new one
+        assert!(!index.remove_entity(old));
+        assert_eq!(index.get_entity(&EntityIid::new("a")), Some(new));
+
+        assert!(index.remove_entity(new));
+        assert_eq!(index.get_entity(&EntityIid::new("a")), None);
+    }
+
+    #[test]
+    fn levels_are_indexed_separately() {
+        let mut index = IidIndex::default();
+        let level = Entity::from_raw_u32(1).unwrap();
+        let entity = Entity::from_raw_u32(2).unwrap();
+
+        index.insert_level(LevelIid::new("a"), level);
+        index.insert_entity(EntityIid::new("a"), entity);
+
+        assert_eq!(index.get_level(&LevelIid::new("a")), Some(level));
+        assert_eq!(index.get_entity(&EntityIid::new("a")), Some(entity));
+        assert_eq!(index.iter_levels().count(), 1);
+
+        assert!(!index.remove_level(entity));
+        assert!(index.remove_level(level));
+
+        assert!(!index.contains_level(&LevelIid::new("a")));
+        assert!(index.contains_entity(&EntityIid::new("a")));
+    }
+}
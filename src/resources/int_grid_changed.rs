@@ -0,0 +1,28 @@
+use bevy::prelude::*;
+
+use crate::{GridCoords, LevelIid};
+
+#[allow(unused_imports)]
+use crate::{IntGridValues, LdtkIntGridMut};
+
+/// Message written whenever an IntGrid value is changed with [`LdtkIntGridMut`].
+///
+/// Written during the same system that changed the value, but the cell entity changes are
+/// performed with [`Commands`], so they are only visible after those are applied.
+///
+/// [`Commands`]: https://docs.rs/bevy/latest/bevy/ecs/prelude/struct.Commands.html
+#[derive(Clone, Eq, PartialEq, Debug, Hash, Message)]
+pub struct IntGridChanged {
+    /// The `iid` of the level containing the changed cell.
+    pub level_iid: LevelIid,
+    /// The layer entity with the [`IntGridValues`] that changed.
+    pub layer_entity: Entity,
+    /// The identifier of the changed layer.
+    pub layer_identifier: String,
+    /// The coordinates of the changed cell.
+    pub grid_coords: GridCoords,
+    /// The value of the cell before the change.
+    pub old_value: i32,
+    /// The value of the cell after the change.
+    pub new_value: i32,
+}
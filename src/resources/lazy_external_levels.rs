@@ -0,0 +1,38 @@
+use crate::assets::LdtkExternalLevel;
+use bevy::prelude::*;
+use std::collections::HashMap;
+
+#[allow(unused_imports)]
+use crate::{assets::ExternalLevelMetadata, components::LevelSet};
+
+/// Handles keeping the assets of a lazily-loaded external level alive.
+#[derive(Clone, Debug)]
+pub(crate) struct RequestedLevel {
+    /// Handle for the level's `.ldtkl` file.
+    pub(crate) level: Handle<LdtkExternalLevel>,
+    /// Handle for the level's background image, if it has one.
+    pub(crate) bg_image: Option<Handle<Image>>,
+}
+
+/// [`Resource`] storing the requested assets of lazily-loaded external levels.
+///
+/// Levels are requested while they are in a [`LevelSet`], and keyed by the placeholder
+/// [`ExternalLevelMetadata::external_handle`] their data is moved to once loaded.
+/// This is maintained by the plugin, see
+/// [`LdtkProjectLoaderSettings::lazy_external_levels`] for more details.
+///
+/// [`Resource`]: https://docs.rs/bevy/latest/bevy/ecs/prelude/trait.Resource.html
+/// [`LdtkProjectLoaderSettings::lazy_external_levels`]: crate::assets::LdtkProjectLoaderSettings::lazy_external_levels
+#[derive(Clone, Debug, Default, Resource)]
+pub struct LazyExternalLevels {
+    pub(crate) requested: HashMap<AssetId<LdtkExternalLevel>, RequestedLevel>,
+}
+
+impl LazyExternalLevels {
+    /// Returns true if the assets of the level with the given placeholder handle are requested.
+    ///
+    /// Requested levels may still be loading.
+    pub fn is_requested(&self, id: AssetId<LdtkExternalLevel>) -> bool {
+        self.requested.contains_key(&id)
+    }
+}
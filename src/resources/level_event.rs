@@ -2,6 +2,9 @@ use bevy::prelude::*;
 
 use crate::LevelIid;
 
+#[allow(unused_imports)]
+use crate::resources::LevelSpawnBudget;
+
 /// Events fired by the plugin related to level spawning/despawning.
 ///
 /// Each variant stores the level's `iid` in LDtk.
@@ -11,6 +14,9 @@ pub enum LevelEvent {
     SpawnTriggered(LevelIid),
     /// The level, with all of its layers, entities, etc., has spawned.
     ///
+    /// If levels are spawned over multiple frames with [`LevelSpawnBudget::PerFrame`], this is
+    /// only fired after the last layer of the level has spawned.
+    ///
     /// Note: due to the frame-delay of [`GlobalTransform`] being updated, this may not be the
     /// event you want to listen for.
     /// If your systems are [`GlobalTransform`]-dependent, see [`LevelEvent::Transformed`].
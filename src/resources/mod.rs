@@ -12,6 +12,21 @@ pub use level_selection::LevelSelection;
 mod level_event;
 pub use level_event::LevelEvent;
 
+mod int_grid_changed;
+pub use int_grid_changed::IntGridChanged;
+
+mod iid_index;
+pub use iid_index::IidIndex;
+
+#[cfg(feature = "external_levels")]
+mod lazy_external_levels;
+
+#[cfg(feature = "external_levels")]
+pub use lazy_external_levels::LazyExternalLevels;
+
+#[cfg(feature = "external_levels")]
+pub(crate) use lazy_external_levels::RequestedLevel;
+
 /// Option in [LdtkSettings] that determines clear color behavior.
 #[derive(Copy, Clone, Default, Eq, PartialEq, Debug)]
 pub enum SetClearColor {
@@ -53,6 +68,75 @@ pub enum IntGridRendering {
     Invisible,
 }
 
+/// Option in [LdtkSettings] that determines how Tile and AutoLayer layers are spawned.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+pub enum TileLayerRendering {
+    /// Layers are spawned as `bevy_ecs_tilemap` tilemaps, with an entity for every tile.
+    #[default]
+    Tilemap,
+    /// Each layer is baked into a single image when it spawns, and rendered as one [`Sprite`].
+    ///
+    /// This drastically reduces entity counts and draw calls for large, purely decorative layers,
+    /// at the cost of the tiles no longer being entities.
+    /// So, tiles of these layers don't get [`TileMetadata`], [`TileEnumTags`], or registered
+    /// [`LdtkTile`] bundles, and aren't updated by [`AutoTileUpdates::OnIntGridChange`].
+    /// IntGrid layers are still spawned as tilemaps.
+    ///
+    /// The tileset image needs to be accessible on the CPU, so it can't be loaded with
+    /// [`RenderAssetUsages::RENDER_WORLD`] alone.
+    /// If the tileset can't be read, the layer is spawned as a tilemap instead.
+    ///
+    /// [`Sprite`]: https://docs.rs/bevy/latest/bevy/prelude/struct.Sprite.html
+    /// [`TileMetadata`]: crate::components::TileMetadata
+    /// [`TileEnumTags`]: crate::components::TileEnumTags
+    /// [`LdtkTile`]: crate::app::LdtkTile
+    /// [`RenderAssetUsages::RENDER_WORLD`]: https://docs.rs/bevy/latest/bevy/asset/struct.RenderAssetUsages.html
+    Baked,
+    /// Levels are rendered with the PNGs LDtk exported for them, if they were loaded with
+    /// [`LdtkProjectLoaderSettings::load_exported_images`].
+    ///
+    /// If an image was exported for the entire level, it is rendered as one [`Sprite`] above the
+    /// level background, and Tile and AutoLayer layers are spawned without any tiles.
+    /// Otherwise, each layer with an exported image is rendered as one [`Sprite`] instead.
+    /// Either way, IntGrid layers still spawn their tilemaps so their cells remain available for
+    /// gameplay, but these tilemaps are hidden.
+    /// Entity layers are unaffected, and layers without exported images are spawned as tilemaps.
+    ///
+    /// Like [`TileLayerRendering::Baked`], tiles drawn by exported images don't get
+    /// [`TileMetadata`] or [`TileEnumTags`], and aren't updated at runtime.
+    ///
+    /// [`LdtkProjectLoaderSettings::load_exported_images`]: crate::assets::LdtkProjectLoaderSettings::load_exported_images
+    /// [`Sprite`]: https://docs.rs/bevy/latest/bevy/prelude/struct.Sprite.html
+    /// [`TileMetadata`]: crate::components::TileMetadata
+    /// [`TileEnumTags`]: crate::components::TileEnumTags
+    ExportedImages,
+}
+
+/// Option in [LdtkSettings] that determines whether IntGrid cells are spawned as entities.
+///
+/// Either way, the values of each IntGrid layer are stored in an [`IntGridValues`] component on
+/// the layer entity.
+///
+/// [`IntGridValues`]: crate::components::IntGridValues
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+pub enum IntGridStorage {
+    /// Every non-empty IntGrid cell is spawned as an entity with an [`IntGridCell`] component.
+    ///
+    /// [`IntGridCell`]: crate::components::IntGridCell
+    #[default]
+    CellEntities,
+    /// IntGrid cells are only spawned as entities if an [`LdtkIntCell`] bundle is registered for
+    /// them, so [`IntGridValues`] is the only record of most cells.
+    ///
+    /// This is much cheaper for large layers that are only used for collision or other lookups.
+    /// Tiles that are visible, like those of [`IntGridRendering::Colorful`] layers, are still
+    /// spawned, but without IntGrid components.
+    ///
+    /// [`LdtkIntCell`]: crate::app::LdtkIntCell
+    /// [`IntGridValues`]: crate::components::IntGridValues
+    Dense,
+}
+
 /// Option in [LdtkSettings] that dictates how the plugin handles level backgrounds.
 #[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
 pub enum LevelBackground {
@@ -64,6 +148,121 @@ pub enum LevelBackground {
     Nonexistent,
 }
 
+/// Option in [LdtkSettings] that determines whether the IntGrid cells of levels are merged into
+/// rectangles.
+///
+/// See [`IntGridRects`] for more details.
+///
+/// [`IntGridRects`]: crate::components::IntGridRects
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+pub enum IntGridRectMerging {
+    /// IntGrid cells are not merged, and levels don't have an [`IntGridRects`] component.
+    ///
+    /// [`IntGridRects`]: crate::components::IntGridRects
+    #[default]
+    Disabled,
+    /// IntGrid cells are merged into rectangles per IntGrid value, and stored in an
+    /// [`IntGridRects`] component on the level entity.
+    ///
+    /// [`IntGridRects`]: crate::components::IntGridRects
+    Enabled,
+}
+
+/// Option in [LdtkSettings] that determines how much of a level is spawned per frame.
+///
+/// Spawning a big level all at once can cause a noticeable hitch.
+/// Limiting the amount spawned per frame spreads this work out over multiple frames, at the cost
+/// of levels taking longer to fully appear.
+///
+/// The progress of levels spawned this way is stored in a [`LevelSpawnProgress`] component on the
+/// level entity, and [`LevelEvent::Spawned`] is only fired once the level has finished spawning.
+///
+/// [`LevelSpawnProgress`]: crate::components::LevelSpawnProgress
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+pub enum LevelSpawnBudget {
+    /// Levels are spawned entirely in a single frame.
+    #[default]
+    Unlimited,
+    /// Levels are spawned layer-by-layer, with every frame spawning tiles and entities until the
+    /// given amount has been reached.
+    ///
+    /// This budget is shared by all levels spawning in the same frame.
+    /// Big layers are split across frames, spawning whole rows of tiles at a time, so at least one
+    /// row of tiles is spawned every frame even if it exceeds the budget on its own.
+    PerFrame {
+        /// Approximate number of tiles and entities to spawn per frame.
+        tiles_and_entities: usize,
+    },
+}
+
+/// Option in [LdtkSettings] that determines whether auto-layer tiles are updated when IntGrid
+/// values change at runtime.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+pub enum AutoTileUpdates {
+    /// Auto-layer tiles are spawned from the tiles baked into the LDtk project, and never change.
+    #[default]
+    Baked,
+    /// When an IntGrid value is changed with [`LdtkIntGridMut`], the auto-layer rules of its
+    /// layer, and of any auto-layer using it as a source, are evaluated again at the cells whose
+    /// rule patterns include the changed cell.
+    /// The existing tilemaps are then updated in place.
+    /// The current tiles of each layer are stored in its [`AutoLayerTiles`].
+    ///
+    /// This makes destructible or placeable terrain possible without respawning the level.
+    ///
+    /// Tiles that need more overlapping tilemaps than the layer was spawned with are skipped.
+    /// Perlin filtering and biomes aren't supported, so rules using them are applied wherever
+    /// their pattern matches.
+    /// Tiles of other cells are kept as LDtk generated them, but randomly picked tiles of cells
+    /// that are evaluated again may differ from the ones LDtk would pick.
+    ///
+    /// Only layers spawned with this setting are updated.
+    ///
+    /// [`LdtkIntGridMut`]: crate::LdtkIntGridMut
+    /// [`AutoLayerTiles`]: crate::components::AutoLayerTiles
+    OnIntGridChange,
+}
+
+/// Option in [LdtkSettings] that determines how spawned worlds respond to their [`LdtkProject`]
+/// being modified, like when it is saved in LDtk while the game is running.
+///
+/// [`LdtkProject`]: crate::assets::LdtkProject
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+pub enum HotReloadBehavior {
+    /// Every world using the modified project is respawned, along with all of its levels.
+    ///
+    /// All runtime state of the spawned levels and [`Worldly`] entities is lost.
+    ///
+    /// [`Worldly`]: crate::components::Worldly
+    #[default]
+    RespawnWorlds,
+    /// The old and new versions of the project are compared, and only levels that changed are
+    /// respawned.
+    /// Entities in untouched levels, and their runtime state, are preserved.
+    ///
+    /// If only some layers of a level changed, only those layers are respawned, and no
+    /// [`LevelEvent`]s are fired for the level.
+    /// The level is marked with [`RespawnLayers`] until then.
+    /// The entire level is respawned instead if its other data changed, if it hasn't finished
+    /// spawning, or if it is rendered with [`TileLayerRendering::ExportedImages`].
+    ///
+    /// Levels that were moved in the world or removed from the project are despawned, and then
+    /// spawned again if they are still in the [`LevelSet`].
+    /// Changes that can affect every level, like changes to layer, entity, or tileset
+    /// definitions, still respawn the entire world.
+    ///
+    /// Entities with [`PatchOnHotReload`] in changed levels are patched in place rather than
+    /// respawned, preserving components added at runtime.
+    ///
+    /// This requires keeping a copy of the project data, and of any external levels, around to
+    /// compare against, so it is best suited for development.
+    ///
+    /// [`LevelSet`]: crate::components::LevelSet
+    /// [`PatchOnHotReload`]: crate::components::PatchOnHotReload
+    /// [`RespawnLayers`]: crate::components::RespawnLayers
+    RespawnChangedLevels,
+}
+
 /// Specifies data that should be ignored completely when spawning levels. Excluded items will still
 /// be present in the [`LdtkProject`] but will not cause any entities to be spawned in the world.
 #[derive(Clone, Eq, PartialEq, Debug, Default)]
@@ -79,6 +278,12 @@ pub struct LdtkSettings {
     pub level_spawn_behavior: LevelSpawnBehavior,
     pub set_clear_color: SetClearColor,
     pub int_grid_rendering: IntGridRendering,
+    pub int_grid_storage: IntGridStorage,
+    pub tile_layer_rendering: TileLayerRendering,
     pub level_background: LevelBackground,
     pub exclusions: SpawnExclusions,
+    pub int_grid_rect_merging: IntGridRectMerging,
+    pub level_spawn_budget: LevelSpawnBudget,
+    pub hot_reload_behavior: HotReloadBehavior,
+    pub auto_tile_updates: AutoTileUpdates,
 }
@@ -3,50 +3,177 @@
 #[cfg(feature = "render")]
 use crate::resources::SetClearColor;
 use crate::{
-    app::{LdtkEntityMap, LdtkIntCellMap},
+    app::{LdtkEntityMap, LdtkEntityTagMap, LdtkIntCellIdentifierMap, LdtkIntCellMap, LdtkTileMap},
     assets::{LdtkProject, LdtkProjectData, LevelMetadataAccessor},
+    auto_layer::{
+        auto_layer_reach, reevaluate_auto_layer_tiles, AutoLayerInstance, AutoLayerSource,
+    },
     components::*,
-    ldtk::{Level, TilesetDefinition},
-    level::spawn_level,
-    resources::{LdtkSettings, LevelEvent, LevelSelection, LevelSpawnBehavior},
+    ldtk::{
+        raw_level_accessor::RawLevelAccessor, LdtkJson, Level, TileInstance, TilesetDefinition,
+    },
+    level::{respawn_layers, spawn_level, spawned_layer_instances, tile_metadata_maps},
+    resources::{
+        AutoTileUpdates, HotReloadBehavior, IidIndex, IntGridChanged, LdtkSettings, LevelEvent,
+        LevelSelection, LevelSpawnBehavior, LevelSpawnBudget, TileLayerRendering,
+    },
     utils::*,
 };
 
 #[cfg(feature = "external_levels")]
-use crate::assets::LdtkExternalLevel;
+use crate::{
+    assets::{ExternalLevels, LdtkExternalLevel, LdtkJsonWithMetadata},
+    resources::{LazyExternalLevels, RequestedLevel},
+};
+
+#[cfg(feature = "external_levels")]
+use bevy::asset::LoadState;
+
+use bevy::{ecs::system::SystemState, prelude::*, transform::helper::TransformHelper};
+use bevy_ecs_tilemap::{
+    map::TilemapId,
+    tiles::{TileBundle, TileColor, TileFlip, TilePos, TileStorage, TileTextureIndex, TileVisible},
+};
+use std::{
+    borrow::Cow,
+    collections::{HashMap, HashSet},
+    ops::Range,
+};
+
+/// How a level changed between two versions of an [LdtkProject], for hot reloading.
+#[derive(Clone, Eq, PartialEq, Debug)]
+enum LevelChange {
+    /// The level's contents changed, but it is still in the same place.
+    Modified,
+    /// Only the layers with these iids changed.
+    LayersModified(HashSet<String>),
+    /// The level was moved or resized in the world.
+    Moved,
+    /// The level no longer exists in the project.
+    Removed,
+}
+
+/// Compares two versions of a level, returning [None] if it didn't change.
+fn level_change(old: &Level, new: &Level) -> Option<LevelChange> {
+    if (old.world_x, old.world_y, old.px_hei) != (new.world_x, new.world_y, new.px_hei) {
+        return Some(LevelChange::Moved);
+    }
+
+    if old == new {
+        return None;
+    }
+
+    let (Some(old_layers), Some(new_layers)) = (&old.layer_instances, &new.layer_instances) else {
+        return Some(LevelChange::Modified);
+    };
 
-use bevy::{ecs::system::SystemState, prelude::*};
-use std::collections::{HashMap, HashSet};
+    // Layers are only respawned individually if nothing else about the level changed, including
+    // which layers it has and their order.
+    let without_layers = |level: &Level| Level {
+        layer_instances: None,
+        ..level.clone()
+    };
+
+    if old_layers
+        .iter()
+        .map(|layer| &layer.iid)
+        .ne(new_layers.iter().map(|layer| &layer.iid))
+        || without_layers(old) != without_layers(new)
+    {
+        return Some(LevelChange::Modified);
+    }
+
+    Some(LevelChange::LayersModified(
+        old_layers
+            .iter()
+            .zip(new_layers)
+            .filter(|(old_layer, new_layer)| old_layer != new_layer)
+            .map(|(_, new_layer)| new_layer.iid.clone())
+            .collect(),
+    ))
+}
+
+/// Compares the levels of two versions of a project.
+///
+/// Returns [None] if the projects differ in ways that can affect every level, like their
+/// definitions.
+fn level_changes(old: &LdtkJson, new: &LdtkJson) -> Option<HashMap<LevelIid, LevelChange>> {
+    if old.defs != new.defs {
+        return None;
+    }
+
+    let new_levels = new
+        .iter_raw_levels()
+        .map(|level| (level.iid.as_str(), level))
+        .collect::<HashMap<_, _>>();
+
+    Some(
+        old.iter_raw_levels()
+            .filter_map(|old_level| {
+                let change = match new_levels.get(old_level.iid.as_str()) {
+                    None => LevelChange::Removed,
+                    Some(new_level) => level_change(old_level, new_level)?,
+                };
+
+                Some((LevelIid::new(old_level.iid.clone()), change))
+            })
+            .collect(),
+    )
+}
 
 /// Detects [LdtkProject] events and spawns levels as children of the [LdtkWorldBundle].
-#[allow(clippy::too_many_arguments)]
+///
+/// When a project is modified, its worlds are respawned according to the
+/// [HotReloadBehavior] in [LdtkSettings].
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
 pub fn process_ldtk_assets(
     mut commands: Commands,
     mut ldtk_project_events: MessageReader<AssetEvent<LdtkProject>>,
+    #[cfg(feature = "external_levels")] mut external_level_events: MessageReader<
+        AssetEvent<LdtkExternalLevel>,
+    >,
     ldtk_world_query: Query<(Entity, &LdtkProjectHandle)>,
-    #[cfg(feature = "render")] ldtk_settings: Res<LdtkSettings>,
+    ldtk_level_query: Query<(
+        Entity,
+        &LevelIid,
+        &ChildOf,
+        Option<&LevelSpawnProgress>,
+        Option<&RespawnLayers>,
+    )>,
+    ldtk_settings: Res<LdtkSettings>,
     #[cfg(feature = "render")] mut clear_color: ResMut<ClearColor>,
-    #[cfg(feature = "render")] ldtk_project_assets: Res<Assets<LdtkProject>>,
+    ldtk_project_assets: Res<Assets<LdtkProject>>,
+    #[cfg(feature = "external_levels")] level_assets: Res<Assets<LdtkExternalLevel>>,
+    mut level_events: MessageWriter<LevelEvent>,
+    mut project_snapshots: Local<HashMap<AssetId<LdtkProject>, LdtkJson>>,
+    #[cfg(feature = "external_levels")] mut level_snapshots: Local<
+        HashMap<AssetId<LdtkExternalLevel>, Level>,
+    >,
 ) {
     let mut ldtk_handles_to_respawn = HashSet::new();
     let mut ldtk_handles_for_clear_color = HashSet::new();
+    let mut ldtk_handles_to_snapshot = HashSet::new();
 
     for event in ldtk_project_events.read() {
         match event {
             AssetEvent::LoadedWithDependencies { id } => {
                 debug!("LDtk asset creation detected.");
-                ldtk_handles_for_clear_color.insert(id);
+                ldtk_handles_for_clear_color.insert(*id);
+                ldtk_handles_to_snapshot.insert(*id);
             }
             AssetEvent::Modified { id } => {
                 info!("LDtk asset modification detected.");
-                ldtk_handles_to_respawn.insert(id);
-                ldtk_handles_for_clear_color.insert(id);
+                ldtk_handles_to_respawn.insert(*id);
+                ldtk_handles_for_clear_color.insert(*id);
+                ldtk_handles_to_snapshot.insert(*id);
             }
             AssetEvent::Removed { id } => {
                 info!("LDtk asset removal detected.");
                 // if mesh was modified and removed in the same update, ignore the modification
                 // events are ordered so future modification events are ok
-                ldtk_handles_to_respawn.retain(|changed_id| *changed_id != id);
+                ldtk_handles_to_respawn.retain(|changed_id| changed_id != id);
+                ldtk_handles_to_snapshot.retain(|changed_id| changed_id != id);
+                project_snapshots.remove(id);
             }
             _ => (),
         }
@@ -55,17 +182,152 @@ pub fn process_ldtk_assets(
     #[cfg(feature = "render")]
     if ldtk_settings.set_clear_color == SetClearColor::FromEditorBackground {
         for handle in ldtk_handles_for_clear_color.iter() {
-            if let Some(project) = &ldtk_project_assets.get(**handle) {
+            if let Some(project) = &ldtk_project_assets.get(*handle) {
                 clear_color.0 = project.json_data().bg_color;
             }
         }
     }
 
+    let mut level_changes_to_apply: HashMap<AssetId<LdtkProject>, HashMap<LevelIid, LevelChange>> =
+        HashMap::new();
+
+    if ldtk_settings.hot_reload_behavior == HotReloadBehavior::RespawnChangedLevels {
+        for id in ldtk_handles_to_snapshot {
+            let Some(project) = ldtk_project_assets.get(id) else {
+                continue;
+            };
+
+            let old_json = project_snapshots.insert(id, project.json_data().clone());
+
+            if !ldtk_handles_to_respawn.contains(&id) {
+                continue;
+            }
+
+            if let Some(changes) =
+                old_json.and_then(|old_json| level_changes(&old_json, project.json_data()))
+            {
+                ldtk_handles_to_respawn.remove(&id);
+                level_changes_to_apply.insert(id, changes);
+            }
+        }
+    } else {
+        project_snapshots.clear();
+    }
+
+    #[cfg(feature = "external_levels")]
+    for event in external_level_events.read() {
+        if ldtk_settings.hot_reload_behavior != HotReloadBehavior::RespawnChangedLevels {
+            level_snapshots.clear();
+            continue;
+        }
+
+        // Lazily-loaded levels are moved to their placeholder handles, which only emits Added
+        let id = match event {
+            AssetEvent::Added { id }
+            | AssetEvent::LoadedWithDependencies { id }
+            | AssetEvent::Modified { id } => id,
+            AssetEvent::Removed { id } => {
+                level_snapshots.remove(id);
+                continue;
+            }
+            _ => continue,
+        };
+
+        let Some(level) = level_assets.get(*id) else {
+            continue;
+        };
+
+        let old_level = level_snapshots.insert(*id, level.data().raw().clone());
+
+        if !matches!(event, AssetEvent::Modified { .. }) {
+            continue;
+        }
+
+        let change = match old_level {
+            Some(old_level) => level_change(&old_level, level.data().raw()),
+            None => Some(LevelChange::Modified),
+        };
+
+        let Some(change) = change else {
+            continue;
+        };
+
+        for (project_id, project) in ldtk_project_assets.iter() {
+            let Ok(parent): Result<&LdtkJsonWithMetadata<ExternalLevels>, _> =
+                project.data().try_into()
+            else {
+                continue;
+            };
+
+            for (level_iid, metadata) in parent.level_map() {
+                if metadata.external_handle().id() == *id {
+                    info!("LDtk external level modification detected.");
+                    level_changes_to_apply
+                        .entry(project_id)
+                        .or_default()
+                        .entry(LevelIid::new(level_iid.clone()))
+                        .or_insert(change.clone());
+                }
+            }
+        }
+    }
+
     for (entity, handle) in ldtk_world_query.iter() {
         if ldtk_handles_to_respawn.contains(&handle.id()) {
             commands.entity(entity).insert(Respawn);
         }
     }
+
+    if level_changes_to_apply.is_empty() {
+        return;
+    }
+
+    for (level_entity, level_iid, child_of, spawn_progress, pending_layers) in
+        ldtk_level_query.iter()
+    {
+        let Some((handle, change)) = ldtk_world_query
+            .get(child_of.parent())
+            .ok()
+            .filter(|(_, handle)| !ldtk_handles_to_respawn.contains(&handle.id()))
+            .and_then(|(_, handle)| {
+                let change = level_changes_to_apply.get(&handle.id())?.get(level_iid)?;
+                Some((handle, change))
+            })
+        else {
+            continue;
+        };
+
+        // Levels that are still spawning, or whose layers may be drawn by a single image LDtk
+        // exported, are respawned entirely.
+        let respawn_entire_level = spawn_progress.is_some_and(|progress| !progress.is_finished())
+            || ldtk_settings.tile_layer_rendering == TileLayerRendering::ExportedImages
+            || ldtk_project_assets
+                .get(handle)
+                .is_some_and(|project| *project.simplified_export());
+
+        match change {
+            LevelChange::LayersModified(layer_iids) if !respawn_entire_level => {
+                let mut layer_iids = layer_iids.clone();
+                if let Some(pending_layers) = pending_layers {
+                    layer_iids.extend(pending_layers.layer_iids().iter().cloned());
+                }
+
+                commands
+                    .entity(level_entity)
+                    .insert(RespawnLayers::new(layer_iids));
+            }
+            LevelChange::Modified | LevelChange::LayersModified(_) => {
+                commands
+                    .entity(level_entity)
+                    .insert((Respawn, HotReloadRespawn));
+            }
+            LevelChange::Moved | LevelChange::Removed => {
+                // apply_level_set will spawn the level again if it's still in the LevelSet
+                commands.entity(level_entity).despawn();
+                level_events.write(LevelEvent::Despawned(level_iid.clone()));
+            }
+        }
+    }
 }
 
 /// Updates all LevelSet components according to the LevelSelection
@@ -118,7 +380,151 @@ pub fn apply_level_selection(
     }
 }
 
+/// Updates all LevelSet components according to the positions of [LevelStreamingFocus] entities.
+pub fn apply_level_streaming(
+    focus_query: Query<(&LevelStreamingFocus, &GlobalTransform)>,
+    ldtk_project_assets: Res<Assets<LdtkProject>>,
+    mut level_set_query: Query<(&LdtkProjectHandle, &GlobalTransform, &mut LevelSet)>,
+) {
+    if focus_query.is_empty() {
+        return;
+    }
+
+    for (ldtk_handle, world_transform, mut level_set) in level_set_query.iter_mut() {
+        let Some(project) = ldtk_project_assets.get(ldtk_handle) else {
+            continue;
+        };
+
+        let world_affine_inverse = world_transform.affine().inverse();
+        let foci: Vec<_> = focus_query
+            .iter()
+            .map(|(focus, focus_transform)| {
+                let position = world_affine_inverse
+                    .transform_point3(focus_transform.translation())
+                    .truncate();
+                (*focus, position)
+            })
+            .collect();
+
+        let new_level_set = streamed_level_set(project.iter_raw_levels(), &level_set, &foci);
+
+        level_set.set_if_neq(new_level_set);
+    }
+}
+
+/// Requests the assets of lazily-loaded external levels that are in a [LevelSet], and unloads them
+/// once they leave every [LevelSet].
+///
+/// Loaded level data is moved to the placeholder handle stored in the project's
+/// [ExternalLevelMetadata], so it can be accessed like the data of any other external level.
+///
+/// [ExternalLevelMetadata]: crate::assets::ExternalLevelMetadata
+#[cfg(feature = "external_levels")]
+pub fn load_lazy_external_levels(
+    ldtk_world_query: Query<(&LevelSet, &LdtkProjectHandle)>,
+    ldtk_project_assets: Res<Assets<LdtkProject>>,
+    mut level_assets: ResMut<Assets<LdtkExternalLevel>>,
+    mut lazy_levels: ResMut<LazyExternalLevels>,
+    asset_server: Res<AssetServer>,
+) {
+    let mut requested_ids = HashSet::new();
+
+    for (level_set, ldtk_handle) in ldtk_world_query.iter() {
+        let Some(project) = ldtk_project_assets.get(ldtk_handle) else {
+            continue;
+        };
+        let Ok(parent): Result<&LdtkJsonWithMetadata<ExternalLevels>, _> =
+            project.data().try_into()
+        else {
+            continue;
+        };
+
+        for level_iid in &level_set.iids {
+            let Some(metadata) = parent.level_map().get(level_iid.get()) else {
+                continue;
+            };
+            let Some(lazy_paths) = metadata.lazy_paths() else {
+                continue;
+            };
+
+            let id = metadata.external_handle().id();
+            requested_ids.insert(id);
+
+            lazy_levels
+                .requested
+                .entry(id)
+                .or_insert_with(|| RequestedLevel {
+                    level: asset_server.load(lazy_paths.level().clone()),
+                    bg_image: lazy_paths
+                        .bg_image()
+                        .as_ref()
+                        .map(|path| asset_server.load(path.clone())),
+                });
+        }
+    }
+
+    // Unload levels that left every level set
+    lazy_levels.requested.retain(|id, _| {
+        let still_requested = requested_ids.contains(id);
+        if !still_requested {
+            level_assets.remove(*id);
+        }
+        still_requested
+    });
+
+    // Move newly loaded, or hot-reloaded, level data to the placeholder handles
+    for (id, requested) in lazy_levels.requested.iter() {
+        if let Some(level) = level_assets.remove(requested.level.id()) {
+            level_assets
+                .insert(*id, level)
+                .expect("placeholder handles of lazy external levels should be uuid handles");
+        }
+    }
+}
+
+/// Returns true if the level can be spawned, i.e. it isn't a lazily-loaded external level still
+/// waiting on its assets.
+#[cfg(feature = "external_levels")]
+fn lazy_level_ready(
+    project: &LdtkProject,
+    level_iid: &LevelIid,
+    level_assets: &Assets<LdtkExternalLevel>,
+    lazy_levels: &LazyExternalLevels,
+    asset_server: &AssetServer,
+) -> bool {
+    let Ok(parent): Result<&LdtkJsonWithMetadata<ExternalLevels>, _> = project.data().try_into()
+    else {
+        return true;
+    };
+    let Some(metadata) = parent
+        .level_map()
+        .get(level_iid.get())
+        .filter(|metadata| metadata.lazy_paths().is_some())
+    else {
+        return true;
+    };
+
+    let id = metadata.external_handle().id();
+
+    // Levels with missing background images are still spawned, just without them
+    let bg_image_settled = |bg_image: &Handle<Image>| {
+        asset_server.is_loaded_with_dependencies(bg_image)
+            || matches!(
+                asset_server.get_load_state(bg_image),
+                Some(LoadState::Failed(_))
+            )
+    };
+
+    level_assets.contains(id)
+        && lazy_levels
+            .requested
+            .get(&id)
+            .is_some_and(|requested| requested.bg_image.as_ref().is_none_or(bg_image_settled))
+}
+
 /// Triggers the spawning/despawning of levels according to `LevelSet` values.
+///
+/// Lazily-loaded external levels are only spawned once their own assets have loaded.
 #[allow(clippy::too_many_arguments, clippy::type_complexity)]
 pub fn apply_level_set(
     mut commands: Commands,
@@ -134,6 +540,8 @@ pub fn apply_level_set(
     ldtk_settings: Res<LdtkSettings>,
     asset_server: Res<AssetServer>,
     mut level_events: MessageWriter<LevelEvent>,
+    #[cfg(feature = "external_levels")] level_assets: Res<Assets<LdtkExternalLevel>>,
+    #[cfg(feature = "external_levels")] lazy_levels: Res<LazyExternalLevels>,
 ) {
     for (world_entity, level_set, children, ldtk_asset_handle, respawn) in ldtk_world_query.iter() {
         // Only apply level set if the asset has finished loading
@@ -159,9 +567,18 @@ pub fn apply_level_set(
 
         let level_set_as_ref = level_set.iids.iter().collect::<HashSet<_>>();
 
+        #[cfg(feature = "external_levels")]
+        let level_ready = |iid: &LevelIid| {
+            lazy_level_ready(project, iid, &level_assets, &lazy_levels, &asset_server)
+        };
+
+        #[cfg(not(feature = "external_levels"))]
+        let level_ready = |_: &LevelIid| true;
+
         // Spawn levels that should be spawned but aren't
         let spawned_levels = level_set_as_ref
             .difference(&previous_iids)
+            .filter(|&&iid| level_ready(iid))
             .filter_map(|&iid| project.get_raw_level_by_iid(iid.get()))
             .map(|level| {
                 level_events.write(LevelEvent::SpawnTriggered(LevelIid::new(level.iid.clone())));
@@ -216,24 +633,62 @@ fn pre_spawn_level(commands: &mut Commands, level: &Level, ldtk_settings: &LdtkS
 
 /// Performs all the spawning of levels, layers, chunks, bundles, entities, tiles, etc. when a
 /// LevelIid is added or respawned.
+///
+/// With [LevelSpawnBudget::PerFrame], levels may take multiple updates to spawn.
+/// Levels with an unfinished [LevelSpawnProgress] continue spawning where they left off.
 #[allow(clippy::too_many_arguments, clippy::type_complexity)]
 pub fn process_ldtk_levels(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
-    images: ResMut<Assets<Image>>,
+    mut images: ResMut<Assets<Image>>,
     mut texture_atlases: ResMut<Assets<TextureAtlasLayout>>,
     ldtk_project_assets: Res<Assets<LdtkProject>>,
     #[cfg(feature = "external_levels")] level_assets: Res<Assets<LdtkExternalLevel>>,
-    ldtk_entity_map: NonSend<LdtkEntityMap>,
-    ldtk_int_cell_map: NonSend<LdtkIntCellMap>,
+    #[cfg(feature = "external_levels")] lazy_levels: Res<LazyExternalLevels>,
+    (ldtk_entity_map, ldtk_entity_tag_map): (NonSend<LdtkEntityMap>, NonSend<LdtkEntityTagMap>),
+    (ldtk_int_cell_map, ldtk_int_cell_identifier_map): (
+        NonSend<LdtkIntCellMap>,
+        NonSend<LdtkIntCellIdentifierMap>,
+    ),
+    ldtk_tile_map: NonSend<LdtkTileMap>,
     ldtk_query: Query<&LdtkProjectHandle>,
-    level_query: Query<(Entity, &LevelIid, &ChildOf), With<Respawn>>,
+    mut level_query: Query<
+        (
+            Entity,
+            &LevelIid,
+            &ChildOf,
+            Has<Respawn>,
+            Option<&mut LevelSpawnProgress>,
+            Option<&RespawnLayers>,
+        ),
+        Or<(With<Respawn>, With<LevelSpawnProgress>, With<RespawnLayers>)>,
+    >,
     worldly_query: Query<&Worldly>,
+    patch_query: Query<(Entity, &EntityIid, &ChildOf), With<PatchOnHotReload>>,
     mut level_events: MessageWriter<LevelEvent>,
     ldtk_settings: Res<LdtkSettings>,
 ) {
+    let mut budget = match ldtk_settings.level_spawn_budget {
+        LevelSpawnBudget::Unlimited => usize::MAX,
+        LevelSpawnBudget::PerFrame { tiles_and_entities } => tiles_and_entities.max(1),
+    };
+
     let mut worldly_set = None;
-    for (ldtk_entity, level_iid, child_of) in level_query.iter() {
+    for (ldtk_entity, level_iid, child_of, respawn, mut spawn_progress, layers_to_respawn) in
+        level_query.iter_mut()
+    {
+        // Respawning the entire level supersedes respawning some of its layers
+        let layers_to_respawn = layers_to_respawn.filter(|_| !respawn);
+
+        if !respawn
+            && layers_to_respawn.is_none()
+            && spawn_progress
+                .as_ref()
+                .is_none_or(|progress| progress.is_finished())
+        {
+            continue;
+        }
+
         // Ensure the project is loaded.
         let Ok(ldtk_handle) = ldtk_query.get(child_of.parent()) else {
             continue;
@@ -269,7 +724,7 @@ pub fn process_ldtk_levels(
                         let loaded_level =
                             project.get_loaded_level_at_indices(level_metadata.indices())?;
 
-                        Some((level_metadata, loaded_level))
+                        Some((Cow::Borrowed(level_metadata), loaded_level))
                     }),
                 #[cfg(feature = "external_levels")]
                 LdtkProjectData::Parent(project) => project
@@ -281,22 +736,114 @@ pub fn process_ldtk_levels(
                             level_metadata.metadata().indices(),
                         )?;
 
-                        Some((level_metadata.metadata(), loaded_level))
+                        // Background images of lazily-loaded levels aren't in the project
+                        let metadata = match (
+                            level_metadata.lazy_paths(),
+                            lazy_levels
+                                .requested
+                                .get(&level_metadata.external_handle().id()),
+                        ) {
+                            (Some(_), Some(requested)) => Cow::Owned(
+                                level_metadata
+                                    .metadata()
+                                    .clone()
+                                    .with_bg_image(requested.bg_image.clone()),
+                            ),
+                            _ => Cow::Borrowed(level_metadata.metadata()),
+                        };
+
+                        Some((metadata, loaded_level))
                     }),
             };
 
         if let Some((level_metadata, loaded_level)) = maybe_level_data {
             let worldly_set =
                 worldly_set.get_or_insert_with(|| worldly_query.iter().cloned().collect());
+
+            // Entities preserved by hot reloading are patched rather than spawned.
+            let mut patch_targets = patch_query
+                .iter()
+                .filter(|(_, _, child_of)| child_of.parent() == ldtk_entity)
+                .map(|(entity, entity_iid, _)| (entity_iid.clone(), entity))
+                .collect::<HashMap<_, _>>();
+
+            // Projects synthesized from LDtk's super simple export can only be rendered with
+            // their exported images.
+            let level_settings = if *ldtk_project.simplified_export() {
+                Cow::Owned(LdtkSettings {
+                    tile_layer_rendering: TileLayerRendering::ExportedImages,
+                    ..ldtk_settings.clone()
+                })
+            } else {
+                Cow::Borrowed(&*ldtk_settings)
+            };
+
+            if let Some(layers_to_respawn) = layers_to_respawn {
+                let layers_respawned = respawn_layers(
+                    loaded_level,
+                    &level_metadata,
+                    &mut commands,
+                    &asset_server,
+                    &mut images,
+                    &mut texture_atlases,
+                    &ldtk_entity_map,
+                    &ldtk_entity_tag_map,
+                    &ldtk_int_cell_map,
+                    &ldtk_int_cell_identifier_map,
+                    &ldtk_tile_map,
+                    ldtk_project.parsed_tile_metadata(),
+                    &entity_definition_map,
+                    &layer_definition_map,
+                    ldtk_project.tileset_map(),
+                    &tileset_definition_map,
+                    int_grid_image_handle,
+                    worldly_set,
+                    ldtk_entity,
+                    &level_settings,
+                    layers_to_respawn,
+                    &mut patch_targets,
+                );
+
+                commands.entity(ldtk_entity).remove::<RespawnLayers>();
+
+                if layers_respawned {
+                    // Entity instances that no longer exist in the respawned layers
+                    for entity in patch_targets.into_values() {
+                        commands.entity(entity).despawn();
+                    }
+                } else {
+                    commands
+                        .entity(ldtk_entity)
+                        .insert((Respawn, HotReloadRespawn));
+                }
+
+                continue;
+            }
+
+            // Respawning levels start over, while others continue where they left off.
+            let mut new_progress = None;
+            let progress = match spawn_progress.as_deref_mut() {
+                Some(progress) if !respawn => progress,
+                _ => new_progress.insert(LevelSpawnProgress::new(
+                    spawned_layer_instances(loaded_level.layer_instances(), &ldtk_settings)
+                        .map(layer_spawn_cost)
+                        .sum(),
+                )),
+            };
+
             spawn_level(
                 loaded_level,
-                level_metadata.bg_image(),
+                &level_metadata,
                 &mut commands,
                 &asset_server,
-                &images,
+                &mut images,
                 &mut texture_atlases,
                 &ldtk_entity_map,
+                &ldtk_entity_tag_map,
                 &ldtk_int_cell_map,
+                &ldtk_int_cell_identifier_map,
+                &ldtk_tile_map,
+                ldtk_project.parsed_tile_metadata(),
                 &entity_definition_map,
                 &layer_definition_map,
                 ldtk_project.tileset_map(),
@@ -304,18 +851,102 @@ pub fn process_ldtk_levels(
                 int_grid_image_handle,
                 worldly_set,
                 ldtk_entity,
-                &ldtk_settings,
+                &level_settings,
+                progress,
+                &mut budget,
+                &mut patch_targets,
             );
-            level_events.write(LevelEvent::Spawned(LevelIid::new(
-                loaded_level.iid().clone(),
-            )));
+
+            if progress.is_finished() {
+                // Entity instances that no longer exist in the level
+                for entity in patch_targets.into_values() {
+                    commands.entity(entity).despawn();
+                }
+
+                level_events.write(LevelEvent::Spawned(LevelIid::new(
+                    loaded_level.iid().clone(),
+                )));
+            }
+
+            if let (Some(new_progress), LevelSpawnBudget::PerFrame { .. }) =
+                (new_progress, ldtk_settings.level_spawn_budget)
+            {
+                commands.entity(ldtk_entity).insert(new_progress);
+            }
+        }
+
+        if respawn {
+            commands
+                .entity(ldtk_entity)
+                .remove::<(Respawn, RespawnLayers)>();
         }
+    }
+}
+
+/// Updates the [IidIndex] as LDtk entities and levels spawn and despawn.
+pub fn update_iid_index(
+    mut iid_index: ResMut<IidIndex>,
+    added_entity_query: Query<(Entity, &EntityIid), Added<EntityIid>>,
+    added_level_query: Query<(Entity, &LevelIid), Added<LevelIid>>,
+    mut removed_entity_iids: RemovedComponents<EntityIid>,
+    mut removed_level_iids: RemovedComponents<LevelIid>,
+) {
+    let mut changed = false;
+
+    for entity in removed_entity_iids.read() {
+        changed |= iid_index.bypass_change_detection().remove_entity(entity);
+    }
+
+    for entity in removed_level_iids.read() {
+        changed |= iid_index.bypass_change_detection().remove_level(entity);
+    }
+
+    for (entity, entity_iid) in added_entity_query.iter() {
+        iid_index
+            .bypass_change_detection()
+            .insert_entity(entity_iid.clone(), entity);
+        changed = true;
+    }
+
+    for (entity, level_iid) in added_level_query.iter() {
+        iid_index
+            .bypass_change_detection()
+            .insert_level(level_iid.clone(), entity);
+        changed = true;
+    }
 
-        commands.entity(ldtk_entity).remove::<Respawn>();
+    if changed {
+        iid_index.set_changed();
     }
 }
 
-/// Performs the "despawning" portion of the respawn process for `Respawn` entities.
+/// Inserts [LdtkEntityRefs] on entities whose [LdtkEntityRefIids] are all spawned, and removes it
+/// from entities whose references are not.
+pub fn resolve_entity_refs(
+    mut commands: Commands,
+    iid_index: Res<IidIndex>,
+    ref_query: Query<(Entity, Ref<LdtkEntityRefIids>, Option<&LdtkEntityRefs>)>,
+) {
+    for (entity, ref_iids, entity_refs) in ref_query.iter() {
+        if !iid_index.is_changed() && !ref_iids.is_changed() {
+            continue;
+        }
+
+        match (ref_iids.resolve(&iid_index), entity_refs) {
+            (Some(new_refs), Some(old_refs)) if new_refs == *old_refs => (),
+            (Some(new_refs), _) => {
+                commands.entity(entity).try_insert(new_refs);
+            }
+            (None, Some(_)) => {
+                commands.entity(entity).try_remove::<LdtkEntityRefs>();
+            }
+            (None, None) => (),
+        }
+    }
+}
+
+/// Performs the "despawning" portion of the respawn process for `Respawn` entities, and for the
+/// changed layers of hot reloaded levels.
 ///
 /// This is currently an exclusive system for scheduling purposes.
 /// If we need to revert it to its non-exclusive form, copy it from commit
@@ -324,21 +955,31 @@ pub fn clean_respawn_entities(world: &mut World) {
     #[allow(clippy::type_complexity)]
     let mut system_state: SystemState<(
         Query<&Children, (With<LdtkProjectHandle>, With<Respawn>)>,
-        Query<(Entity, &LevelIid), With<Respawn>>,
+        Query<(Entity, &LevelIid, Has<HotReloadRespawn>), With<Respawn>>,
+        Query<(Entity, &RespawnLayers), Without<Respawn>>,
         Query<&LevelIid, Without<Respawn>>,
         Query<Entity, With<Worldly>>,
+        Query<&Children>,
+        Query<(&LayerMetadata, &Transform)>,
+        Query<(), With<PatchOnHotReload>>,
         MessageWriter<LevelEvent>,
     )> = SystemState::new(world);
 
     let mut entities_to_despawn_recursively = Vec::new();
     let mut entities_to_despawn_descendants = Vec::new();
+    let mut entities_to_patch = Vec::new();
+    let mut layer_z_to_record = Vec::new();
 
     {
         let (
             ldtk_worlds_to_clean,
             ldtk_levels_to_clean,
+            ldtk_levels_with_layers_to_clean,
             other_ldtk_levels,
             worldly_entities,
+            children_query,
+            layer_query,
+            patch_query,
             mut level_events,
         ) = system_state.get_mut(world).unwrap();
 
@@ -355,11 +996,69 @@ pub fn clean_respawn_entities(world: &mut World) {
             }
         }
 
-        for (level_entity, level_iid) in ldtk_levels_to_clean.iter() {
+        for (level_entity, level_iid, hot_reload) in ldtk_levels_to_clean.iter() {
             entities_to_despawn_descendants.push(level_entity);
 
+            if hot_reload {
+                // entities are children of the level's layers
+                let patched_entities = children_query
+                    .iter_descendants(level_entity)
+                    .filter(|entity| patch_query.contains(*entity))
+                    .collect::<Vec<_>>();
+
+                entities_to_patch.push((level_entity, patched_entities));
+            }
+
             level_events.write(LevelEvent::Despawned(level_iid.clone()));
         }
+
+        for (level_entity, respawn_layers) in ldtk_levels_with_layers_to_clean.iter() {
+            let mut layer_z: HashMap<String, Range<i32>> = HashMap::new();
+            let mut patched_entities = Vec::new();
+
+            for layer_entity in children_query
+                .get(level_entity)
+                .into_iter()
+                .flat_map(|children| children.iter())
+            {
+                let Ok((layer_metadata, transform)) = layer_query.get(layer_entity) else {
+                    continue;
+                };
+
+                if !respawn_layers.layer_iids().contains(&layer_metadata.iid) {
+                    continue;
+                }
+
+                // the layer's replacement is spawned at the same z values
+                let z = transform.translation.z as i32;
+                layer_z
+                    .entry(layer_metadata.iid.clone())
+                    .and_modify(|range| {
+                        range.start = range.start.min(z);
+                        range.end = range.end.max(z + 1);
+                    })
+                    .or_insert(z..z + 1);
+
+                patched_entities.extend(
+                    children_query
+                        .iter_descendants(layer_entity)
+                        .filter(|entity| patch_query.contains(*entity)),
+                );
+
+                entities_to_despawn_recursively.push(layer_entity);
+            }
+
+            entities_to_patch.push((level_entity, patched_entities));
+            layer_z_to_record.push((level_entity, layer_z));
+        }
+    }
+
+    // entities being patched are detached from the level while it is cleaned, then kept as
+    // direct children of the level until process_ldtk_levels patches them
+    for (_, patched_entities) in &entities_to_patch {
+        for entity in patched_entities {
+            world.entity_mut(*entity).remove::<ChildOf>();
+        }
     }
 
     for entity in entities_to_despawn_recursively {
@@ -369,6 +1068,19 @@ pub fn clean_respawn_entities(world: &mut World) {
     for entity in entities_to_despawn_descendants {
         world.entity_mut(entity).despawn_related::<Children>();
     }
+
+    for (level_entity, patched_entities) in entities_to_patch {
+        world
+            .entity_mut(level_entity)
+            .add_children(&patched_entities)
+            .remove::<HotReloadRespawn>();
+    }
+
+    for (level_entity, layer_z) in layer_z_to_record {
+        if let Some(mut respawn_layers) = world.get_mut::<RespawnLayers>(level_entity) {
+            respawn_layers.set_layer_z(layer_z);
+        }
+    }
 }
 
 /// Implements the functionality for `Worldly` components.
@@ -392,6 +1104,347 @@ pub fn worldly_adoption(
     }
 }
 
+/// Updates the [Transform]s of layers with [LayerParallax] according to the [ParallaxCamera] of
+/// their world.
+#[allow(clippy::type_complexity)]
+pub fn apply_layer_parallax(
+    mut transforms: ParamSet<(TransformHelper, Query<&mut Transform, With<LayerParallax>>)>,
+    layer_query: Query<(Entity, &LayerParallax, &ChildOf)>,
+    level_query: Query<&ChildOf, With<LevelIid>>,
+    parallax_camera_query: Query<&ParallaxCamera>,
+) {
+    let new_transforms = {
+        let transform_helper = transforms.p0();
+
+        layer_query
+            .iter()
+            .filter_map(|(layer_entity, layer_parallax, child_of)| {
+                let level_entity = child_of.parent();
+                let world_entity = level_query.get(level_entity).ok()?.parent();
+                let ParallaxCamera(camera_entity) = parallax_camera_query.get(world_entity).ok()?;
+
+                let camera_translation = transform_helper
+                    .compute_global_transform(*camera_entity)
+                    .ok()?
+                    .translation();
+                let level_transform = transform_helper
+                    .compute_global_transform(level_entity)
+                    .ok()?;
+
+                let camera_translation_in_level = level_transform
+                    .affine()
+                    .inverse()
+                    .transform_point3(camera_translation)
+                    .truncate();
+
+                Some((
+                    layer_entity,
+                    layer_parallax.transform(camera_translation_in_level),
+                ))
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let mut layer_transforms = transforms.p1();
+    for (layer_entity, new_transform) in new_transforms {
+        if let Ok(mut transform) = layer_transforms.get_mut(layer_entity) {
+            transform.set_if_neq(new_transform);
+        }
+    }
+}
+
+/// Evaluates auto-layer rules again at cells whose patterns include IntGrid values that changed,
+/// updating the [AutoLayerTiles] and tilemaps of the affected layers in place.
+///
+/// Only does anything with [AutoTileUpdates::OnIntGridChange].
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub fn update_auto_layer_tiles(
+    mut commands: Commands,
+    ldtk_settings: Res<LdtkSettings>,
+    ldtk_project_assets: Res<Assets<LdtkProject>>,
+    mut int_grid_changed: MessageReader<IntGridChanged>,
+    int_grid_values_query: Query<&IntGridValues>,
+    mut auto_layer_tiles_query: Query<&mut AutoLayerTiles>,
+    mut tilemap_query: Query<(
+        Entity,
+        &LayerMetadata,
+        &ChildOf,
+        &mut TileStorage,
+        &Transform,
+    )>,
+    level_query: Query<&ChildOf, With<LevelIid>>,
+    ldtk_world_query: Query<&LdtkProjectHandle>,
+    mut tile_query: Query<(
+        &mut TileTextureIndex,
+        &mut TileFlip,
+        &mut TileColor,
+        &mut TileVisible,
+    )>,
+) {
+    if ldtk_settings.auto_tile_updates != AutoTileUpdates::OnIntGridChange {
+        return;
+    }
+
+    // changed cells, in LDtk cell coordinates, per level and source layer
+    let mut changed_cells: HashMap<(Entity, i32), HashSet<IVec2>> = HashMap::new();
+    for IntGridChanged {
+        layer_entity,
+        grid_coords,
+        ..
+    } in int_grid_changed.read()
+    {
+        let Ok((_, layer_metadata, child_of, ..)) = tilemap_query.get(*layer_entity) else {
+            continue;
+        };
+
+        changed_cells
+            .entry((child_of.parent(), layer_metadata.layer_def_uid))
+            .or_default()
+            .insert(grid_coords_to_ldtk_grid_coords(
+                *grid_coords,
+                layer_metadata.c_hei,
+            ));
+    }
+
+    for ((level_entity, source_layer_def_uid), changed_cells) in changed_cells {
+        let Some(ldtk_project) = level_query
+            .get(level_entity)
+            .ok()
+            .and_then(|child_of| ldtk_world_query.get(child_of.parent()).ok())
+            .and_then(|handle| ldtk_project_assets.get(handle))
+        else {
+            continue;
+        };
+        let defs = &ldtk_project.json_data().defs;
+
+        let Some(source_layer_definition) = defs
+            .layers
+            .iter()
+            .find(|layer_definition| layer_definition.uid == source_layer_def_uid)
+        else {
+            continue;
+        };
+
+        // tilemaps of each layer in this level, in z order
+        let mut level_tilemaps: HashMap<i32, Vec<(f32, Entity)>> = HashMap::new();
+        for (tilemap_entity, layer_metadata, _, _, transform) in tilemap_query
+            .iter()
+            .filter(|(_, _, child_of, ..)| child_of.parent() == level_entity)
+        {
+            level_tilemaps
+                .entry(layer_metadata.layer_def_uid)
+                .or_default()
+                .push((transform.translation.z, tilemap_entity));
+        }
+        for tilemaps in level_tilemaps.values_mut() {
+            tilemaps.sort_by(|(a, _), (b, _)| a.total_cmp(b));
+        }
+
+        let Some(source_values) = level_tilemaps
+            .get(&source_layer_def_uid)
+            .into_iter()
+            .flatten()
+            .find_map(|(_, tilemap_entity)| int_grid_values_query.get(*tilemap_entity).ok())
+        else {
+            continue;
+        };
+        let (c_wid, c_hei) = (source_values.c_wid(), source_values.c_hei());
+
+        let source = AutoLayerSource::new(
+            source_values.values(),
+            c_wid,
+            c_hei,
+            source_layer_definition,
+        );
+        let layer_bounds = IRect::new(0, 0, c_wid - 1, c_hei - 1);
+
+        for target_layer_definition in defs.layers.iter().filter(|layer_definition| {
+            !layer_definition.auto_rule_groups.is_empty()
+                && (layer_definition.uid == source_layer_def_uid
+                    || layer_definition.auto_source_layer_def_uid == Some(source_layer_def_uid))
+        }) {
+            let Some(target_tilemaps) = level_tilemaps.get(&target_layer_definition.uid) else {
+                continue;
+            };
+            let Ok((_, target_metadata, ..)) = tilemap_query.get(target_tilemaps[0].1) else {
+                continue;
+            };
+            let target_metadata = target_metadata.clone();
+
+            let tileset_definition = target_metadata.tileset_def_uid.and_then(|uid| {
+                defs.tilesets
+                    .iter()
+                    .find(|tileset_definition| tileset_definition.uid == uid)
+            });
+            let (metadata_map, enum_tags_map) = tile_metadata_maps(tileset_definition);
+
+            let instance = AutoLayerInstance {
+                seed: target_metadata.seed,
+                optional_rules: &target_metadata.optional_rules,
+                c_wid,
+                grid_size: target_metadata.grid_size,
+                tileset_definition,
+            };
+
+            // only cells whose patterns include a changed cell are evaluated again, and their
+            // tiles can only be displayed within reach of them
+            let (pattern_radius, tile_reach) = auto_layer_reach(target_layer_definition, &instance);
+            let origins = cells_within(&changed_cells, pattern_radius, layer_bounds);
+            let display_cells = cells_within(&origins, tile_reach, layer_bounds);
+
+            if display_cells.is_empty() {
+                continue;
+            }
+
+            let Ok(mut auto_layer_tiles) = auto_layer_tiles_query.get_mut(target_tilemaps[0].1)
+            else {
+                continue;
+            };
+
+            reevaluate_auto_layer_tiles(
+                target_layer_definition,
+                &instance,
+                &source,
+                auto_layer_tiles.tiles_mut(),
+                &origins,
+            );
+
+            let mut tile_stacks: HashMap<IVec2, Vec<&TileInstance>> = HashMap::new();
+            for tile in auto_layer_tiles.tiles() {
+                let display_cell = IVec2::new(tile.px[0], tile.px[1])
+                    .div_euclid(IVec2::splat(target_metadata.grid_size));
+
+                if display_cells.contains(&display_cell) {
+                    tile_stacks.entry(display_cell).or_default().push(tile);
+                }
+            }
+
+            let mut overflowed = false;
+            for IVec2 { x, y } in display_cells {
+                let tile_stack = tile_stacks
+                    .get(&IVec2::new(x, y))
+                    .map(Vec::as_slice)
+                    .unwrap_or_default();
+                overflowed |= tile_stack.len() > target_tilemaps.len();
+
+                let tile_pos = TilePos::new(x as u32, (c_hei - 1 - y) as u32);
+
+                for (sublayer, (_, tilemap_entity)) in target_tilemaps.iter().enumerate() {
+                    let Ok((_, _, _, mut storage, _)) = tilemap_query.get_mut(*tilemap_entity)
+                    else {
+                        continue;
+                    };
+
+                    let tile_entity = match (tile_stack.get(sublayer), storage.get(&tile_pos)) {
+                        (Some(tile), Some(tile_entity)) => {
+                            if let Ok((mut texture_index, mut flip, mut color, mut visible)) =
+                                tile_query.get_mut(tile_entity)
+                            {
+                                *texture_index = TileTextureIndex(tile.t as u32);
+                                *flip = auto_tile_flip(tile);
+                                *color = TileColor(
+                                    Color::WHITE.with_alpha(tile.a * target_metadata.opacity),
+                                );
+                                visible.0 = true;
+                            }
+                            tile_entity
+                        }
+                        (Some(tile), None) => {
+                            let tile_entity = commands
+                                .spawn((
+                                    TileBundle {
+                                        position: tile_pos,
+                                        tilemap_id: TilemapId(*tilemap_entity),
+                                        texture_index: TileTextureIndex(tile.t as u32),
+                                        flip: auto_tile_flip(tile),
+                                        color: TileColor(
+                                            Color::WHITE
+                                                .with_alpha(tile.a * target_metadata.opacity),
+                                        ),
+                                        ..default()
+                                    },
+                                    GridCoords::from(tile_pos),
+                                    Transform::from_translation(
+                                        grid_coords_to_translation_relative_to_tile_layer(
+                                            tile_pos.into(),
+                                            IVec2::splat(target_metadata.grid_size),
+                                        )
+                                        .extend(0.),
+                                    ),
+                                    ChildOf(*tilemap_entity),
+                                ))
+                                .id();
+                            storage.set(&tile_pos, tile_entity);
+                            tile_entity
+                        }
+                        (None, Some(tile_entity)) => {
+                            let is_int_grid_cell = int_grid_values_query
+                                .get(*tilemap_entity)
+                                .is_ok_and(|values| {
+                                    values.get(tile_pos.into()).is_some_and(|v| v != 0)
+                                });
+
+                            if is_int_grid_cell {
+                                if let Ok((.., mut visible)) = tile_query.get_mut(tile_entity) {
+                                    visible.0 = false;
+                                }
+                                commands
+                                    .entity(tile_entity)
+                                    .remove::<(TileMetadata, TileEnumTags)>();
+                            } else {
+                                commands.entity(tile_entity).despawn();
+                                storage.remove(&tile_pos);
+                            }
+                            continue;
+                        }
+                        (None, None) => continue,
+                    };
+
+                    let tile_id = tile_stack[sublayer].t;
+                    let mut entity_commands = commands.entity(tile_entity);
+                    match metadata_map.get(&tile_id) {
+                        Some(tile_metadata) => entity_commands.insert(tile_metadata.clone()),
+                        None => entity_commands.remove::<TileMetadata>(),
+                    };
+                    match enum_tags_map.get(&tile_id) {
+                        Some(enum_tags) => entity_commands.insert(enum_tags.clone()),
+                        None => entity_commands.remove::<TileEnumTags>(),
+                    };
+                }
+            }
+
+            if overflowed {
+                warn!(
+                    "auto-layer tiles of layer \"{}\" stack higher than the layer did when it spawned, some tiles won't be displayed",
+                    target_layer_definition.identifier
+                );
+            }
+        }
+    }
+}
+
+/// Returns the cells within `radius` cells of any of the given cells, that are within `bounds`.
+fn cells_within(cells: &HashSet<IVec2>, radius: i32, bounds: IRect) -> HashSet<IVec2> {
+    cells
+        .iter()
+        .flat_map(|cell| {
+            let region =
+                IRect::from_center_half_size(*cell, IVec2::splat(radius)).intersect(bounds);
+            (region.min.y..=region.max.y)
+                .flat_map(move |y| (region.min.x..=region.max.x).map(move |x| IVec2::new(x, y)))
+        })
+        .collect()
+}
+
+/// Converts the flip bits of a [TileInstance] to a [TileFlip].
+fn auto_tile_flip(tile: &TileInstance) -> TileFlip {
+    TileFlip {
+        x: tile.f & 1 != 0,
+        y: tile.f & 2 != 0,
+        ..default()
+    }
+}
+
 /// Returns the `iid`s of levels that have spawned in this update.
 ///
 /// Mean to be used in a chain with [fire_level_transformed_events].
@@ -417,3 +1470,916 @@ pub fn fire_level_transformed_events(
         writer.write(LevelEvent::Transformed(id));
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ref_iids(iid: &str) -> LdtkEntityRefIids {
+        let entity_instance = EntityInstance {
+            field_instances: vec![crate::ldtk::FieldInstance {
+                identifier: "target".to_string(),
+                value: crate::ldtk::FieldValue::EntityRef(Some(
+                    crate::ldtk::ReferenceToAnEntityInstance {
+                        entity_iid: iid.to_string(),
+                        ..Default::default()
+                    },
+                )),
+                field_instance_type: "".to_string(),
+                tile: None,
+                def_uid: 0,
+                real_editor_values: Vec::new(),
+            }],
+            ..Default::default()
+        };
+
+        LdtkEntityRefIids::from_entity_instance(&entity_instance).unwrap()
+    }
+
+    #[test]
+    fn entity_refs_resolve_lazily_and_clear_on_despawn() {
+        let mut app = App::new();
+        app.init_resource::<IidIndex>()
+            .add_systems(Update, (update_iid_index, resolve_entity_refs).chain());
+
+        let source = app
+            .world_mut()
+            .spawn((EntityIid::new("source"), ref_iids("target")))
+            .id();
+
+        app.update();
+
+        assert!(app.world().get::<LdtkEntityRefs>(source).is_none());
+
+        let target = app.world_mut().spawn(EntityIid::new("target")).id();
+
+        app.update();
+
+        assert_eq!(
+            app.world()
+                .resource::<IidIndex>()
+                .get_entity(&EntityIid::new("target")),
+            Some(target)
+        );
+        assert_eq!(
+            app.world()
+                .get::<LdtkEntityRefs>(source)
+                .unwrap()
+                .get_single("target"),
+            Some(target)
+        );
+
+        app.world_mut().despawn(target);
+
+        app.update();
+
+        assert!(!app
+            .world()
+            .resource::<IidIndex>()
+            .contains_entity(&EntityIid::new("target")));
+        assert!(app.world().get::<LdtkEntityRefs>(source).is_none());
+    }
+
+    #[test]
+    fn iid_index_stays_consistent_across_respawns() {
+        let mut app = App::new();
+        app.init_resource::<IidIndex>()
+            .add_systems(Update, update_iid_index);
+
+        let old_level = app.world_mut().spawn(LevelIid::new("level")).id();
+        let worldly = app
+            .world_mut()
+            .spawn((EntityIid::new("worldly"), Worldly::default()))
+            .id();
+
+        app.update();
+
+        // despawning the old level and spawning its replacement in the same update
+        app.world_mut().despawn(old_level);
+        let new_level = app.world_mut().spawn(LevelIid::new("level")).id();
+
+        app.update();
+
+        let iid_index = app.world().resource::<IidIndex>();
+        assert_eq!(
+            iid_index.get_level(&LevelIid::new("level")),
+            Some(new_level)
+        );
+        assert_eq!(
+            iid_index.get_entity(&EntityIid::new("worldly")),
+            Some(worldly)
+        );
+        assert_eq!(iid_index.iter_levels().count(), 1);
+    }
+
+    fn project_with_levels(levels: Vec<Level>) -> LdtkJson {
+        LdtkJson {
+            levels,
+            ..Default::default()
+        }
+    }
+
+    fn level(iid: &str, world_x: i32) -> Level {
+        Level {
+            iid: iid.to_string(),
+            world_x,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn level_changes_detects_modified_moved_and_removed_levels() {
+        let old = project_with_levels(vec![
+            level("unchanged", 0),
+            level("modified", 0),
+            level("moved", 0),
+            level("removed", 0),
+        ]);
+
+        let mut new = project_with_levels(vec![
+            level("unchanged", 0),
+            level("modified", 0),
+            level("moved", 16),
+            level("added", 0),
+        ]);
+        new.levels[1].identifier = "Modified".to_string();
+
+        let changes = level_changes(&old, &new).unwrap();
+
+        assert_eq!(changes.len(), 3);
+        assert_eq!(
+            changes.get(&LevelIid::new("modified")),
+            Some(&LevelChange::Modified)
+        );
+        assert_eq!(
+            changes.get(&LevelIid::new("moved")),
+            Some(&LevelChange::Moved)
+        );
+        assert_eq!(
+            changes.get(&LevelIid::new("removed")),
+            Some(&LevelChange::Removed)
+        );
+
+        let mut new_defs = old.clone();
+        new_defs.defs.entities.push(Default::default());
+        assert_eq!(level_changes(&old, &new_defs), None);
+    }
+
+    #[test]
+    fn level_change_detects_modified_layers() {
+        use crate::ldtk::LayerInstance;
+
+        let layer = |iid: &str| LayerInstance {
+            iid: iid.to_string(),
+            ..Default::default()
+        };
+
+        let old = Level {
+            layer_instances: Some(vec![layer("a"), layer("b"), layer("c")]),
+            ..level("level", 0)
+        };
+
+        assert_eq!(level_change(&old, &old), None);
+
+        let mut layers_modified = old.clone();
+        layers_modified.layer_instances.as_mut().unwrap()[1].seed = 1;
+        assert_eq!(
+            level_change(&old, &layers_modified),
+            Some(LevelChange::LayersModified(HashSet::from(
+                ["b".to_string()]
+            )))
+        );
+
+        let mut level_modified = layers_modified.clone();
+        level_modified.identifier = "Modified".to_string();
+        assert_eq!(
+            level_change(&old, &level_modified),
+            Some(LevelChange::Modified)
+        );
+
+        let mut layers_reordered = old.clone();
+        layers_reordered
+            .layer_instances
+            .as_mut()
+            .unwrap()
+            .swap(0, 2);
+        assert_eq!(
+            level_change(&old, &layers_reordered),
+            Some(LevelChange::Modified)
+        );
+
+        let mut layer_removed = old.clone();
+        layer_removed.layer_instances.as_mut().unwrap().pop();
+        assert_eq!(
+            level_change(&old, &layer_removed),
+            Some(LevelChange::Modified)
+        );
+    }
+
+    #[test]
+    fn hot_reload_respawn_preserves_patched_entities() {
+        let mut app = App::new();
+        app.add_message::<LevelEvent>()
+            .add_systems(Update, clean_respawn_entities);
+
+        let level = app
+            .world_mut()
+            .spawn((LevelIid::new("level"), Respawn, HotReloadRespawn))
+            .id();
+        let layer = app.world_mut().spawn(ChildOf(level)).id();
+        let patched = app
+            .world_mut()
+            .spawn((EntityIid::new("patched"), PatchOnHotReload, ChildOf(layer)))
+            .id();
+        let respawned = app
+            .world_mut()
+            .spawn((EntityIid::new("respawned"), ChildOf(layer)))
+            .id();
+
+        app.update();
+
+        assert!(app.world().get_entity(layer).is_err());
+        assert!(app.world().get_entity(respawned).is_err());
+        assert_eq!(
+            app.world().get::<ChildOf>(patched).map(ChildOf::parent),
+            Some(level)
+        );
+        assert!(app.world().get::<HotReloadRespawn>(level).is_none());
+    }
+
+    /// An app that spawns levels, without rendering them.
+    #[cfg(any(feature = "internal_levels", feature = "external_levels"))]
+    fn level_spawning_app(ldtk_settings: LdtkSettings) -> App {
+        let mut app = App::new();
+        app.add_plugins((TaskPoolPlugin::default(), AssetPlugin::default()))
+            .init_asset::<LdtkProject>()
+            .init_asset::<Image>()
+            .init_asset::<TextureAtlasLayout>()
+            .insert_resource(ldtk_settings)
+            .add_message::<LevelEvent>()
+            .init_non_send::<LdtkEntityMap>()
+            .init_non_send::<LdtkEntityTagMap>()
+            .init_non_send::<LdtkIntCellMap>()
+            .init_non_send::<LdtkIntCellIdentifierMap>()
+            .init_non_send::<LdtkTileMap>()
+            .add_systems(
+                Update,
+                (
+                    process_ldtk_assets,
+                    clean_respawn_entities,
+                    process_ldtk_levels,
+                )
+                    .chain(),
+            );
+
+        #[cfg(feature = "render")]
+        app.init_resource::<ClearColor>();
+
+        #[cfg(feature = "external_levels")]
+        app.init_asset::<LdtkExternalLevel>()
+            .init_resource::<LazyExternalLevels>();
+
+        app
+    }
+
+    #[cfg(feature = "internal_levels")]
+    #[test]
+    fn big_layers_spawn_over_multiple_frames() {
+        use crate::{
+            assets::{LdtkJsonWithMetadata, LevelIndices, LevelMetadata},
+            ldtk::{IntGridValueDefinition, LayerDefinition, LayerInstance, Type},
+            resources::LevelSpawnBudget,
+        };
+        use bevy_ecs_tilemap::tiles::{TilePos, TileStorage};
+
+        let mut json = project_with_levels(vec![Level {
+            px_wid: 64,
+            px_hei: 128,
+            layer_instances: Some(vec![LayerInstance {
+                identifier: "Terrain".to_string(),
+                layer_instance_type: Type::IntGrid,
+                layer_def_uid: 1,
+                grid_size: 16,
+                c_wid: 4,
+                c_hei: 8,
+                int_grid_csv: vec![1; 32],
+                ..Default::default()
+            }]),
+            ..level("level", 0)
+        }]);
+        json.defs.layers.push(LayerDefinition {
+            identifier: "Terrain".to_string(),
+            purple_type: Type::IntGrid,
+            uid: 1,
+            grid_size: 16,
+            int_grid_values: vec![IntGridValueDefinition {
+                value: 1,
+                ..Default::default()
+            }],
+            ..Default::default()
+        });
+
+        let project = LdtkProject::new(
+            LdtkProjectData::Standalone(LdtkJsonWithMetadata::new(
+                json,
+                HashMap::from([(
+                    "level".to_string(),
+                    LevelMetadata::new(None, LevelIndices::in_root(0)),
+                )]),
+            )),
+            HashMap::new(),
+            Some(Handle::default()),
+            false,
+        );
+
+        let mut app = level_spawning_app(LdtkSettings {
+            level_spawn_budget: LevelSpawnBudget::PerFrame {
+                tiles_and_entities: 8,
+            },
+            ..default()
+        });
+
+        let handle = app
+            .world_mut()
+            .resource_mut::<Assets<LdtkProject>>()
+            .add(project);
+        let world_entity = app.world_mut().spawn(LdtkProjectHandle::from(handle)).id();
+        let level_entity = app
+            .world_mut()
+            .spawn((LevelIid::new("level"), Respawn, ChildOf(world_entity)))
+            .id();
+
+        let cell_rows = |app: &mut App| {
+            let mut rows = app
+                .world_mut()
+                .query_filtered::<&TilePos, With<IntGridCell>>()
+                .iter(app.world())
+                .map(|tile_pos| tile_pos.y)
+                .collect::<Vec<_>>();
+            rows.sort();
+            rows.dedup();
+            rows
+        };
+
+        // two rows of the layer fit in the budget each frame, starting from the top
+        for frame in 1..=4 {
+            app.update();
+
+            let progress = app.world().get::<LevelSpawnProgress>(level_entity).unwrap();
+            assert_eq!(progress.spawned(), frame * 8);
+            assert_eq!(progress.is_finished(), frame == 4);
+            assert_eq!(
+                cell_rows(&mut app),
+                (8 - frame as u32 * 2..8).collect::<Vec<_>>()
+            );
+        }
+
+        let storages = app
+            .world_mut()
+            .query::<&TileStorage>()
+            .iter(app.world())
+            .cloned()
+            .collect::<Vec<_>>();
+        assert_eq!(storages.len(), 1);
+        assert!(storages[0].iter().all(Option::is_some));
+
+        let spawned_events = app
+            .world()
+            .resource::<Messages<LevelEvent>>()
+            .iter_current_update_messages()
+            .filter(|event| matches!(event, LevelEvent::Spawned(_)))
+            .count();
+        assert_eq!(spawned_events, 1);
+    }
+
+    #[cfg(feature = "internal_levels")]
+    #[test]
+    fn hot_reload_patches_entities_of_modified_levels() {
+        use crate::{
+            app::{LdtkEntity, LdtkEntityAppExt},
+            assets::{LdtkJsonWithMetadata, LevelIndices, LevelMetadata},
+            ldtk::{
+                ldtk_fields::LdtkFields, EntityDefinition, FieldInstance, FieldValue,
+                LayerInstance, Type,
+            },
+        };
+
+        #[derive(Component, Debug, PartialEq)]
+        struct Health(i32);
+
+        #[derive(Component)]
+        struct RuntimeState;
+
+        #[derive(Bundle)]
+        struct EnemyBundle {
+            health: Health,
+            patch_on_hot_reload: PatchOnHotReload,
+        }
+
+        impl LdtkEntity for EnemyBundle {
+            fn bundle_entity(
+                entity_instance: &EntityInstance,
+                _: &LayerInstance,
+                _: Option<&Handle<Image>>,
+                _: Option<&TilesetDefinition>,
+                _: &AssetServer,
+                _: &mut Assets<TextureAtlasLayout>,
+            ) -> Self {
+                EnemyBundle {
+                    health: Health(*entity_instance.get_int_field("Health").unwrap()),
+                    patch_on_hot_reload: PatchOnHotReload,
+                }
+            }
+        }
+
+        let enemy = |iid: &str, health: i32| EntityInstance {
+            identifier: "Enemy".to_string(),
+            iid: iid.to_string(),
+            def_uid: 1,
+            width: 16,
+            height: 16,
+            field_instances: vec![FieldInstance {
+                identifier: "Health".to_string(),
+                value: FieldValue::Int(Some(health)),
+                field_instance_type: "Int".to_string(),
+                tile: None,
+                def_uid: 2,
+                real_editor_values: Vec::new(),
+            }],
+            ..Default::default()
+        };
+
+        let project = |entity_instances: Vec<EntityInstance>| {
+            let mut json = project_with_levels(vec![Level {
+                px_wid: 64,
+                px_hei: 64,
+                layer_instances: Some(vec![LayerInstance {
+                    identifier: "Entities".to_string(),
+                    layer_instance_type: Type::Entities,
+                    grid_size: 16,
+                    entity_instances,
+                    ..Default::default()
+                }]),
+                ..level("level", 0)
+            }]);
+            json.defs.entities.push(EntityDefinition {
+                identifier: "Enemy".to_string(),
+                uid: 1,
+                width: 16,
+                height: 16,
+                ..Default::default()
+            });
+
+            LdtkProject::new(
+                LdtkProjectData::Standalone(LdtkJsonWithMetadata::new(
+                    json,
+                    HashMap::from([(
+                        "level".to_string(),
+                        LevelMetadata::new(None, LevelIndices::in_root(0)),
+                    )]),
+                )),
+                HashMap::new(),
+                None,
+                false,
+            )
+        };
+
+        let mut app = level_spawning_app(LdtkSettings {
+            hot_reload_behavior: HotReloadBehavior::RespawnChangedLevels,
+            ..default()
+        });
+        app.register_ldtk_entity::<EnemyBundle>("Enemy");
+
+        let handle = app
+            .world_mut()
+            .resource_mut::<Assets<LdtkProject>>()
+            .add(project(vec![enemy("kept", 1), enemy("removed", 1)]));
+        // the asset server reports this for loaded projects, which is when they are snapshotted
+        app.world_mut()
+            .write_message(AssetEvent::LoadedWithDependencies { id: handle.id() });
+
+        let world_entity = app
+            .world_mut()
+            .spawn(LdtkProjectHandle::from(handle.clone()))
+            .id();
+        let level_entity = app
+            .world_mut()
+            .spawn((LevelIid::new("level"), Respawn, ChildOf(world_entity)))
+            .id();
+
+        app.update();
+
+        let find_entity = |app: &mut App, iid: &str| {
+            app.world_mut()
+                .query::<(Entity, &EntityIid)>()
+                .iter(app.world())
+                .find_map(|(entity, entity_iid)| (entity_iid.as_str() == iid).then_some(entity))
+        };
+
+        let kept = find_entity(&mut app, "kept").unwrap();
+        let removed = find_entity(&mut app, "removed").unwrap();
+        app.world_mut().entity_mut(kept).insert(RuntimeState);
+
+        app.world_mut()
+            .resource_mut::<Assets<LdtkProject>>()
+            .insert(&handle, project(vec![enemy("kept", 5), enemy("added", 3)]))
+            .unwrap();
+
+        for _ in 0..2 {
+            app.update();
+        }
+
+        // matched iids are patched in place, and re-evaluated
+        assert_eq!(find_entity(&mut app, "kept"), Some(kept));
+        assert_eq!(app.world().get::<Health>(kept), Some(&Health(5)));
+        assert!(app.world().get::<RuntimeState>(kept).is_some());
+        let layer_entity = app.world().get::<ChildOf>(kept).unwrap().parent();
+        assert!(app.world().get::<LayerMetadata>(layer_entity).is_some());
+        assert_eq!(
+            app.world()
+                .get::<ChildOf>(layer_entity)
+                .map(ChildOf::parent),
+            Some(level_entity)
+        );
+
+        // new instances are spawned
+        let added = find_entity(&mut app, "added").unwrap();
+        assert_eq!(app.world().get::<Health>(added), Some(&Health(3)));
+
+        // removed instances are despawned
+        assert!(app.world().get_entity(removed).is_err());
+        assert_eq!(find_entity(&mut app, "removed"), None);
+    }
+
+    #[cfg(feature = "internal_levels")]
+    #[test]
+    fn hot_reload_respawns_only_modified_layers() {
+        use crate::{
+            assets::{LdtkJsonWithMetadata, LevelIndices, LevelMetadata},
+            ldtk::{
+                EntityDefinition, IntGridValueDefinition, LayerDefinition, LayerInstance, Type,
+            },
+        };
+
+        #[derive(Component)]
+        struct RuntimeState;
+
+        let project = |int_grid_csv: Vec<i32>| {
+            let mut json = project_with_levels(vec![Level {
+                px_wid: 32,
+                px_hei: 32,
+                layer_instances: Some(vec![
+                    LayerInstance {
+                        identifier: "Entities".to_string(),
+                        iid: "entities".to_string(),
+                        layer_instance_type: Type::Entities,
+                        grid_size: 16,
+                        entity_instances: vec![EntityInstance {
+                            identifier: "Enemy".to_string(),
+                            iid: "enemy".to_string(),
+                            def_uid: 1,
+                            ..Default::default()
+                        }],
+                        ..Default::default()
+                    },
+                    LayerInstance {
+                        identifier: "Terrain".to_string(),
+                        iid: "terrain".to_string(),
+                        layer_instance_type: Type::IntGrid,
+                        layer_def_uid: 2,
+                        grid_size: 16,
+                        c_wid: 2,
+                        c_hei: 2,
+                        int_grid_csv,
+                        ..Default::default()
+                    },
+                ]),
+                ..level("level", 0)
+            }]);
+            json.defs.entities.push(EntityDefinition {
+                identifier: "Enemy".to_string(),
+                uid: 1,
+                ..Default::default()
+            });
+            json.defs.layers.push(LayerDefinition {
+                identifier: "Terrain".to_string(),
+                purple_type: Type::IntGrid,
+                uid: 2,
+                grid_size: 16,
+                int_grid_values: vec![IntGridValueDefinition {
+                    value: 1,
+                    ..Default::default()
+                }],
+                ..Default::default()
+            });
+
+            LdtkProject::new(
+                LdtkProjectData::Standalone(LdtkJsonWithMetadata::new(
+                    json,
+                    HashMap::from([(
+                        "level".to_string(),
+                        LevelMetadata::new(None, LevelIndices::in_root(0)),
+                    )]),
+                )),
+                HashMap::new(),
+                Some(Handle::default()),
+                false,
+            )
+        };
+
+        let mut app = level_spawning_app(LdtkSettings {
+            hot_reload_behavior: HotReloadBehavior::RespawnChangedLevels,
+            ..default()
+        });
+
+        let handle = app
+            .world_mut()
+            .resource_mut::<Assets<LdtkProject>>()
+            .add(project(vec![1, 0, 0, 0]));
+        app.world_mut()
+            .write_message(AssetEvent::LoadedWithDependencies { id: handle.id() });
+
+        let world_entity = app
+            .world_mut()
+            .spawn(LdtkProjectHandle::from(handle.clone()))
+            .id();
+        let level_entity = app
+            .world_mut()
+            .spawn((LevelIid::new("level"), Respawn, ChildOf(world_entity)))
+            .id();
+
+        app.update();
+
+        let layers = |app: &mut App| {
+            let mut layers = app
+                .world_mut()
+                .query::<(Entity, &LayerMetadata, &Transform)>()
+                .iter(app.world())
+                .map(|(entity, layer_metadata, transform)| {
+                    (layer_metadata.iid.clone(), entity, transform.translation.z)
+                })
+                .collect::<Vec<_>>();
+            layers.sort_by(|a, b| a.0.cmp(&b.0));
+            layers
+        };
+        let cells = |app: &mut App| {
+            app.world_mut()
+                .query_filtered::<(), With<IntGridCell>>()
+                .iter(app.world())
+                .count()
+        };
+
+        let old_layers = layers(&mut app);
+        assert_eq!(old_layers.len(), 2);
+        assert_eq!(cells(&mut app), 1);
+
+        let enemy = app
+            .world_mut()
+            .query::<(Entity, &EntityIid)>()
+            .iter(app.world())
+            .find_map(|(entity, entity_iid)| (entity_iid.as_str() == "enemy").then_some(entity))
+            .unwrap();
+        app.world_mut().entity_mut(enemy).insert(RuntimeState);
+
+        app.world_mut()
+            .resource_mut::<Assets<LdtkProject>>()
+            .insert(&handle, project(vec![1, 1, 0, 1]))
+            .unwrap();
+
+        for _ in 0..2 {
+            app.update();
+
+            assert!(app
+                .world()
+                .resource::<Messages<LevelEvent>>()
+                .iter_current_update_messages()
+                .all(|event| !matches!(event, LevelEvent::Despawned(_))));
+        }
+
+        // the unchanged layer, and its entities, are untouched
+        let new_layers = layers(&mut app);
+        assert_eq!(new_layers[0], old_layers[0]);
+        assert!(app.world().get::<RuntimeState>(enemy).is_some());
+
+        // the changed layer is respawned in the same place
+        assert_eq!(new_layers[1].0, "terrain");
+        assert_ne!(new_layers[1].1, old_layers[1].1);
+        assert_eq!(new_layers[1].2, old_layers[1].2);
+        assert!(app.world().get_entity(old_layers[1].1).is_err());
+        assert_eq!(
+            app.world()
+                .get::<ChildOf>(new_layers[1].1)
+                .map(ChildOf::parent),
+            Some(level_entity)
+        );
+        assert_eq!(cells(&mut app), 3);
+
+        assert!(app.world().get::<RespawnLayers>(level_entity).is_none());
+    }
+
+    #[cfg(feature = "external_levels")]
+    #[test]
+    fn lazy_external_levels_load_while_in_a_level_set() {
+        use crate::{
+            assets::{
+                ExternalLevelMetadata, LazyLevelPaths, LdtkJsonWithMetadata, LevelIndices,
+                LevelMetadata,
+            },
+            ldtk::{IntGridValueDefinition, LayerDefinition, LayerInstance, Type},
+        };
+        use bevy::asset::uuid::Uuid;
+
+        let placeholder = Handle::<LdtkExternalLevel>::Uuid(
+            Uuid::from_u128(0x6c61_7a79_5f6c_6576_656c),
+            Default::default(),
+        );
+        let level_path = "lazy/level.ldtkl";
+
+        let mut json = project_with_levels(vec![Level {
+            px_wid: 32,
+            px_hei: 32,
+            external_rel_path: Some(level_path.to_string()),
+            ..level("level", 0)
+        }]);
+        json.external_levels = true;
+        json.defs.layers.push(LayerDefinition {
+            identifier: "Terrain".to_string(),
+            purple_type: Type::IntGrid,
+            uid: 2,
+            grid_size: 16,
+            int_grid_values: vec![IntGridValueDefinition {
+                value: 1,
+                ..Default::default()
+            }],
+            ..Default::default()
+        });
+
+        let external_level = |int_grid_csv: Vec<i32>| {
+            LdtkExternalLevel::new(Level {
+                px_wid: 32,
+                px_hei: 32,
+                layer_instances: Some(vec![
+                    LayerInstance {
+                        identifier: "Entities".to_string(),
+                        iid: "entities".to_string(),
+                        layer_instance_type: Type::Entities,
+                        grid_size: 16,
+                        ..Default::default()
+                    },
+                    LayerInstance {
+                        identifier: "Terrain".to_string(),
+                        iid: "terrain".to_string(),
+                        layer_instance_type: Type::IntGrid,
+                        layer_def_uid: 2,
+                        grid_size: 16,
+                        c_wid: 2,
+                        c_hei: 2,
+                        int_grid_csv,
+                        ..Default::default()
+                    },
+                ]),
+                ..level("level", 0)
+            })
+        };
+
+        let project = LdtkProject::new(
+            LdtkProjectData::Parent(LdtkJsonWithMetadata::new(
+                json,
+                HashMap::from([(
+                    "level".to_string(),
+                    ExternalLevelMetadata::new(
+                        LevelMetadata::new(None, LevelIndices::in_root(0)),
+                        placeholder.clone(),
+                    )
+                    .with_lazy_paths(LazyLevelPaths::new(level_path.into(), None)),
+                )]),
+            )),
+            HashMap::new(),
+            Some(Handle::default()),
+            false,
+        );
+
+        let mut app = level_spawning_app(LdtkSettings {
+            hot_reload_behavior: HotReloadBehavior::RespawnChangedLevels,
+            ..default()
+        });
+        app.add_systems(
+            Update,
+            (load_lazy_external_levels, apply_level_set)
+                .chain()
+                .before(process_ldtk_assets),
+        );
+
+        let handle = app
+            .world_mut()
+            .resource_mut::<Assets<LdtkProject>>()
+            .add(project);
+        let world_entity = app
+            .world_mut()
+            .spawn((LdtkProjectHandle::from(handle), LevelSet::default()))
+            .id();
+
+        let is_requested = |app: &App| {
+            app.world()
+                .resource::<LazyExternalLevels>()
+                .is_requested(placeholder.id())
+        };
+        let level_entities = |app: &mut App| {
+            app.world_mut()
+                .query_filtered::<Entity, With<LevelIid>>()
+                .iter(app.world())
+                .collect::<Vec<_>>()
+        };
+        let layers = |app: &mut App| {
+            let mut layers = app
+                .world_mut()
+                .query::<(Entity, &LayerMetadata)>()
+                .iter(app.world())
+                .map(|(entity, layer_metadata)| (layer_metadata.iid.clone(), entity))
+                .collect::<Vec<_>>();
+            layers.sort();
+            layers
+        };
+        let cells = |app: &mut App| {
+            app.world_mut()
+                .query_filtered::<(), With<IntGridCell>>()
+                .iter(app.world())
+                .count()
+        };
+
+        // the level isn't requested until it's in a level set
+        app.update();
+
+        assert!(!is_requested(&app));
+        assert!(app
+            .world()
+            .resource::<AssetServer>()
+            .get_handle::<LdtkExternalLevel>(level_path)
+            .is_none());
+
+        app.world_mut()
+            .entity_mut(world_entity)
+            .insert(LevelSet::from_iids(["level"]));
+        app.update();
+
+        assert!(is_requested(&app));
+        let requested_level = app
+            .world()
+            .resource::<AssetServer>()
+            .get_handle::<LdtkExternalLevel>(level_path)
+            .unwrap();
+
+        // the level isn't spawned until its data has loaded
+        for _ in 0..2 {
+            app.update();
+            assert!(level_entities(&mut app).is_empty());
+        }
+
+        let load_level = |app: &mut App, int_grid_csv| {
+            app.world_mut()
+                .resource_mut::<Assets<LdtkExternalLevel>>()
+                .insert(requested_level.id(), external_level(int_grid_csv))
+                .unwrap();
+        };
+
+        load_level(&mut app, vec![1, 0, 0, 0]);
+        app.update();
+        app.update();
+
+        let level_entity = level_entities(&mut app)[0];
+        let old_layers = layers(&mut app);
+        assert_eq!(old_layers.len(), 2);
+        assert_eq!(cells(&mut app), 1);
+
+        // hot reloading the level only respawns its changed layers
+        load_level(&mut app, vec![1, 1, 0, 1]);
+        for _ in 0..3 {
+            app.update();
+
+            assert!(app
+                .world()
+                .resource::<Messages<LevelEvent>>()
+                .iter_current_update_messages()
+                .all(|event| !matches!(event, LevelEvent::Despawned(_))));
+        }
+
+        let new_layers = layers(&mut app);
+        assert_eq!(level_entities(&mut app), vec![level_entity]);
+        assert_eq!(new_layers[0], old_layers[0]);
+        assert_ne!(new_layers[1], old_layers[1]);
+        assert_eq!(cells(&mut app), 3);
+
+        // the level's data is unloaded once it leaves every level set
+        app.world_mut()
+            .entity_mut(world_entity)
+            .insert(LevelSet::default());
+        app.update();
+
+        assert!(!is_requested(&app));
+        assert!(!app
+            .world()
+            .resource::<Assets<LdtkExternalLevel>>()
+            .contains(placeholder.id()));
+        assert!(level_entities(&mut app).is_empty());
+    }
+}
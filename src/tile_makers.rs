@@ -5,7 +5,7 @@
 //! impl FnMut(TilePos) -> Option<TileBundle>
 //! ```
 //!
-//! Tile makers can be used with [set_all_tiles_with_func] to spawn many tiles at once.
+//! Tile makers can be used with [set_tiles_with_func] to spawn many tiles at once.
 
 use crate::{
     components::TileGridBundle,
@@ -6,14 +6,18 @@ use crate::{
     components::{GridCoords, IntGridCell},
 };
 
-use crate::{components::TileGridBundle, ldtk::*};
+use crate::{
+    app::{IntGridValueIdentifier, LdtkIntCellIdentifierMap, LdtkIntCellMap, TileSelector},
+    components::TileGridBundle,
+    ldtk::*,
+};
 use bevy::prelude::*;
 use bevy_ecs_tilemap::{
     map::{TilemapId, TilemapSize},
     tiles::{TilePos, TileStorage},
 };
 
-use std::{collections::HashMap, hash::Hash};
+use std::{collections::HashMap, hash::Hash, ops::Range};
 
 /// The `int_grid_csv` field of a [LayerInstance] is a 1-dimensional [`Vec<i32>`].
 /// This function can map the indices of this [Vec] to a corresponding [GridCoords].
@@ -236,19 +240,130 @@ pub fn ldtk_pixel_coords_to_translation_pivoted(
     pivot_point + offset
 }
 
+/// Merges the nonzero cells of an IntGrid layer into as few rectangles as is reasonable.
+///
+/// The `int_grid_csv` should be laid out like [LayerInstance::int_grid_csv].
+/// Every nonzero value is passed to `key_func` to determine which group of rectangles it belongs
+/// to.
+/// Only cells with the same key are merged together, and cells for which `key_func` returns
+/// [None] are skipped entirely.
+/// So, merging every value separately is as simple as passing `Some` as the `key_func`.
+///
+/// The resulting [IRect]s are in [GridCoords] space, where `min` is the bottom-left cell of the
+/// rectangle and `max` is one past the top-right cell.
+///
+/// The algorithm used here is a nice compromise between simplicity, speed, and a small number of
+/// rectangles.
+/// In basic terms, it will:
+/// 1. combine cells into flat "plates" in each individual row
+/// 2. combine the plates into rectangles across multiple rows wherever possible
+pub fn merge_int_grid_cells<K>(
+    int_grid_csv: &[i32],
+    layer_width_in_tiles: i32,
+    layer_height_in_tiles: i32,
+    mut key_func: impl FnMut(i32) -> Option<K>,
+) -> HashMap<K, Vec<IRect>>
+where
+    K: Hash + Eq + Clone,
+{
+    /// Represents a wide group of cells that is 1 cell tall.
+    #[derive(Clone, Eq, PartialEq, Debug, Hash)]
+    struct Plate<K> {
+        key: K,
+        left: i32,
+        right: i32,
+    }
+
+    let mut key_at = |x: i32, y: i32| -> Option<K> {
+        let ldtk_coords =
+            grid_coords_to_ldtk_grid_coords(GridCoords::new(x, y), layer_height_in_tiles);
+        let index = (ldtk_coords.y * layer_width_in_tiles + ldtk_coords.x) as usize;
+
+        match int_grid_csv.get(index) {
+            Some(value) if *value != 0 => key_func(*value),
+            _ => None,
+        }
+    };
+
+    let mut rect_builder: HashMap<Plate<K>, IRect> = HashMap::new();
+    let mut merged_rects: HashMap<K, Vec<IRect>> = HashMap::new();
+
+    // an extra empty row so the algorithm "finishes" the rects that touch the top edge
+    for y in 0..layer_height_in_tiles + 1 {
+        let mut row_plates: Vec<Plate<K>> = Vec::new();
+
+        if y < layer_height_in_tiles {
+            let mut plate_start: Option<(K, i32)> = None;
+
+            // + 1 to the width so the algorithm "terminates" plates that touch the right edge
+            for x in 0..layer_width_in_tiles + 1 {
+                let key = if x < layer_width_in_tiles {
+                    key_at(x, y)
+                } else {
+                    None
+                };
+
+                if let Some((start_key, left)) = &plate_start {
+                    if Some(start_key) != key.as_ref() {
+                        row_plates.push(Plate {
+                            key: start_key.clone(),
+                            left: *left,
+                            right: x - 1,
+                        });
+                        plate_start = None;
+                    }
+                }
+
+                if plate_start.is_none() {
+                    plate_start = key.map(|key| (key, x));
+                }
+            }
+        }
+
+        // remove the finished rects so that the same plate in the future starts a new rect
+        rect_builder.retain(|plate, rect| {
+            let continued = row_plates.contains(plate);
+            if !continued {
+                merged_rects
+                    .entry(plate.key.clone())
+                    .or_default()
+                    .push(*rect);
+            }
+            continued
+        });
+
+        for plate in row_plates {
+            let new_rect = IRect::new(plate.left, y, plate.right + 1, y + 1);
+            rect_builder
+                .entry(plate)
+                .and_modify(|rect| rect.max.y += 1)
+                .or_insert(new_rect);
+        }
+    }
+
+    for rects in merged_rects.values_mut() {
+        rects.sort_by_key(|rect| (rect.min.y, rect.min.x));
+    }
+
+    merged_rects
+}
+
 /// Similar to [LayerBuilder::new_batch], except it doesn't consume the [LayerBuilder]
 ///
 /// This allows for more methods to be performed on the [LayerBuilder] before building it.
 /// However, the performance cons of using non-batch methods still apply here.
-pub(crate) fn set_all_tiles_with_func(
+///
+/// Only the tiles in the given range of rows are set, so big layers can be spawned in parts.
+pub(crate) fn set_tiles_with_func(
     commands: &mut Commands,
     storage: &mut TileStorage,
     size: TilemapSize,
+    rows: Range<u32>,
     tilemap_id: TilemapId,
     mut func: impl FnMut(TilePos) -> Option<TileGridBundle>,
 ) {
-    for x in 0..size.x {
-        for y in 0..size.y {
+    for y in rows {
+        for x in 0..size.x {
             let tile_pos = TilePos { x, y };
             let tile_entity = func(tile_pos).map(|mut tile_bundle| {
                 tile_bundle.tile_bundle.tilemap_id = tilemap_id;
@@ -309,6 +424,175 @@ where
     try_each_optional_permutation(a, b, |x, y| map.get(&(x, y))).unwrap_or(default)
 }
 
+/// The "get" function used on [bevy_ecs_ldtk::app::LdtkEntityMap] and
+/// [bevy_ecs_ldtk::app::LdtkEntityTagMap] together.
+///
+/// Similar to [ldtk_map_get_or_default], except registrations for the entity's tags are tried
+/// after the registrations for its identifier, but before registrations for just its layer.
+/// Tags are tried in the order they appear on the entity.
+pub(crate) fn ldtk_entity_map_get_or_default<'a, L>(
+    layer_identifier: &str,
+    entity_instance: &EntityInstance,
+    default: &'a L,
+    ldtk_entity_map: &'a HashMap<(Option<String>, Option<String>), L>,
+    ldtk_entity_tag_map: &'a HashMap<String, L>,
+) -> &'a L {
+    let layer_identifier = layer_identifier.to_string();
+    let entity_identifier = entity_instance.identifier.clone();
+
+    if ldtk_entity_tag_map.is_empty() {
+        return ldtk_map_get_or_default(
+            layer_identifier,
+            entity_identifier,
+            default,
+            ldtk_entity_map,
+        );
+    }
+
+    // registrations with or without an entity identifier
+    let get = |with_identifier: bool| {
+        try_each_optional_permutation(
+            layer_identifier.clone(),
+            entity_identifier.clone(),
+            |x, y| {
+                (y.is_some() == with_identifier)
+                    .then(|| ldtk_entity_map.get(&(x, y)))
+                    .flatten()
+            },
+        )
+    };
+
+    get(true)
+        .or_else(|| {
+            entity_instance
+                .tags
+                .iter()
+                .find_map(|tag| ldtk_entity_tag_map.get(tag))
+        })
+        .or_else(|| get(false))
+        .unwrap_or(default)
+}
+
+/// Identifiers of the given IntGrid value and its group, according to the layer definition.
+fn int_grid_value_identifiers(
+    layer_definition: &LayerDefinition,
+    value: i32,
+) -> (Option<&String>, Option<&String>) {
+    let Some(value_definition) = layer_definition
+        .int_grid_values
+        .iter()
+        .find(|value_definition| value_definition.value == value)
+    else {
+        return (None, None);
+    };
+
+    let group_identifier = layer_definition
+        .int_grid_values_groups
+        .iter()
+        .find(|group_definition| group_definition.uid == value_definition.group_uid)
+        .and_then(|group_definition| group_definition.identifier.as_ref());
+
+    (value_definition.identifier.as_ref(), group_identifier)
+}
+
+/// The "get" function used on [bevy_ecs_ldtk::app::LdtkIntCellMap] and
+/// [bevy_ecs_ldtk::app::LdtkIntCellIdentifierMap] together.
+///
+/// Due to the defaulting functionality of [bevy_ecs_ldtk::app::LdtkIntCellAppExt], a single
+/// IntGrid tile may match multiple registrations.
+/// This function is responsible for picking the correct registration while spawning tiles.
+/// Registrations for the value's identifier and group identifier are tried after the
+/// registrations for the numeric value, but before registrations for just the layer.
+/// These identifiers are resolved through the `layer_definition`, if there is one.
+pub(crate) fn ldtk_int_cell_map_get<'a, L>(
+    layer_identifier: &str,
+    value: i32,
+    layer_definition: Option<&LayerDefinition>,
+    ldtk_int_cell_map: &'a HashMap<(Option<String>, Option<i32>), L>,
+    ldtk_int_cell_identifier_map: &'a HashMap<IntGridValueIdentifier, L>,
+) -> Option<&'a L> {
+    // registrations with or without a value
+    let get = |with_value: bool| {
+        try_each_optional_permutation(layer_identifier.to_string(), value, |x, y| {
+            (y.is_some() == with_value)
+                .then(|| ldtk_int_cell_map.get(&(x, y)))
+                .flatten()
+        })
+    };
+
+    get(true)
+        .or_else(|| {
+            if ldtk_int_cell_identifier_map.is_empty() {
+                return None;
+            }
+
+            let (value_identifier, group_identifier) =
+                int_grid_value_identifiers(layer_definition?, value);
+
+            value_identifier
+                .and_then(|identifier| {
+                    ldtk_int_cell_identifier_map
+                        .get(&IntGridValueIdentifier::Value(identifier.clone()))
+                })
+                .or_else(|| {
+                    group_identifier.and_then(|identifier| {
+                        ldtk_int_cell_identifier_map
+                            .get(&IntGridValueIdentifier::Group(identifier.clone()))
+                    })
+                })
+        })
+        .or_else(|| get(false))
+}
+
+/// Returns true if an [LdtkIntCell](crate::app::LdtkIntCell) is registered for the given
+/// layer and IntGrid value, including registrations for every layer or every value.
+pub(crate) fn int_cell_registered(
+    ldtk_int_cell_map: &LdtkIntCellMap,
+    ldtk_int_cell_identifier_map: &LdtkIntCellIdentifierMap,
+    layer_identifier: &str,
+    layer_definition: Option<&LayerDefinition>,
+    value: i32,
+) -> bool {
+    ldtk_int_cell_map_get(
+        layer_identifier,
+        value,
+        layer_definition,
+        ldtk_int_cell_map,
+        ldtk_int_cell_identifier_map,
+    )
+    .is_some()
+}
+
+/// The "get" function used on [bevy_ecs_ldtk::app::LdtkTileMap].
+///
+/// A single tile may match multiple registrations of [bevy_ecs_ldtk::app::LdtkTileAppExt].
+/// This function is responsible for picking the correct registration while spawning tiles.
+/// The registration for the tile's id is tried first, followed by the registrations for each of
+/// its `enum_tag_values`, in order.
+pub(crate) fn ldtk_tile_map_get<'a, L>(
+    tileset_identifier: &str,
+    tile_id: i32,
+    enum_tag_values: &[String],
+    ldtk_tile_map: &'a HashMap<(String, TileSelector), L>,
+) -> Option<&'a L> {
+    if ldtk_tile_map.is_empty() {
+        return None;
+    }
+
+    let tileset_identifier = tileset_identifier.to_string();
+
+    ldtk_tile_map
+        .get(&(tileset_identifier.clone(), TileSelector::TileId(tile_id)))
+        .or_else(|| {
+            enum_tag_values.iter().find_map(|enum_tag_value| {
+                ldtk_tile_map.get(&(
+                    tileset_identifier.clone(),
+                    TileSelector::EnumTag(enum_tag_value.clone()),
+                ))
+            })
+        })
+}
+
 /// Creates a [`Sprite`] with [`TextureAtlas`] from the entity information available to the
 /// [LdtkEntity::bundle_entity] method.
 ///
@@ -664,6 +948,50 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_ldtk_entity_map_get_or_default() {
+        let entity_instance = EntityInstance {
+            identifier: "Goblin".to_string(),
+            tags: vec!["Enemy".to_string(), "Melee".to_string()],
+            ..Default::default()
+        };
+
+        let mut map = HashMap::from([((None, None), 5)]);
+        let mut tag_map = HashMap::new();
+
+        let get = |map: &HashMap<_, _>, tag_map: &HashMap<_, _>| {
+            *ldtk_entity_map_get_or_default("Layer", &entity_instance, &0, map, tag_map)
+        };
+
+        assert_eq!(get(&map, &tag_map), 5);
+
+        map.insert((Some("Layer".to_string()), None), 4);
+        assert_eq!(get(&map, &tag_map), 4);
+
+        tag_map.insert("Melee".to_string(), 3);
+        assert_eq!(get(&map, &tag_map), 3);
+
+        tag_map.insert("Enemy".to_string(), 2);
+        assert_eq!(get(&map, &tag_map), 2);
+
+        map.insert((None, Some("Goblin".to_string())), 1);
+        assert_eq!(get(&map, &tag_map), 1);
+
+        map.insert((Some("Layer".to_string()), Some("Goblin".to_string())), 0);
+        assert_eq!(get(&map, &tag_map), 0);
+
+        assert_eq!(
+            *ldtk_entity_map_get_or_default(
+                "Layer",
+                &entity_instance,
+                &6,
+                &HashMap::new(),
+                &HashMap::new()
+            ),
+            6
+        );
+    }
+
     #[test]
     fn test_try_each_optional_permutation() {
         fn test_func(a: Option<i32>, b: Option<i32>) -> Option<i32> {
@@ -687,4 +1015,140 @@ mod tests {
         assert_eq!(try_each_optional_permutation(4, 4, test_func), Some(4));
         assert_eq!(try_each_optional_permutation(5, 5, test_func), Some(4));
     }
+
+    #[test]
+    fn test_ldtk_int_cell_map_get() {
+        let layer_definition = LayerDefinition {
+            int_grid_values: vec![
+                IntGridValueDefinition {
+                    value: 1,
+                    identifier: Some("Stone".to_string()),
+                    group_uid: 1,
+                    ..Default::default()
+                },
+                IntGridValueDefinition {
+                    value: 2,
+                    identifier: Some("Water".to_string()),
+                    ..Default::default()
+                },
+            ],
+            int_grid_values_groups: vec![IntGridValueGroupDefinition {
+                uid: 1,
+                identifier: Some("Solid".to_string()),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        let mut map = HashMap::new();
+        let mut identifier_map = HashMap::new();
+
+        let get = |value, map: &HashMap<_, _>, identifier_map: &HashMap<_, _>| {
+            ldtk_int_cell_map_get("Layer", value, Some(&layer_definition), map, identifier_map)
+                .copied()
+        };
+
+        assert_eq!(get(1, &map, &identifier_map), None);
+
+        map.insert((None, None), 6);
+        assert_eq!(get(1, &map, &identifier_map), Some(6));
+
+        map.insert((Some("Layer".to_string()), None), 5);
+        assert_eq!(get(1, &map, &identifier_map), Some(5));
+
+        identifier_map.insert(IntGridValueIdentifier::Group("Solid".to_string()), 4);
+        assert_eq!(get(1, &map, &identifier_map), Some(4));
+        assert_eq!(get(2, &map, &identifier_map), Some(5));
+
+        identifier_map.insert(IntGridValueIdentifier::Value("Stone".to_string()), 3);
+        assert_eq!(get(1, &map, &identifier_map), Some(3));
+
+        map.insert((None, Some(1)), 2);
+        assert_eq!(get(1, &map, &identifier_map), Some(2));
+
+        map.insert((Some("Layer".to_string()), Some(1)), 1);
+        assert_eq!(get(1, &map, &identifier_map), Some(1));
+
+        // identifiers can't be resolved without the layer definition
+        assert_eq!(
+            ldtk_int_cell_map_get("Layer", 2, None, &map, &identifier_map).copied(),
+            Some(5)
+        );
+    }
+
+    #[test]
+    fn test_ldtk_tile_map_get() {
+        let mut map = HashMap::new();
+        let enum_tag_values = ["Spikes".to_string(), "Solid".to_string()];
+
+        let get = |tile_id, map: &HashMap<_, _>| {
+            ldtk_tile_map_get("Tileset", tile_id, &enum_tag_values, map).copied()
+        };
+
+        assert_eq!(get(1, &map), None);
+
+        map.insert(("Other".to_string(), TileSelector::TileId(1)), 4);
+        assert_eq!(get(1, &map), None);
+
+        map.insert(
+            (
+                "Tileset".to_string(),
+                TileSelector::EnumTag("Solid".to_string()),
+            ),
+            3,
+        );
+        assert_eq!(get(1, &map), Some(3));
+
+        map.insert(
+            (
+                "Tileset".to_string(),
+                TileSelector::EnumTag("Spikes".to_string()),
+            ),
+            2,
+        );
+        assert_eq!(get(1, &map), Some(2));
+
+        map.insert(("Tileset".to_string(), TileSelector::TileId(1)), 1);
+        assert_eq!(get(1, &map), Some(1));
+        assert_eq!(get(2, &map), Some(2));
+
+        assert_eq!(ldtk_tile_map_get("Tileset", 2, &[], &map).copied(), None);
+    }
+
+    #[test]
+    fn test_merge_int_grid_cells() {
+        // LDtk orders int_grid_csv from the top-left, so the bottom row comes last
+        #[rustfmt::skip]
+        let int_grid_csv = vec![
+            1, 1, 0, 2,
+            1, 1, 0, 2,
+            0, 0, 0, 2,
+            3, 1, 1, 1,
+        ];
+
+        let merged = merge_int_grid_cells(&int_grid_csv, 4, 4, Some);
+
+        assert_eq!(merged.len(), 3);
+        assert_eq!(
+            merged[&1],
+            vec![IRect::new(1, 0, 4, 1), IRect::new(0, 2, 2, 4)]
+        );
+        assert_eq!(merged[&2], vec![IRect::new(3, 1, 4, 4)]);
+        assert_eq!(merged[&3], vec![IRect::new(0, 0, 1, 1)]);
+    }
+
+    #[test]
+    fn test_merge_int_grid_cells_with_grouped_keys() {
+        #[rustfmt::skip]
+        let int_grid_csv = vec![
+            1, 3, 2,
+            3, 1, 2,
+        ];
+
+        let merged =
+            merge_int_grid_cells(&int_grid_csv, 3, 2, |value| (value != 2).then_some("wall"));
+
+        assert_eq!(merged.len(), 1);
+        assert_eq!(merged["wall"], vec![IRect::new(0, 0, 2, 2)]);
+    }
 }